@@ -1,4 +1,6 @@
-//! Defines constant values for various colours.
+//! Defines constant values for various colours and functions for working with them.
+use std::error::Error;
+use std::fmt;
 
 pub type Colour = [f32; 4];
 pub const BLUE: Colour = [0.0, 0.0, 1.0, 1.0];
@@ -8,4 +10,425 @@ pub const YELLOW: Colour = [1.0, 1.0, 0.0, 1.0];
 pub const MAGENTA: Colour = [1.0, 0.0, 1.0, 1.0];
 pub const CYAN: Colour = [0.0, 1.0, 1.0, 1.0];
 pub const WHITE: Colour = [1.0, 1.0, 1.0, 1.0];
-pub const BLACK: Colour = [0.0, 0.0, 0.0, 1.0];
\ No newline at end of file
+pub const BLACK: Colour = [0.0, 0.0, 0.0, 1.0];
+
+/// The colours that the game is drawn in.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours::{self, Theme};
+///
+/// let theme = Theme { tile: colours::GREEN, ..Theme::default() };
+/// assert_eq!(theme.background, colours::BLUE);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Theme {
+    /// The colour the window is cleared to.
+    pub background: Colour,
+    /// The colour of the cell under the cursor.
+    pub highlight: Colour,
+    /// The outline of the cell where the next tile will be added.
+    pub preview: Colour,
+    pub tile: Colour,
+    /// The outline drawn just inside the edge of each tile.
+    pub tile_border: Colour,
+    pub wall: Colour,
+    pub cursor: Colour,
+    /// Text shown over the game, such as the points scored for a hit.
+    pub hud: Colour,
+    /// Text warning of something bad, such as a missed whack.
+    pub warning: Colour,
+    /// How much each new tile's colour is varied from `tile` to make them easier to tell
+    /// apart, see `vary`. The built in themes leave this at `0.0`, so tiles are only varied
+    /// when a theme opts in.
+    pub tile_variation: f32,
+}
+
+impl Default for Theme {
+    /// Returns the classic theme of red tiles on a blue background with a yellow cursor.
+    fn default() -> Theme {
+        Theme {
+            background: BLUE,
+            highlight: brighten([0.2, 0.2, 0.8, 1.0], 0.25),
+            preview: [1.0, 1.0, 1.0, 0.3],
+            tile: RED,
+            tile_border: darken(RED, 0.5),
+            wall: BLACK,
+            cursor: YELLOW,
+            hud: WHITE,
+            warning: RED,
+            tile_variation: 0.0,
+        }
+    }
+}
+
+impl Theme {
+    /// Returns a theme for deuteranopia, orange tiles and a sky blue cursor on a dark blue
+    /// background, avoiding telling things apart by red against green.
+    pub fn deuteranopia() -> Theme {
+        const ORANGE: Colour = [0.9, 0.62, 0.0, 1.0];
+        Theme {
+            background: [0.1, 0.12, 0.25, 1.0],
+            highlight: [0.25, 0.3, 0.5, 1.0],
+            preview: [1.0, 1.0, 1.0, 0.4],
+            tile: ORANGE,
+            tile_border: darken(ORANGE, 0.5),
+            wall: [0.02, 0.02, 0.05, 1.0],
+            cursor: [0.34, 0.71, 0.91, 1.0],
+            hud: WHITE,
+            warning: [0.94, 0.89, 0.26, 1.0],
+            tile_variation: 0.0,
+        }
+    }
+
+    /// Returns a theme for protanopia, which darkens reds, with bright yellow tiles and a sky
+    /// blue cursor on a near black background.
+    pub fn protanopia() -> Theme {
+        const TILE_YELLOW: Colour = [0.94, 0.89, 0.26, 1.0];
+        Theme {
+            background: [0.05, 0.05, 0.08, 1.0],
+            highlight: [0.2, 0.22, 0.3, 1.0],
+            preview: [1.0, 1.0, 1.0, 0.4],
+            tile: TILE_YELLOW,
+            tile_border: darken(TILE_YELLOW, 0.5),
+            wall: [0.3, 0.3, 0.3, 1.0],
+            cursor: [0.34, 0.71, 0.91, 1.0],
+            hud: WHITE,
+            warning: [0.9, 0.62, 0.0, 1.0],
+            tile_variation: 0.0,
+        }
+    }
+
+    /// Returns a theme that relies on brightness alone, with white tiles and a yellow cursor
+    /// on black.
+    pub fn high_contrast() -> Theme {
+        Theme {
+            background: BLACK,
+            highlight: [0.3, 0.3, 0.3, 1.0],
+            preview: [1.0, 1.0, 1.0, 0.6],
+            tile: WHITE,
+            tile_border: [0.5, 0.5, 0.5, 1.0],
+            wall: [0.4, 0.4, 0.4, 1.0],
+            cursor: YELLOW,
+            hud: WHITE,
+            warning: YELLOW,
+            tile_variation: 0.0,
+        }
+    }
+
+    /// Returns every built in theme, starting with the default, in the order they are cycled
+    /// through in game.
+    pub fn presets() -> [Theme; 4] {
+        [Theme::default(), Theme::deuteranopia(), Theme::protanopia(), Theme::high_contrast()]
+    }
+}
+
+/// Returns a brighter version of a `Colour` by scaling its RGB channels by `1.0 + amount`.
+///
+/// Channels are clamped to `[0.0, 1.0]` and the alpha channel is left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+///
+/// let c = colours::brighten([0.5, 0.2, 0.0, 0.5], 0.5);
+/// assert_eq!(c, [0.75, 0.3, 0.0, 0.5]);
+/// ```
+pub fn brighten(c: Colour, amount: f32) -> Colour {
+    scale_rgb(c, 1.0 + amount)
+}
+
+/// Returns a darker version of a `Colour` by scaling its RGB channels by `1.0 - amount`.
+///
+/// Channels are clamped to `[0.0, 1.0]` and the alpha channel is left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+///
+/// let c = colours::darken([0.5, 1.0, 0.0, 0.5], 0.5);
+/// assert_eq!(c, [0.25, 0.5, 0.0, 0.5]);
+/// ```
+pub fn darken(c: Colour, amount: f32) -> Colour {
+    scale_rgb(c, 1.0 - amount)
+}
+
+/// Returns a lighter version of a `Colour` by moving its RGB channels `amount` of the way
+/// towards white.
+///
+/// Unlike `brighten`, this lightens black and pure colours too. `amount` is clamped to
+/// `[0.0, 1.0]` and the alpha channel is left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+///
+/// let c = colours::lighten([0.5, 0.0, 1.0, 0.5], 0.5);
+/// assert_eq!(c, [0.75, 0.5, 1.0, 0.5]);
+/// ```
+pub fn lighten(c: Colour, amount: f32) -> Colour {
+    with_alpha(lerp(c, WHITE, amount), c[3])
+}
+
+/// Returns `c` with its alpha channel set to `alpha`, clamped to `[0.0, 1.0]`.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+///
+/// assert_eq!(colours::with_alpha(colours::RED, 0.25), [1.0, 0.0, 0.0, 0.25]);
+/// ```
+pub fn with_alpha(c: Colour, alpha: f32) -> Colour {
+    [c[0], c[1], c[2], clamp_channel(alpha)]
+}
+
+/// Returns the colour `t` of the way from `a` to `b`, blending every channel including alpha.
+///
+/// `t` is clamped to `[0.0, 1.0]`, so the result is never beyond either colour.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+///
+/// let c = colours::lerp(colours::BLACK, [1.0, 0.5, 0.0, 0.0], 0.5);
+/// assert_eq!(c, [0.5, 0.25, 0.0, 0.5]);
+/// ```
+pub fn lerp(a: Colour, b: Colour, t: f32) -> Colour {
+    let t = clamp_channel(t);
+    let mut c = a;
+    for (channel, &end) in c.iter_mut().zip(&b) {
+        *channel += (end - *channel) * t;
+    }
+    c
+}
+
+/// An error raised when a `Colour` cannot be read from a hex string, see `from_hex`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ParseColourError {
+    /// The string has this many hex digits rather than 6 or 8.
+    InvalidLength(usize),
+    /// The string contains a character that is not a hex digit.
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseColourError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseColourError::InvalidLength(n) => {
+                write!(f, "a hex colour needs 6 or 8 digits, got {}", n)
+            }
+            ParseColourError::InvalidDigit(c) => write!(f, "{:?} is not a hex digit", c),
+        }
+    }
+}
+
+impl Error for ParseColourError {
+    fn description(&self) -> &str {
+        match *self {
+            ParseColourError::InvalidLength(_) => "a hex colour needs 6 or 8 digits",
+            ParseColourError::InvalidDigit(_) => "invalid hex digit in colour",
+        }
+    }
+}
+
+/// Reads a `Colour` from a hex string such as `"#ff8000"`, as used in config files.
+///
+/// The leading `#` is optional and two more digits can be given for alpha, otherwise the
+/// colour is opaque.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours::{self, ParseColourError};
+///
+/// assert_eq!(colours::from_hex("#ff0000"), Ok(colours::RED));
+/// assert_eq!(colours::from_hex("00ff0000"), Ok([0.0, 1.0, 0.0, 0.0]));
+/// assert_eq!(colours::from_hex("#fff"), Err(ParseColourError::InvalidLength(3)));
+/// ```
+pub fn from_hex(hex: &str) -> Result<Colour, ParseColourError> {
+    let digits = if hex.starts_with('#') { &hex[1..] } else { hex };
+    if let Some(c) = digits.chars().find(|c| !c.is_digit(16)) {
+        return Err(ParseColourError::InvalidDigit(c));
+    }
+    if (digits.len() != 6) && (digits.len() != 8) {
+        return Err(ParseColourError::InvalidLength(digits.len()));
+    }
+    let mut colour = WHITE;
+    for (i, channel) in colour.iter_mut().enumerate().take(digits.len() / 2) {
+        let byte = u8::from_str_radix(&digits[(2 * i)..(2 * i) + 2], 16)
+            .expect("digits were checked");
+        *channel = byte as f32 / 255.0;
+    }
+    Ok(colour)
+}
+
+/// Returns `base` with its colour shifted by a pseudo-random amount derived from `seed`, so
+/// that the same seed always gives the same colour.
+///
+/// Each RGB channel moves by up to `amount`, which changes both the hue and the brightness
+/// slightly. Channels are clamped to `[0.0, 1.0]` and the alpha channel is left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+///
+/// let a = colours::vary(colours::RED, 7, 0.1);
+/// assert_eq!(a, colours::vary(colours::RED, 7, 0.1));
+/// assert!((a[0] - 1.0).abs() <= 0.1 && a[1] <= 0.1);
+/// assert_eq!(colours::vary(colours::RED, 7, 0.0), colours::RED);
+/// ```
+pub fn vary(base: Colour, seed: u64, amount: f32) -> Colour {
+    let mut state = seed;
+    let mut c = base;
+    for channel in c.iter_mut().take(3) {
+        let offset = (unit_noise(&mut state) * 2.0) - 1.0;
+        *channel = clamp_channel(*channel + (offset * amount));
+    }
+    c
+}
+
+/// Advances `state` and returns a number in `[0.0, 1.0]` derived from it, using the splitmix64
+/// generator.
+fn unit_noise(state: &mut u64) -> f32 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    (z >> 40) as f32 / ((1u64 << 24) - 1) as f32
+}
+
+/// A tolerance for `approx_eq` that absorbs float rounding but is far smaller than any
+/// visible difference, a single step of an 8-bit channel being about `0.004`.
+pub const EPSILON: f32 = 1e-5;
+
+/// Tests if every channel of two colours, including alpha, differs by at most `eps`.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+///
+/// let c = colours::brighten(colours::darken([0.3, 0.6, 0.9, 1.0], 0.1), 0.1);
+/// assert!(colours::approx_eq(c, [0.297, 0.594, 0.891, 1.0], colours::EPSILON));
+/// assert!(!colours::approx_eq(c, colours::WHITE, colours::EPSILON));
+/// ```
+pub fn approx_eq(a: Colour, b: Colour, eps: f32) -> bool {
+    a.iter().zip(&b).all(|(x, y)| (x - y).abs() <= eps)
+}
+
+/// Multiplies the RGB channels of a `Colour` by `factor`, clamping the result.
+fn scale_rgb(c: Colour, factor: f32) -> Colour {
+    [clamp_channel(c[0] * factor),
+     clamp_channel(c[1] * factor),
+     clamp_channel(c[2] * factor),
+     c[3]]
+}
+
+/// Clamps a single colour channel to `[0.0, 1.0]`.
+fn clamp_channel(v: f32) -> f32 {
+    v.max(0.0).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brighten_white() {
+        assert_eq!(brighten(WHITE, 0.5), WHITE);
+    }
+
+    #[test]
+    fn darken_black() {
+        assert_eq!(darken(BLACK, 0.5), BLACK);
+    }
+
+    #[test]
+    fn alpha_preserved() {
+        let c = [0.5, 0.5, 0.5, 0.3];
+        assert_eq!(brighten(c, 0.2)[3], 0.3);
+        assert_eq!(darken(c, 0.2)[3], 0.3);
+        assert_eq!(darken(c, 2.0), [0.0, 0.0, 0.0, 0.3]);
+    }
+
+    #[test]
+    fn hex_colours() {
+        assert_eq!(from_hex("#000000"), Ok(BLACK));
+        assert_eq!(from_hex("FFFFFF"), Ok(WHITE));
+        assert_eq!(from_hex("#0000ff"), Ok(BLUE));
+        let c = from_hex("#336699cc").unwrap();
+        assert!(approx_eq(c, [0.2, 0.4, 0.6, 0.8], EPSILON));
+        assert_eq!(from_hex("#33669"), Err(ParseColourError::InvalidLength(5)));
+        assert_eq!(from_hex("3366990"), Err(ParseColourError::InvalidLength(7)));
+        assert_eq!(from_hex(""), Err(ParseColourError::InvalidLength(0)));
+        assert_eq!(from_hex("#"), Err(ParseColourError::InvalidLength(0)));
+        assert_eq!(from_hex("##336699"), Err(ParseColourError::InvalidDigit('#')));
+        assert_eq!(from_hex("#33g699"), Err(ParseColourError::InvalidDigit('g')));
+        assert_eq!(from_hex("#+12345"), Err(ParseColourError::InvalidDigit('+')));
+        assert_eq!(from_hex("#33669é"), Err(ParseColourError::InvalidDigit('é')));
+    }
+
+    #[test]
+    fn blending_clamps() {
+        assert_eq!(with_alpha(RED, 1.5), RED);
+        assert_eq!(with_alpha(RED, -0.5), [1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(lerp(RED, BLUE, 0.0), RED);
+        assert_eq!(lerp(RED, BLUE, 1.0), BLUE);
+        assert_eq!(lerp(RED, BLUE, 2.0), BLUE);
+        assert_eq!(lerp(RED, BLUE, -1.0), RED);
+        assert_eq!(lighten(BLACK, 1.0), WHITE);
+        assert_eq!(lighten(BLACK, 3.0), WHITE);
+        assert_eq!(lighten(RED, -1.0), RED);
+        assert_eq!(lighten(with_alpha(BLACK, 0.5), 0.5), [0.5, 0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn presets_are_distinct() {
+        let presets = Theme::presets();
+        assert_eq!(presets[0], Theme::default());
+        for (i, a) in presets.iter().enumerate() {
+            assert!(a.tile != a.background && a.cursor != a.tile && a.wall != a.background);
+            assert!(presets[(i + 1)..].iter().all(|b| a != b));
+        }
+    }
+
+    #[test]
+    fn vary_is_deterministic() {
+        for seed in 0..100 {
+            let c = vary([0.5, 0.5, 0.5, 0.7], seed, 0.2);
+            assert_eq!(c, vary([0.5, 0.5, 0.5, 0.7], seed, 0.2));
+            assert!(c[..3].iter().all(|&x| (x - 0.5).abs() <= 0.2 + EPSILON));
+            assert_eq!(c[3], 0.7);
+        }
+        assert!(vary(RED, 1, 0.2) != vary(RED, 2, 0.2));
+    }
+
+    #[test]
+    fn vary_stays_in_range() {
+        for seed in 0..200 {
+            for &base in &[WHITE, BLACK, RED, CYAN] {
+                let c = vary(base, seed, 5.0);
+                assert!(c.iter().all(|&x| (x >= 0.0) && (x <= 1.0)));
+            }
+        }
+        assert_eq!(vary(WHITE, u64::max_value(), 0.0), WHITE);
+    }
+
+    #[test]
+    fn approx_eq_tolerance() {
+        let c = [0.25, 0.5, 0.75, 1.0];
+        assert!(approx_eq(c, c, 0.0));
+        assert!(approx_eq(c, [0.25, 0.5 + 1e-7, 0.75, 1.0], EPSILON));
+        assert!(approx_eq(c, [0.25 - 1e-7, 0.5, 0.75, 1.0 - 1e-7], EPSILON));
+        assert!(!approx_eq(c, [0.25, 0.5, 0.85, 1.0], EPSILON));
+        assert!(!approx_eq(c, [0.25, 0.5, 0.75, 0.9], EPSILON));
+    }
+}
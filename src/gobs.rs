@@ -1,26 +1,94 @@
 //! Contains code for various different game objects used in **Whack!**
 extern crate graphics;
+extern crate num_traits;
 extern crate rand;
 
-use rand::sample;
-use colours::{Colour, RED};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::ops::{self, Index};
+use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use rand::{sample, Rng, SeedableRng, StdRng};
+use graphics::Transformed;
+use graphics::math::Matrix2d;
+use num_traits::Float;
+use colours::{self, Colour, Theme, BLACK, WHITE};
+
+/// The number of spawn positions that the `Board` schedules in advance.
+const SPAWN_QUEUE_LENGTH: usize = 3;
+
+/// The time in seconds that a removed tile takes to fade out.
+pub const FADE_DURATION: f64 = 0.25;
+
+/// The most removed tiles that a `Board` keeps fading at once.
+pub const MAX_FADING_TILES: usize = 16;
+
+/// How much darker a tile is drawn for each whack it needs after the next, see
+/// `Tile::hits_required`.
+pub const EXTRA_HIT_DARKEN: f32 = 0.25;
+
+/// The thickness of the darker border drawn around each tile.
+const TILE_BORDER_THICKNESS: f64 = 3.0;
+
+/// The time in seconds that a `Label` is shown for.
+pub const LABEL_DURATION: f64 = 0.75;
+
+/// The speed in pixels per second that a `Label` drifts upwards at.
+pub const LABEL_SPEED: f64 = 40.0;
+
+/// The id given to the next tile added to any `Board`, see `Sprite::id`.
+static NEXT_TILE_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// The characters used for each kind of cell by `Board::to_code`.
+const CODE_EMPTY: char = '.';
+const CODE_TILE: char = 'X';
+const CODE_WALL: char = '#';
+const CODE_BLOCKED: char = '-';
+const CODE_ROW_SEPARATOR: char = '/';
+const CODE_HEX: char = 'H';
 
 /// Represents two-dimensional vector.
+///
+/// Components are `f64` by default, which is what `Sprite` and `Board` use, but any `Float`
+/// such as `f32` can be used. Convert between the two with `From` and `Into`.
+///
+/// # Examples
+///
+/// ```
+/// use whack::gobs::Vec2D;
+///
+/// let small: Vec2D<f32> = Vec2D::new(3.0, 4.0);
+/// assert_eq!(small.length(), 5.0);
+/// let pos: Vec2D = small.into();
+/// assert_eq!(pos, Vec2D::new(3.0, 4.0));
+/// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Vec2D {
-    pub x: f64,
-    pub y: f64,
+pub struct Vec2D<T = f64> {
+    pub x: T,
+    pub y: T,
 }
 
 impl Vec2D {
+    /// Returns an new `Vec2D` instance where `x` and `y` are equal to `0.0`.
+    ///
+    /// This is only for the default `f64` components, so that it needs no type annotations,
+    /// use `zero` for other scalars.
+    pub fn empty() -> Vec2D {
+        Vec2D { x: 0.0, y: 0.0 }
+    }
+}
+
+impl<T: Float> Vec2D<T> {
     /// Returns a new `Vec2D` instance.
-    pub fn new(x: f64, y: f64) -> Vec2D {
+    pub fn new(x: T, y: T) -> Vec2D<T> {
         Vec2D { x: x, y: y }
     }
 
-    /// Returns an new `Vec2D` instance where `x` and `y` are equal to `0.0`.
-    pub fn empty() -> Vec2D {
-        Vec2D { x: 0.0, y: 0.0 }
+    /// Returns a new `Vec2D` instance where `x` and `y` are equal to zero.
+    pub fn zero() -> Vec2D<T> {
+        Vec2D::new(T::zero(), T::zero())
     }
 
     /// Updates the fields of the `Vec2D` by pairwise addition of another instance.
@@ -28,292 +96,5080 @@ impl Vec2D {
     /// # Examples
     ///
     /// ```
+    /// # #![allow(deprecated)]
     /// use whack::gobs::Vec2D;
     ///
     /// let mut v1 = Vec2D::new(10.0, -13.2);
     /// let v2 = Vec2D::new(-57.2, -99.3);
     /// v1.add(v2);
     /// ```
-    pub fn add(&mut self, other: Vec2D) {
-        self.x += other.x;
-        self.y += other.y;
+    #[deprecated(since = "0.2.0", note = "use the `+=` operator instead")]
+    pub fn add(&mut self, other: Vec2D<T>) {
+        *self += other;
     }
-}
 
-/// Represents a sprite that can be rendered.
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Sprite {
-    pub pos: Vec2D,
-    pub width: f64,
-    pub height: f64,
-    pub colour: Colour,
-}
+    /// Returns the length of the `Vec2D`.
+    pub fn length(&self) -> T {
+        self.length_squared().sqrt()
+    }
 
-impl Sprite {
-    /// Returns a tile struct.
+    /// Returns the square of the length of the `Vec2D`, which avoids a square root when only
+    /// comparing lengths.
+    pub fn length_squared(&self) -> T {
+        self.dot(*self)
+    }
+
+    /// Returns the distance between the points `self` and `other`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use whack::colours;
-    /// use whack::gobs::Sprite;
+    /// use whack::gobs::Vec2D;
     ///
-    /// let tile = Sprite::new(100.0, 100.0, 50.0, 50.0, colours::BLUE);
+    /// assert_eq!(Vec2D::new(1.0, 1.0).distance(Vec2D::new(4.0, 5.0)), 5.0);
     /// ```
-    pub fn new(x: f64, y: f64, width: f64, height: f64, colour: Colour) -> Sprite {
-        Sprite {
-            pos: Vec2D { x: x, y: y },
-            width: width,
-            height: height,
-            colour: colour,
+    pub fn distance(&self, other: Vec2D<T>) -> T {
+        (*self - other).length()
+    }
+
+    /// Returns a `Vec2D` with the same direction and a length of `1.0`.
+    ///
+    /// The zero vector has no direction, so it is returned unchanged rather than dividing by
+    /// zero and producing NaNs.
+    pub fn normalized(&self) -> Vec2D<T> {
+        let length = self.length();
+        if length == T::zero() {
+            return Vec2D::zero();
         }
+        *self / length
     }
 
-    /// Creates a rect type array from the `Sprite`.
+    /// Returns the dot product of `self` and `other`.
+    pub fn dot(&self, other: Vec2D<T>) -> T {
+        (self.x * other.x) + (self.y * other.y)
+    }
+
+    /// Linearly interpolates from `a` to `b`, returning `a` when `t` is `0.0` and `b` when
+    /// `t` is `1.0`.
+    ///
+    /// `t` is clamped to the range `[0.0, 1.0]`, so the result never overshoots either end.
     ///
     /// # Examples
     ///
     /// ```
-    /// use whack::colours;
-    /// use whack::gobs::Sprite;
+    /// use whack::gobs::Vec2D;
     ///
-    /// let tile = Sprite::new(100.0, 100.0, 50.0, 50.0, colours::GREEN);
-    /// assert_eq!([tile.pos.x, tile.pos.y, tile.width, tile.height], tile.get_rect())
-    pub fn get_rect(&self) -> [f64; 4] {
-        [self.pos.x, self.pos.y, self.width, self.height]
+    /// let a = Vec2D::new(0.0, 10.0);
+    /// let b = Vec2D::new(10.0, 20.0);
+    /// assert_eq!(Vec2D::lerp(a, b, 0.25), Vec2D::new(2.5, 12.5));
+    /// assert_eq!(Vec2D::lerp(a, b, 2.0), b);
+    /// ```
+    pub fn lerp(a: Vec2D<T>, b: Vec2D<T>, t: T) -> Vec2D<T> {
+        let t = t.max(T::zero()).min(T::one());
+        a + ((b - a) * t)
     }
 
-    /// Tests if the `Sprite` overlaps with a reference `Sprite`.
+    /// Returns a copy of the `Vec2D` with each component clamped between the matching
+    /// components of `min` and `max`.
+    ///
+    /// If a component of `min` is greater than the same component of `max`, `min` wins.
     ///
     /// # Examples
     ///
     /// ```
-    /// use whack::gobs::Sprite;
-    /// use whack::colours;
+    /// use whack::gobs::Vec2D;
     ///
-    /// let s1 = Sprite::new(100.0, 100.0, 50.0, 50.0, colours::YELLOW);
-    /// let s2 = Sprite::new(125.0, 100.0, 50.0, 50.0, colours::YELLOW);
-    /// let s3 = Sprite::new(155.0, 100.0, 50.0, 50.0, colours::YELLOW);
-    /// assert!(s1.is_overlapping(&s2));
-    /// assert!(!s1.is_overlapping(&s3));
-    /// assert!(s2.is_overlapping(&s3));
+    /// let v = Vec2D::new(-5.0, 50.0).clamped(Vec2D::new(0.0, 0.0), Vec2D::new(10.0, 10.0));
+    /// assert_eq!(v, Vec2D::new(0.0, 10.0));
     /// ```
-    pub fn is_overlapping(&self, other: &Sprite) -> bool {
-        if (self.pos.x + self.width < other.pos.x) || (other.pos.x + other.width < self.pos.x) ||
-           (self.pos.y + self.height < other.pos.y) ||
-           (other.pos.y + other.height < self.pos.y) {
-            return false;
-        }
-        true
+    pub fn clamped(self, min: Vec2D<T>, max: Vec2D<T>) -> Vec2D<T> {
+        self.min(max).max(min)
     }
-}
 
-/// Represents the game board.
-#[derive(Debug, PartialEq)]
-pub struct Board {
-    pub tiles: Tiles,
-    pub length: f64,
-}
+    /// Clamps each component of the `Vec2D` in place between the matching components of
+    /// `min` and `max`, as `clamped` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Vec2D;
+    ///
+    /// let mut pos = Vec2D::new(120.0, -4.0);
+    /// pos.clamp(Vec2D::new(0.0, 0.0), Vec2D::new(100.0, 100.0));
+    /// assert_eq!(pos, Vec2D::new(100.0, 0.0));
+    /// ```
+    pub fn clamp(&mut self, min: Vec2D<T>, max: Vec2D<T>) {
+        *self = self.clamped(min, max);
+    }
 
-impl Board {
-    /// Returns a Board struct with an empty Tiles array
+    /// Returns the component-wise minimum of `self` and `other`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use whack::gobs::Board;
+    /// use whack::gobs::Vec2D;
     ///
-    /// let board = Board::from_length(300.0);
+    /// let v = Vec2D::new(1.0, 8.0).min(Vec2D::new(3.0, 2.0));
+    /// assert_eq!(v, Vec2D::new(1.0, 2.0));
     /// ```
-    pub fn from_length(length: f64) -> Board {
-        Board {
-            tiles: [None; 9],
-            length: length,
-        }
+    pub fn min(self, other: Vec2D<T>) -> Vec2D<T> {
+        Vec2D::new(self.x.min(other.x), self.y.min(other.y))
     }
 
-    /// Returns a vector containing the indices of all the free positions on the `Board`.
-    pub fn free_positions(&self) -> Vec<usize> {
-        let positions: Vec<usize> = self.tiles
-            .iter()
-            .enumerate()
-            .filter(|t| t.1.is_none())
-            .map(|t| t.0)
-            .collect();
-        positions
+    /// Returns the component-wise maximum of `self` and `other`.
+    pub fn max(self, other: Vec2D<T>) -> Vec2D<T> {
+        Vec2D::new(self.x.max(other.x), self.y.max(other.y))
     }
+}
 
-    /// True if there are no free positions on the `Board`.
-    pub fn is_full(&self) -> bool {
-        if self.free_positions().is_empty() {
-            true
-        } else {
-            false
-        }
+/// Pairwise addition.
+///
+/// # Examples
+///
+/// ```
+/// use whack::gobs::Vec2D;
+///
+/// let mut pos = Vec2D::new(10.0, 20.0);
+/// pos += Vec2D::new(1.0, -2.0) * 2.0;
+/// assert_eq!(pos, Vec2D::new(12.0, 16.0));
+/// assert_eq!(pos - Vec2D::new(2.0, 6.0), Vec2D::new(10.0, 10.0));
+/// assert_eq!(-pos / 4.0, Vec2D::new(-3.0, -4.0));
+/// ```
+impl<T: Float> ops::Add for Vec2D<T> {
+    type Output = Vec2D<T>;
+
+    fn add(self, other: Vec2D<T>) -> Vec2D<T> {
+        Vec2D::new(self.x + other.x, self.y + other.y)
     }
+}
 
-    /// Adds a tile to a random position on the `Board`.
-    pub fn add_tile(&mut self) {
-        let new_pos = self.random_position();
-        if let Some(i) = new_pos {
-            let new_tile = Sprite::new(self.x_from_index(i),
-                                       self.y_from_index(i),
-                                       self.length / 3.0,
-                                       self.length / 3.0,
-                                       RED);
-            self.tiles[i] = Some(new_tile);
-        }
+/// Pairwise subtraction.
+impl<T: Float> ops::Sub for Vec2D<T> {
+    type Output = Vec2D<T>;
+
+    fn sub(self, other: Vec2D<T>) -> Vec2D<T> {
+        Vec2D::new(self.x - other.x, self.y - other.y)
     }
+}
 
-    /// Generates a random index if the `Board` is not full.
-    fn random_position(&self) -> Option<usize> {
-        let free_positions = self.free_positions();
-        if free_positions.is_empty() {
-            return None;
-        }
-        let mut rng = rand::thread_rng();
-        let sample = sample(&mut rng, free_positions.into_iter(), 1);
-        Some(sample[0])
+/// Negates both components.
+impl<T: Float> ops::Neg for Vec2D<T> {
+    type Output = Vec2D<T>;
+
+    fn neg(self) -> Vec2D<T> {
+        Vec2D::new(-self.x, -self.y)
     }
+}
 
-    /// Calculates the x coordinate of a position on the `Board` from its index.
-    pub fn x_from_index(&self, i: usize) -> f64 {
-        let tile_length = self.length / 3.0;
-        ((i as f64 % 3.0) * tile_length)
+/// Multiplies both components by a scalar.
+impl<T: Float> ops::Mul<T> for Vec2D<T> {
+    type Output = Vec2D<T>;
+
+    fn mul(self, scalar: T) -> Vec2D<T> {
+        Vec2D::new(self.x * scalar, self.y * scalar)
     }
+}
 
-    /// Calculates the y coordinate of a position on the `Board` from its index.
-    pub fn y_from_index(&self, i: usize) -> f64 {
-        let tile_length = self.length / 3.0;
-        ((i as f64 / 3.0).floor() * tile_length)
+/// Divides both components by a scalar.
+impl<T: Float> ops::Div<T> for Vec2D<T> {
+    type Output = Vec2D<T>;
+
+    fn div(self, scalar: T) -> Vec2D<T> {
+        Vec2D::new(self.x / scalar, self.y / scalar)
     }
+}
 
-    /// Removes all tiles from the `Board`.
-    pub fn clear_board(&mut self) {
-        self.tiles = [None; 9];
+impl<T: Float> ops::AddAssign for Vec2D<T> {
+    fn add_assign(&mut self, other: Vec2D<T>) {
+        *self = *self + other;
     }
 }
 
-/// Array that represents the tile positions of the game `Board`.
-pub type Tiles = [Option<Sprite>; 9];
+impl<T: Float> ops::SubAssign for Vec2D<T> {
+    fn sub_assign(&mut self, other: Vec2D<T>) {
+        *self = *self - other;
+    }
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use colours;
+impl<T: Float> ops::MulAssign<T> for Vec2D<T> {
+    fn mul_assign(&mut self, scalar: T) {
+        *self = *self * scalar;
+    }
+}
 
-    #[test]
-    fn add_tile() {
-        let mut board = Board::from_length(300.0);
-        board.add_tile();
-        let is_some_array: Vec<bool> = board.tiles.iter().map(|x| x.is_some()).collect();
-        assert!(is_some_array.contains(&true));
+impl<T: Float> ops::DivAssign<T> for Vec2D<T> {
+    fn div_assign(&mut self, scalar: T) {
+        *self = *self / scalar;
     }
+}
 
-    #[test]
-    fn free_positions() {
-        let mut board = Board::from_length(300.0);
-        board.add_tile();
-        assert_eq!(board.free_positions().len(), 8);
+impl From<Vec2D<f32>> for Vec2D<f64> {
+    fn from(v: Vec2D<f32>) -> Vec2D<f64> {
+        Vec2D::new(v.x as f64, v.y as f64)
     }
+}
 
-    #[test]
-    fn clear_board() {
-        let mut board = Board::from_length(300.0);
-        for _ in 0..8 {
-            board.add_tile();
-        }
-        assert!(!board.is_full());
-        board.add_tile();
-        assert!(board.is_full());
-        board.clear_board();
-        assert!(!board.is_full());
+/// Narrows each component to `f32`, losing precision beyond what an `f32` can hold.
+impl From<Vec2D<f64>> for Vec2D<f32> {
+    fn from(v: Vec2D<f64>) -> Vec2D<f32> {
+        Vec2D::new(v.x as f32, v.y as f32)
     }
+}
 
-    #[test]
-    fn is_overlapping() {
-        let window_size = 300.0;
-        let mut board = Board::from_length(window_size);
-        let mut cursor = Sprite::new(window_size / 2.0,
-                                     window_size / 2.0,
-                                     window_size / 16.0,
-                                     window_size / 16.0,
-                                     colours::YELLOW);
-        for _ in 0..9 {
-            board.add_tile();
-        }
-        let overlapping: Vec<bool> = board.tiles
-            .iter()
-            .map(|x| x.unwrap())
-            .map(|x| cursor.is_overlapping(&x))
-            .collect();
-        assert_eq!(overlapping,
-                   [false, false, false, false, true, false, false, false, false]);
-        cursor.pos.x -= 100.0;
-        let overlapping: Vec<bool> = board.tiles
-            .iter()
-            .map(|x| x.unwrap())
-            .map(|x| cursor.is_overlapping(&x))
-            .collect();
-        assert_eq!(overlapping,
-                   [false, false, false, true, false, false, false, false, false]);
-        cursor.pos.y -= 100.0;
-        let overlapping: Vec<bool> = board.tiles
-            .iter()
-            .map(|x| x.unwrap())
-            .map(|x| cursor.is_overlapping(&x))
-            .collect();
-        assert_eq!(overlapping,
-                   [true, false, false, false, false, false, false, false, false]);
+/// An axis-aligned bounding box, spanning from its `min` corner to its `max` corner.
+///
+/// # Examples
+///
+/// ```
+/// use whack::gobs::{Aabb, Vec2D};
+///
+/// let cell = Aabb::from_pos_size(Vec2D::new(100.0, 0.0), 100.0, 100.0);
+/// let cursor = Aabb::from_pos_size(Vec2D::new(190.0, 40.0), 20.0, 20.0);
+/// assert!(cell.intersects(&cursor));
+/// assert_eq!(cell.intersection(&cursor).unwrap().to_rect(), [190.0, 40.0, 10.0, 20.0]);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Aabb {
+    pub min: Vec2D,
+    pub max: Vec2D,
+}
+
+impl Aabb {
+    /// Returns an `Aabb` spanning from `min` to `max`.
+    pub fn new(min: Vec2D, max: Vec2D) -> Aabb {
+        Aabb { min: min, max: max }
     }
 
-    #[test]
-    fn move_cursor() {
-        let window_size = 300.0;
-        let mut cursor = Sprite::new(window_size / 2.0,
-                                     window_size / 2.0,
-                                     window_size / 16.0,
-                                     window_size / 16.0,
-                                     colours::YELLOW);
-        cursor.pos.add(Vec2D {
-            x: -100.0,
-            y: 0.0,
-        });
-        assert_eq!(cursor.pos.x, 50.0);
-        assert_eq!(cursor.pos.y, 150.0);
-        cursor.pos.add(Vec2D {
-            x: 100.0,
-            y: 100.0,
-        });
-        assert_eq!(cursor.pos.x, 150.0);
-        assert_eq!(cursor.pos.y, 250.0);
+    /// Returns an `Aabb` with its top left corner at `pos`.
+    pub fn from_pos_size(pos: Vec2D, width: f64, height: f64) -> Aabb {
+        Aabb::new(pos, Vec2D::new(pos.x + width, pos.y + height))
     }
 
-    #[test]
-    fn gen_random_index() {
-        let board = Board::from_length(300.0);
-        for _ in 1..10 {
-            if let Some(i) = board.random_position() {
-                assert!(i <= 8);
-            }
+    /// Returns the `Aabb` of a `[x, y, width, height]` rectangle.
+    pub fn from_rect(rect: [f64; 4]) -> Aabb {
+        Aabb::from_pos_size(Vec2D::new(rect[0], rect[1]), rect[2], rect[3])
+    }
+
+    /// Returns the `Aabb` as the `[x, y, width, height]` rectangle used by `graphics`.
+    pub fn to_rect(&self) -> [f64; 4] {
+        [self.min.x, self.min.y, self.width(), self.height()]
+    }
+
+    /// Returns the distance from the left edge of the `Aabb` to its right edge.
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    /// Returns the distance from the top edge of the `Aabb` to its bottom edge.
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    /// Returns the area of the `Aabb`, which is `0.0` if it has no width or height.
+    pub fn area(&self) -> f64 {
+        self.width().max(0.0) * self.height().max(0.0)
+    }
+
+    /// Returns the point halfway between the corners of the `Aabb`.
+    pub fn center(&self) -> Vec2D {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Tests if two boxes overlap.
+    ///
+    /// Boxes that only touch along an edge or at a corner do not intersect.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        (self.min.x < other.max.x) && (other.min.x < self.max.x) &&
+        (self.min.y < other.max.y) && (other.min.y < self.max.y)
+    }
+
+    /// Returns the box covered by both boxes, or `None` if they do not intersect.
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        if !self.intersects(other) {
+            return None;
         }
+        Some(Aabb::new(Vec2D::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+                       Vec2D::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y))))
     }
 
-    #[test]
-    fn check_x_from_i() {
-        let board = Board::from_length(300.0);
-        assert_eq!(board.x_from_index(0), 0.0);
-        assert_eq!(board.x_from_index(1), 100.0);
-        assert_eq!(board.x_from_index(2), 200.0);
-        assert_eq!(board.x_from_index(8), 200.0);
+    /// Returns the smallest box covering both boxes.
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb::new(Vec2D::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+                  Vec2D::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)))
     }
 
-    #[test]
-    fn check_y_from_i() {
-        let board = Board::from_length(300.0);
-        assert_eq!(board.y_from_index(0), 0.0);
-        assert_eq!(board.y_from_index(1), 0.0);
-        assert_eq!(board.y_from_index(2), 0.0);
-        assert_eq!(board.y_from_index(8), 200.0);
+    /// Tests if a point lies within the `Aabb`, counting points on its edges as inside.
+    pub fn contains(&self, point: Vec2D) -> bool {
+        (point.x >= self.min.x) && (point.x <= self.max.x) &&
+        (point.y >= self.min.y) && (point.y <= self.max.y)
+    }
+
+    /// Tests if a point lies within the `Aabb`, counting points on its left and top edges as
+    /// inside but not those on its right and bottom edges.
+    pub fn contains_half_open(&self, point: Vec2D) -> bool {
+        (point.x >= self.min.x) && (point.x < self.max.x) &&
+        (point.y >= self.min.y) && (point.y < self.max.y)
+    }
+
+    /// Returns the point in the `Aabb` nearest to `point`.
+    pub fn nearest_point(&self, point: Vec2D) -> Vec2D {
+        point.clamped(self.min, self.max)
+    }
+
+    /// Returns the `Aabb` shrunk by `margin` on every side, or grown if `margin` is negative,
+    /// about the same centre.
+    ///
+    /// A box shrunk by more than half its width or height collapses to zero size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{Aabb, Vec2D};
+    ///
+    /// let cell = Aabb::from_rect([0.0, 0.0, 100.0, 50.0]);
+    /// assert_eq!(cell.inset(10.0).to_rect(), [10.0, 10.0, 80.0, 30.0]);
+    /// assert_eq!(cell.inset(30.0).to_rect(), [30.0, 25.0, 40.0, 0.0]);
+    /// ```
+    pub fn inset(&self, margin: f64) -> Aabb {
+        let center = self.center();
+        let half_width = ((self.width() / 2.0) - margin).max(0.0);
+        let half_height = ((self.height() / 2.0) - margin).max(0.0);
+        let half = Vec2D::new(half_width, half_height);
+        Aabb::new(center - half, center + half)
+    }
+
+    /// Returns the `Aabb` moved the shortest distance needed to lie within `bounds`.
+    ///
+    /// A box that is wider or taller than `bounds` is aligned with its left or top edge.
+    pub fn moved_within(&self, bounds: &Aabb) -> Aabb {
+        let size = self.max - self.min;
+        let min = self.min.clamped(bounds.min, bounds.max - size);
+        Aabb::new(min, min + size)
+    }
+}
+
+impl From<[f64; 4]> for Aabb {
+    fn from(rect: [f64; 4]) -> Aabb {
+        Aabb::from_rect(rect)
+    }
+}
+
+impl From<Aabb> for [f64; 4] {
+    fn from(aabb: Aabb) -> [f64; 4] {
+        aabb.to_rect()
+    }
+}
+
+/// Helpers for the `[x, y, width, height]` rectangles used by `graphics`.
+///
+/// Each helper gives the same result as the matching `Aabb` method.
+pub mod rect {
+    use super::{Aabb, Vec2D};
+
+    /// Returns `rect` shrunk by `margin` on every side, or grown if `margin` is negative,
+    /// about the same centre.
+    ///
+    /// A rectangle shrunk by more than half its width or height collapses to zero size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::rect;
+    ///
+    /// assert_eq!(rect::inset([0.0, 0.0, 100.0, 50.0], 10.0), [10.0, 10.0, 80.0, 30.0]);
+    /// ```
+    pub fn inset(rect: [f64; 4], margin: f64) -> [f64; 4] {
+        Aabb::from_rect(rect).inset(margin).to_rect()
+    }
+
+    /// Returns the smallest rectangle covering both `a` and `b`.
+    pub fn union(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+        Aabb::from_rect(a).union(&Aabb::from_rect(b)).to_rect()
+    }
+
+    /// Returns the rectangle covered by both `a` and `b`, or `None` if they do not overlap.
+    ///
+    /// Rectangles that only touch along an edge or at a corner do not overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::rect;
+    ///
+    /// let a = [0.0, 0.0, 10.0, 10.0];
+    /// assert_eq!(rect::intersection(a, [5.0, 5.0, 10.0, 10.0]), Some([5.0, 5.0, 5.0, 5.0]));
+    /// assert_eq!(rect::intersection(a, [10.0, 0.0, 10.0, 10.0]), None);
+    /// ```
+    pub fn intersection(a: [f64; 4], b: [f64; 4]) -> Option<[f64; 4]> {
+        Aabb::from_rect(a).intersection(&Aabb::from_rect(b)).map(|aabb| aabb.to_rect())
+    }
+
+    /// Returns the point at the centre of `rect`.
+    pub fn center(rect: [f64; 4]) -> Vec2D {
+        Aabb::from_rect(rect).center()
+    }
+
+    /// Returns the largest square that fits inside `outer`, centred in it.
+    ///
+    /// This is the area a square board fills when the window is letterboxed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::rect;
+    ///
+    /// assert_eq!(rect::fit_square([0.0, 0.0, 800.0, 600.0]), [100.0, 0.0, 600.0, 600.0]);
+    /// ```
+    pub fn fit_square(outer: [f64; 4]) -> [f64; 4] {
+        let side = outer[2].min(outer[3]).max(0.0);
+        let center = center(outer);
+        [center.x - (side / 2.0), center.y - (side / 2.0), side, side]
+    }
+}
+
+/// The shape that a `Sprite` is drawn and hit-tested as.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Shape {
+    /// Fills the `Sprite`'s rectangle.
+    Rect,
+    /// A circle of `radius` about the centre of the `Sprite`.
+    Circle { radius: f64 },
+}
+
+/// Identifies an image that a `Sprite` can be drawn with, see `textures::TextureStore`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TextureId(pub usize);
+
+/// The layer that tiles are drawn on.
+pub const TILE_LAYER: i8 = 0;
+
+/// The layer that the cursor is drawn on, above the tiles.
+pub const CURSOR_LAYER: i8 = 10;
+
+/// Represents a sprite that can be rendered.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Sprite {
+    pub pos: Vec2D,
+    pub width: f64,
+    pub height: f64,
+    pub colour: Colour,
+    pub shape: Shape,
+    /// Sprites on higher layers are drawn on top of those on lower layers.
+    pub layer: i8,
+    /// The distance the `Sprite` moves per second when stepped, see `Sprite::step`.
+    pub velocity: Vec2D,
+    /// The angle, in radians, that the `Sprite` is rotated by about its centre when drawn.
+    ///
+    /// Rotation is only cosmetic: overlap tests still use the unrotated rectangle.
+    pub rotation: f64,
+    /// The image the `Sprite` is drawn with in place of its colour, if it has one.
+    pub texture: Option<TextureId>,
+    /// The colour and thickness of an outline drawn just inside the edge of the `Sprite`.
+    pub border: Option<(Colour, f64)>,
+    /// Whether the inside of the `Sprite` is drawn. An unfilled `Sprite` with a border is
+    /// drawn as a hollow outline.
+    pub filled: bool,
+    /// Whether the `Sprite` is drawn at all, see `Blink`.
+    pub visible: bool,
+    /// Identifies the tile that the `Sprite` belongs to across frames.
+    ///
+    /// Every tile added to a `Board` gets an id that is never given to another tile while the
+    /// game runs, starting from 1. Every other `Sprite`, such as the cursor, has id 0.
+    pub id: u64,
+}
+
+impl Sprite {
+    /// Returns a `SpriteBuilder` for a white, zero sized `Rect` at the origin on the
+    /// `TILE_LAYER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Vec2D, CURSOR_LAYER};
+    ///
+    /// let cursor = Sprite::builder()
+    ///     .center(Vec2D::new(150.0, 150.0))
+    ///     .size(20.0, 10.0)
+    ///     .colour(colours::YELLOW)
+    ///     .layer(CURSOR_LAYER)
+    ///     .build();
+    /// assert_eq!(cursor.get_rect(), [140.0, 145.0, 20.0, 10.0]);
+    /// ```
+    pub fn builder() -> SpriteBuilder {
+        SpriteBuilder::new()
+    }
+
+    /// Returns a tile struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::Sprite;
+    ///
+    /// let tile = Sprite::new(100.0, 100.0, 50.0, 50.0, colours::BLUE);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `width` or `height` is negative or not finite, see
+    /// `Sprite::try_new`.
+    pub fn new(x: f64, y: f64, width: f64, height: f64, colour: Colour) -> Sprite {
+        debug_assert!(is_valid_dimension(width) && is_valid_dimension(height),
+                      "invalid sprite size {} x {}",
+                      width,
+                      height);
+        Sprite {
+            pos: Vec2D { x: x, y: y },
+            width: width,
+            height: height,
+            colour: colour,
+            shape: Shape::Rect,
+            layer: TILE_LAYER,
+            velocity: Vec2D::empty(),
+            rotation: 0.0,
+            texture: None,
+            border: None,
+            filled: true,
+            visible: true,
+            id: 0,
+        }
+    }
+
+    /// Returns a tile struct, or an error if `width` or `height` is negative or not finite.
+    ///
+    /// A size of zero is allowed, but such a `Sprite` never overlaps anything, see
+    /// `Sprite::is_empty`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, SpriteError};
+    ///
+    /// assert!(Sprite::try_new(0.0, 0.0, 10.0, 0.0, colours::RED).is_ok());
+    /// assert_eq!(Sprite::try_new(0.0, 0.0, -10.0, 5.0, colours::RED),
+    ///            Err(SpriteError::InvalidWidth(-10.0)));
+    /// ```
+    pub fn try_new(x: f64,
+                   y: f64,
+                   width: f64,
+                   height: f64,
+                   colour: Colour)
+                   -> Result<Sprite, SpriteError> {
+        if !is_valid_dimension(width) {
+            return Err(SpriteError::InvalidWidth(width));
+        }
+        if !is_valid_dimension(height) {
+            return Err(SpriteError::InvalidHeight(height));
+        }
+        Ok(Sprite::new(x, y, width, height, colour))
+    }
+
+    /// Tests if the `Sprite` covers no area, because its width, height or radius is not
+    /// positive.
+    ///
+    /// Empty sprites never overlap anything and contain no points under `contains_point`.
+    pub fn is_empty(&self) -> bool {
+        let no_radius = match self.shape {
+            Shape::Circle { radius } => radius.is_nan() || (radius <= 0.0),
+            Shape::Rect => false,
+        };
+        self.width.is_nan() || (self.width <= 0.0) || self.height.is_nan() ||
+        (self.height <= 0.0) || no_radius
+    }
+
+    /// Returns the `Sprite` moving with `velocity`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Vec2D};
+    ///
+    /// let popup = Sprite::new(0.0, 100.0, 10.0, 10.0, colours::YELLOW)
+    ///     .with_velocity(Vec2D::new(0.0, -20.0));
+    /// assert!(popup.is_moving());
+    /// ```
+    pub fn with_velocity(mut self, velocity: Vec2D) -> Sprite {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Tests if the `Sprite` has a non-zero velocity.
+    pub fn is_moving(&self) -> bool {
+        self.velocity != Vec2D::empty()
+    }
+
+    /// Moves the `Sprite` by its velocity over `dt` seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Vec2D};
+    ///
+    /// let mut popup = Sprite::new(0.0, 100.0, 10.0, 10.0, colours::YELLOW)
+    ///     .with_velocity(Vec2D::new(0.0, -20.0));
+    /// popup.step(0.5);
+    /// assert_eq!(popup.pos, Vec2D::new(0.0, 90.0));
+    /// ```
+    pub fn step(&mut self, dt: f64) {
+        self.pos += self.velocity * dt;
+    }
+
+    /// Returns a circular `Sprite` of `radius` centred on `center`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Shape, Sprite, Vec2D};
+    ///
+    /// let cursor = Sprite::circle(Vec2D::new(50.0, 50.0), 10.0, colours::YELLOW);
+    /// assert_eq!(cursor.shape, Shape::Circle { radius: 10.0 });
+    /// assert_eq!(cursor.get_rect(), [40.0, 40.0, 20.0, 20.0]);
+    /// ```
+    pub fn circle(center: Vec2D, radius: f64, colour: Colour) -> Sprite {
+        let mut sprite = Sprite::from_center(center, 2.0 * radius, 2.0 * radius, colour);
+        sprite.shape = Shape::Circle { radius: radius };
+        sprite
+    }
+
+    /// Returns a `Sprite` centred on `center`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Vec2D};
+    ///
+    /// let cursor = Sprite::from_center(Vec2D::new(150.0, 150.0), 20.0, 10.0, colours::YELLOW);
+    /// assert_eq!(cursor.get_rect(), [140.0, 145.0, 20.0, 10.0]);
+    /// ```
+    pub fn from_center(center: Vec2D, width: f64, height: f64, colour: Colour) -> Sprite {
+        Sprite::new(center.x - (0.5 * width),
+                    center.y - (0.5 * height),
+                    width,
+                    height,
+                    colour)
+    }
+
+    /// Returns the position of the centre of the `Sprite`.
+    pub fn center(&self) -> Vec2D {
+        Vec2D::new(self.pos.x + (0.5 * self.width),
+                   self.pos.y + (0.5 * self.height))
+    }
+
+    /// Moves the `Sprite` so that it is centred on `center`, keeping its size.
+    pub fn set_center(&mut self, center: Vec2D) {
+        self.pos = Vec2D::new(center.x - (0.5 * self.width), center.y - (0.5 * self.height));
+    }
+
+    /// Centres the `Sprite` in the cell of `board` whose centre is nearest its own, returning
+    /// the index of that cell.
+    ///
+    /// A centre exactly between two cells snaps to the cell to its right or below, as with
+    /// `Board::index_from_pos`. A `Sprite` off the `Board` snaps to the nearest cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Board, Sprite, Vec2D};
+    ///
+    /// let board = Board::from_length(300.0);
+    /// let mut cursor = Sprite::new(160.0, 30.0, 20.0, 20.0, colours::YELLOW);
+    /// assert_eq!(cursor.snap_to_grid(&board), 1);
+    /// assert_eq!(cursor.center(), Vec2D::new(150.0, 50.0));
+    /// ```
+    pub fn snap_to_grid(&mut self, board: &Board) -> usize {
+        let center = self.center();
+        let i = board.index_from_pos(center).unwrap_or_else(|| board.nearest_cell(center));
+        self.set_center(board.cell_center(i));
+        i
+    }
+
+    /// Returns a copy of the `Sprite` moved by `delta`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Vec2D};
+    ///
+    /// let tile = Sprite::new(10.0, 10.0, 50.0, 50.0, colours::RED);
+    /// let moved = tile.translated(Vec2D::new(5.0, -10.0));
+    /// assert_eq!(moved.pos, Vec2D::new(15.0, 0.0));
+    /// assert_eq!(tile.pos, Vec2D::new(10.0, 10.0));
+    /// ```
+    pub fn translated(&self, delta: Vec2D) -> Sprite {
+        let mut sprite = *self;
+        sprite.pos += delta;
+        sprite
+    }
+
+    /// Returns a copy of the `Sprite` with its size, and the radius of a circle, multiplied by
+    /// `factor` while its centre stays in the same place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::Sprite;
+    ///
+    /// let tile = Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED);
+    /// assert_eq!(tile.scaled_about_center(0.5).get_rect(), [25.0, 25.0, 50.0, 50.0]);
+    /// ```
+    pub fn scaled_about_center(&self, factor: f64) -> Sprite {
+        let mut sprite = *self;
+        sprite.width *= factor;
+        sprite.height *= factor;
+        if let Shape::Circle { radius } = self.shape {
+            sprite.shape = Shape::Circle { radius: radius * factor };
+        }
+        sprite.set_center(self.center());
+        sprite
+    }
+
+    /// Returns a copy of the `Sprite` grown by `margin` on every side, or shrunk if `margin` is
+    /// negative, while its centre stays in the same place.
+    ///
+    /// The size, and the radius of a circle, never shrink below zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::Sprite;
+    ///
+    /// let cursor = Sprite::new(40.0, 40.0, 20.0, 10.0, colours::YELLOW);
+    /// assert_eq!(cursor.inflated(5.0).get_rect(), [35.0, 35.0, 30.0, 20.0]);
+    /// assert_eq!(cursor.inflated(-6.0).get_rect(), [46.0, 45.0, 8.0, 0.0]);
+    /// ```
+    pub fn inflated(&self, margin: f64) -> Sprite {
+        let grown = self.aabb().inset(-margin);
+        let mut sprite = *self;
+        sprite.width = grown.width();
+        sprite.height = grown.height();
+        if let Shape::Circle { radius } = self.shape {
+            sprite.shape = Shape::Circle { radius: (radius + margin).max(0.0) };
+        }
+        sprite.set_center(self.center());
+        sprite
+    }
+
+    /// Returns `transform` with the `Sprite`'s rotation about its centre applied, ready to draw
+    /// the `Sprite` with.
+    ///
+    /// An unrotated `Sprite` returns `transform` unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate graphics;
+    /// extern crate whack;
+    ///
+    /// use whack::colours;
+    /// use whack::gobs::Sprite;
+    ///
+    /// # fn main() {
+    /// let identity = graphics::math::identity();
+    /// let mut tile = Sprite::new(0.0, 0.0, 2.0, 2.0, colours::RED);
+    /// assert_eq!(tile.render_transform(identity), identity);
+    /// tile.rotation = std::f64::consts::PI;
+    /// let t = tile.render_transform(identity);
+    /// assert!((t[0][0] + 1.0).abs() < 1e-9 && (t[0][2] - 2.0).abs() < 1e-9);
+    /// # }
+    /// ```
+    pub fn render_transform(&self, transform: Matrix2d) -> Matrix2d {
+        if self.rotation == 0.0 {
+            return transform;
+        }
+        let center = self.center();
+        transform.trans(center.x, center.y)
+            .rot_rad(self.rotation)
+            .trans(-center.x, -center.y)
+    }
+
+    /// Returns the bounding box of the `Sprite`'s rectangle.
+    pub fn aabb(&self) -> Aabb {
+        Aabb::from_pos_size(self.pos, self.width, self.height)
+    }
+
+    /// Creates a rect type array from the `Sprite`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::Sprite;
+    ///
+    /// let tile = Sprite::new(100.0, 100.0, 50.0, 50.0, colours::GREEN);
+    /// assert_eq!([tile.pos.x, tile.pos.y, tile.width, tile.height], tile.get_rect())
+    pub fn get_rect(&self) -> [f64; 4] {
+        self.aabb().to_rect()
+    }
+
+    /// Tests if the `Sprite` overlaps with a reference `Sprite`.
+    ///
+    /// Sprites that only touch along an edge or at a corner are not overlapping, so adjacent
+    /// tiles on the `Board` never overlap each other and a cursor whose edge lies exactly on
+    /// a cell boundary only overlaps the tile it is inside. Circular sprites are tested using
+    /// their `Shape` rather than their rectangle, and likewise do not overlap anything they
+    /// only touch. Empty sprites, see `is_empty`, never overlap anything. The `rotation` of
+    /// either `Sprite` is ignored, so rotated rectangles are tested as if axis-aligned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Sprite;
+    /// use whack::colours;
+    ///
+    /// let s1 = Sprite::new(100.0, 100.0, 50.0, 50.0, colours::YELLOW);
+    /// let s2 = Sprite::new(125.0, 100.0, 50.0, 50.0, colours::YELLOW);
+    /// let s3 = Sprite::new(155.0, 100.0, 50.0, 50.0, colours::YELLOW);
+    /// assert!(s1.is_overlapping(&s2));
+    /// assert!(!s1.is_overlapping(&s3));
+    /// assert!(s2.is_overlapping(&s3));
+    /// ```
+    pub fn is_overlapping(&self, other: &Sprite) -> bool {
+        if self.is_empty() || other.is_empty() {
+            return false;
+        }
+        match (self.shape, other.shape) {
+            (Shape::Rect, Shape::Rect) => self.rect_overlaps_rect(other),
+            (Shape::Circle { radius }, Shape::Rect) => {
+                other.rect_overlaps_circle(self.center(), radius)
+            }
+            (Shape::Rect, Shape::Circle { radius }) => {
+                self.rect_overlaps_circle(other.center(), radius)
+            }
+            (Shape::Circle { radius: r1 }, Shape::Circle { radius: r2 }) => {
+                self.center().distance(other.center()) < r1 + r2
+            }
+        }
+    }
+
+    /// Tests if the rectangles of two sprites overlap, ignoring their shapes.
+    fn rect_overlaps_rect(&self, other: &Sprite) -> bool {
+        self.aabb().intersects(&other.aabb())
+    }
+
+    /// Tests if the `Sprite`'s rectangle overlaps the circle of `radius` about `center`.
+    fn rect_overlaps_circle(&self, center: Vec2D, radius: f64) -> bool {
+        center.distance(self.aabb().nearest_point(center)) < radius
+    }
+
+    /// Returns the area of the intersection of the `Sprite` with a reference `Sprite`.
+    ///
+    /// The rectangles of the sprites are used whatever their `Shape`. The area is `0.0` if
+    /// either `Sprite` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::Sprite;
+    ///
+    /// let tile = Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED);
+    /// let cursor = Sprite::new(90.0, 80.0, 20.0, 20.0, colours::YELLOW);
+    /// assert_eq!(tile.overlap_area(&cursor), 200.0);
+    /// ```
+    pub fn overlap_area(&self, other: &Sprite) -> f64 {
+        if self.is_empty() || other.is_empty() {
+            return 0.0;
+        }
+        self.aabb().intersection(&other.aabb()).map_or(0.0, |i| i.area())
+    }
+
+    /// Returns the area of the intersection of the `Sprite` with a reference `Sprite` as a
+    /// fraction of the area of the smaller of the two.
+    ///
+    /// The result is `1.0` when one `Sprite` lies entirely inside the other. Sprites with no
+    /// area never overlap anything, so `0.0` is returned if either has zero width or height.
+    pub fn overlap_fraction(&self, other: &Sprite) -> f64 {
+        let smaller = (self.width * self.height).min(other.width * other.height);
+        if smaller <= 0.0 {
+            return 0.0;
+        }
+        self.overlap_area(other) / smaller
+    }
+
+    /// Tests if a point lies within the `Sprite`'s rectangle, whatever its `Shape`.
+    ///
+    /// Points on the edges of the rectangle are counted as inside.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Vec2D};
+    ///
+    /// let s = Sprite::new(100.0, 100.0, 50.0, 50.0, colours::YELLOW);
+    /// assert!(s.contains(Vec2D::new(125.0, 125.0)));
+    /// assert!(s.contains(Vec2D::new(150.0, 100.0)));
+    /// assert!(!s.contains(Vec2D::new(151.0, 125.0)));
+    /// ```
+    pub fn contains(&self, point: Vec2D) -> bool {
+        self.aabb().contains(point)
+    }
+
+    /// Tests if a point lies within the `Sprite`'s rectangle, treating it as half-open.
+    ///
+    /// Points on the left and top edges are inside while points on the right and bottom
+    /// edges are not, so adjacent sprites never both contain the same point. This matches the
+    /// convention used by `Board::index_from_pos`, unlike `contains`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Vec2D};
+    ///
+    /// let sprite = Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED);
+    /// assert!(sprite.contains_point(Vec2D::new(0.0, 0.0)));
+    /// assert!(!sprite.contains_point(Vec2D::new(100.0, 50.0)));
+    /// ```
+    pub fn contains_point(&self, p: Vec2D) -> bool {
+        self.aabb().contains_half_open(p)
+    }
+
+    /// Moves the `Sprite` the shortest distance needed to keep the whole of it inside `rect`,
+    /// given as `[x, y, width, height]`.
+    ///
+    /// A `Sprite` that is wider or taller than `rect` is aligned with its left or top edge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Vec2D};
+    ///
+    /// let mut sprite = Sprite::new(290.0, -10.0, 20.0, 20.0, colours::YELLOW);
+    /// sprite.clamp_to_rect([0.0, 0.0, 300.0, 300.0]);
+    /// assert_eq!(sprite.pos, Vec2D::new(280.0, 0.0));
+    /// ```
+    pub fn clamp_to_rect(&mut self, rect: [f64; 4]) {
+        self.pos = self.aabb().moved_within(&Aabb::from_rect(rect)).min;
+    }
+}
+
+/// Tests if `d` can be used as the width or height of a `Sprite`.
+fn is_valid_dimension(d: f64) -> bool {
+    d.is_finite() && (d >= 0.0)
+}
+
+/// Errors that can occur when creating a `Sprite`, see `Sprite::try_new`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SpriteError {
+    /// The width is negative or not finite.
+    InvalidWidth(f64),
+    /// The height is negative or not finite.
+    InvalidHeight(f64),
+}
+
+impl fmt::Display for SpriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SpriteError::InvalidWidth(w) => {
+                write!(f, "sprite width must be non-negative and finite, got {}", w)
+            }
+            SpriteError::InvalidHeight(h) => {
+                write!(f, "sprite height must be non-negative and finite, got {}", h)
+            }
+        }
+    }
+}
+
+impl Error for SpriteError {
+    fn description(&self) -> &str {
+        match *self {
+            SpriteError::InvalidWidth(_) => "sprite width must be non-negative and finite",
+            SpriteError::InvalidHeight(_) => "sprite height must be non-negative and finite",
+        }
+    }
+}
+
+/// Builds a `Sprite` one named property at a time, see `Sprite::builder`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SpriteBuilder {
+    pos: Vec2D,
+    center: Option<Vec2D>,
+    width: f64,
+    height: f64,
+    colour: Colour,
+    shape: Shape,
+    layer: i8,
+    velocity: Vec2D,
+    rotation: f64,
+    texture: Option<TextureId>,
+    border: Option<(Colour, f64)>,
+    filled: bool,
+    visible: bool,
+}
+
+impl SpriteBuilder {
+    /// Returns a `SpriteBuilder` for a white, zero sized `Rect` at the origin on the
+    /// `TILE_LAYER`.
+    pub fn new() -> SpriteBuilder {
+        SpriteBuilder {
+            pos: Vec2D::empty(),
+            center: None,
+            width: 0.0,
+            height: 0.0,
+            colour: WHITE,
+            shape: Shape::Rect,
+            layer: TILE_LAYER,
+            velocity: Vec2D::empty(),
+            rotation: 0.0,
+            texture: None,
+            border: None,
+            filled: true,
+            visible: true,
+        }
+    }
+
+    /// Sets the position of the top left corner of the `Sprite`.
+    pub fn pos(mut self, pos: Vec2D) -> SpriteBuilder {
+        self.pos = pos;
+        self.center = None;
+        self
+    }
+
+    /// Sets the position of the centre of the `Sprite`, whatever size it is given.
+    pub fn center(mut self, center: Vec2D) -> SpriteBuilder {
+        self.center = Some(center);
+        self
+    }
+
+    /// Sets the width and height of the `Sprite`.
+    pub fn size(mut self, width: f64, height: f64) -> SpriteBuilder {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the colour of the `Sprite`.
+    pub fn colour(mut self, colour: Colour) -> SpriteBuilder {
+        self.colour = colour;
+        self
+    }
+
+    /// Sets the `Shape` of the `Sprite`.
+    pub fn shape(mut self, shape: Shape) -> SpriteBuilder {
+        self.shape = shape;
+        self
+    }
+
+    /// Sets the layer that the `Sprite` is drawn on.
+    pub fn layer(mut self, layer: i8) -> SpriteBuilder {
+        self.layer = layer;
+        self
+    }
+
+    /// Sets the velocity of the `Sprite`.
+    pub fn velocity(mut self, velocity: Vec2D) -> SpriteBuilder {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Sets the rotation, in radians, of the `Sprite` about its centre.
+    pub fn rotation(mut self, rotation: f64) -> SpriteBuilder {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Sets the image that the `Sprite` is drawn with.
+    pub fn texture(mut self, texture: TextureId) -> SpriteBuilder {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// Sets the colour and thickness of the outline drawn just inside the edge of the
+    /// `Sprite`.
+    pub fn border(mut self, colour: Colour, thickness: f64) -> SpriteBuilder {
+        self.border = Some((colour, thickness));
+        self
+    }
+
+    /// Sets whether the inside of the `Sprite` is drawn.
+    pub fn filled(mut self, filled: bool) -> SpriteBuilder {
+        self.filled = filled;
+        self
+    }
+
+    /// Sets whether the `Sprite` is drawn.
+    pub fn visible(mut self, visible: bool) -> SpriteBuilder {
+        self.visible = visible;
+        self
+    }
+
+    /// Returns the `Sprite`.
+    pub fn build(&self) -> Sprite {
+        let mut sprite = Sprite::new(self.pos.x, self.pos.y, self.width, self.height, self.colour);
+        sprite.shape = self.shape;
+        sprite.layer = self.layer;
+        sprite.velocity = self.velocity;
+        sprite.rotation = self.rotation;
+        sprite.texture = self.texture;
+        sprite.border = self.border;
+        sprite.filled = self.filled;
+        sprite.visible = self.visible;
+        if let Some(center) = self.center {
+            sprite.set_center(center);
+        }
+        sprite
+    }
+}
+
+impl Default for SpriteBuilder {
+    fn default() -> SpriteBuilder {
+        SpriteBuilder::new()
+    }
+}
+
+/// A short piece of text, such as a score popup, that drifts upwards and fades away.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+/// use whack::gobs::{Label, Vec2D, LABEL_DURATION};
+///
+/// let mut label = Label::new("+1", Vec2D::new(50.0, 50.0), colours::WHITE);
+/// label.tick(LABEL_DURATION / 2.0);
+/// assert!(label.pos.y < 50.0);
+/// assert_eq!(label.faded_colour()[3], 0.5);
+/// label.tick(LABEL_DURATION / 2.0);
+/// assert!(label.is_expired());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub text: String,
+    /// The screen position the text is drawn from.
+    pub pos: Vec2D,
+    pub colour: Colour,
+    /// The time in seconds until the `Label` disappears.
+    pub ttl: f64,
+    /// The distance the `Label` moves per second.
+    pub velocity: Vec2D,
+}
+
+impl Label {
+    /// Returns a `Label` showing `text` at `pos` for `LABEL_DURATION` seconds, drifting upwards
+    /// at `LABEL_SPEED`.
+    pub fn new<T: Into<String>>(text: T, pos: Vec2D, colour: Colour) -> Label {
+        Label {
+            text: text.into(),
+            pos: pos,
+            colour: colour,
+            ttl: LABEL_DURATION,
+            velocity: Vec2D::new(0.0, -LABEL_SPEED),
+        }
+    }
+
+    /// Moves the `Label` along its velocity and counts down its time to live by `dt` seconds.
+    pub fn tick(&mut self, dt: f64) {
+        self.pos += self.velocity * dt;
+        self.ttl -= dt;
+    }
+
+    /// True once the `Label` has no time left to live.
+    pub fn is_expired(&self) -> bool {
+        self.ttl <= 0.0
+    }
+
+    /// Returns the colour of the `Label` with its alpha reduced in proportion to the time it
+    /// has left.
+    pub fn faded_colour(&self) -> Colour {
+        let mut colour = self.colour;
+        colour[3] *= (self.ttl / LABEL_DURATION).max(0.0).min(1.0) as f32;
+        colour
+    }
+}
+
+/// How a tile came to be on the `Board`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TileKind {
+    /// A single tile added on its own.
+    Normal,
+    /// One of the tiles added together by `Board::spawn_pattern`.
+    Pattern,
+}
+
+/// Flashes a `Sprite` on and off, visible for the first half of every `period` seconds and
+/// hidden for the second.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+/// use whack::gobs::{Blink, Sprite};
+///
+/// let mut sprite = Sprite::new(0.0, 0.0, 10.0, 10.0, colours::RED);
+/// let mut blink = Blink::new(0.5);
+/// blink.tick(0.3, &mut sprite);
+/// assert!(!sprite.visible);
+/// blink.tick(0.3, &mut sprite);
+/// assert!(sprite.visible);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Blink {
+    /// The time in seconds for one cycle of showing and hiding.
+    pub period: f64,
+    /// The time in seconds since the `Blink` started.
+    pub t: f64,
+}
+
+impl Blink {
+    /// Returns a `Blink` with `period` at the start of its cycle.
+    pub fn new(period: f64) -> Blink {
+        Blink {
+            period: period,
+            t: 0.0,
+        }
+    }
+
+    /// True if a blinking `Sprite` is shown at the current time.
+    pub fn is_visible(&self) -> bool {
+        if self.period <= 0.0 {
+            return true;
+        }
+        (self.t % self.period) < (self.period / 2.0)
+    }
+
+    /// Advances the `Blink` by `dt` seconds and shows or hides `sprite` to match.
+    pub fn tick(&mut self, dt: f64, sprite: &mut Sprite) {
+        self.t += dt;
+        sprite.visible = self.is_visible();
+    }
+}
+
+/// Measures time towards a `duration` in seconds, for anything that happens after a delay.
+///
+/// A `Timer` is finished once its `elapsed` time reaches its `duration`, and stays finished
+/// until it is reset.
+///
+/// # Examples
+///
+/// ```
+/// use whack::gobs::Timer;
+///
+/// let mut timer = Timer::new(1.0);
+/// assert!(!timer.tick(0.75));
+/// assert_eq!(timer.fraction(), 0.75);
+/// assert!(timer.tick(0.25));
+/// assert!(!timer.tick(0.25));
+/// assert!(timer.is_finished());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Timer {
+    /// The time in seconds that the `Timer` runs for.
+    pub duration: f64,
+    /// The time in seconds since the `Timer` was started.
+    pub elapsed: f64,
+}
+
+impl Timer {
+    /// Returns a `Timer` that has just started and runs for `duration` seconds.
+    ///
+    /// A `Timer` with no duration is finished from the start.
+    pub fn new(duration: f64) -> Timer {
+        Timer {
+            duration: duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advances the `Timer` by `dt` seconds, returning true only if this finished it.
+    pub fn tick(&mut self, dt: f64) -> bool {
+        let was_finished = self.is_finished();
+        self.elapsed += dt;
+        !was_finished && self.is_finished()
+    }
+
+    /// True once the `elapsed` time has reached the `duration`.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Starts the `Timer` again with the same `duration`.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    /// Starts the `Timer` again with a new `duration`.
+    pub fn reset_with(&mut self, duration: f64) {
+        self.duration = duration;
+        self.reset();
+    }
+
+    /// Returns the time in seconds until the `Timer` finishes, which is `0.0` once it has.
+    pub fn remaining(&self) -> f64 {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    /// Returns how far through its `duration` the `Timer` is, from `0.0` when it starts to
+    /// `1.0` once it has finished.
+    pub fn fraction(&self) -> f64 {
+        if self.is_finished() {
+            return 1.0;
+        }
+        (self.elapsed / self.duration).max(0.0)
+    }
+}
+
+/// A mark drawn on a tile so that its `TileKind` can be told apart without relying on
+/// colour.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TileShape {
+    /// No mark, the tile is a plain block of colour.
+    Plain,
+    /// A smaller square in the middle of the tile.
+    InnerRect,
+}
+
+impl TileShape {
+    /// Returns the `TileShape` that tiles of `kind` are marked with.
+    pub fn for_kind(kind: TileKind) -> TileShape {
+        match kind {
+            TileKind::Normal => TileShape::Plain,
+            TileKind::Pattern => TileShape::InnerRect,
+        }
+    }
+
+    /// Returns the `Sprite` that marks the tile drawn as `sprite` with this shape, if there is
+    /// one.
+    ///
+    /// Marks are drawn in the colour of the tile's border, or black if it has none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, TileShape};
+    ///
+    /// let tile = Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED);
+    /// assert_eq!(TileShape::Plain.mark(&tile), None);
+    /// let mark = TileShape::InnerRect.mark(&tile).unwrap();
+    /// assert_eq!(mark.get_rect(), [35.0, 35.0, 30.0, 30.0]);
+    /// ```
+    pub fn mark(&self, sprite: &Sprite) -> Option<Sprite> {
+        let colour = sprite.border.map_or(BLACK, |b| b.0);
+        match *self {
+            TileShape::Plain => None,
+            TileShape::InnerRect => {
+                let inset = sprite.width.min(sprite.height) * 0.35;
+                let mut mark = sprite.inflated(-inset);
+                mark.colour = colour;
+                mark.border = None;
+                mark.texture = None;
+                Some(mark)
+            }
+        }
+    }
+}
+
+/// Represents a tile on the game `Board`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tile {
+    pub sprite: Sprite,
+    /// The time in seconds since the tile was added to the `Board`.
+    pub age: f64,
+    pub kind: TileKind,
+    /// Flashes the tile while it is about to expire.
+    pub blink: Option<Blink>,
+    /// The number of whacks the tile takes to clear, see `Tile::hit`.
+    pub hits_required: u32,
+}
+
+impl Tile {
+    /// Returns a new `Normal` `Tile` with an age of `0.0` that is cleared by a single whack.
+    pub fn new(sprite: Sprite) -> Tile {
+        Tile {
+            sprite: sprite,
+            age: 0.0,
+            kind: TileKind::Normal,
+            blink: None,
+            hits_required: 1,
+        }
+    }
+
+    /// Whacks the `Tile` once, returning `true` if that was the last whack it needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Tile};
+    ///
+    /// let mut tile = Tile::new(Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED));
+    /// tile.hits_required = 2;
+    /// assert!(!tile.hit());
+    /// assert!(tile.hit());
+    /// ```
+    pub fn hit(&mut self) -> bool {
+        self.hits_required = self.hits_required.saturating_sub(1);
+        self.hits_required == 0
+    }
+
+    /// Returns the sprite of the `Tile` as it looked `rewind` seconds ago, moved back along its
+    /// velocity.
+    ///
+    /// Tiles that need more than one more whack are drawn darker by `EXTRA_HIT_DARKEN` for
+    /// each extra whack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::{Sprite, Tile, Vec2D};
+    ///
+    /// let mut tile = Tile::new(Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED));
+    /// assert_eq!(tile.sprite_at(0.0), tile.sprite);
+    /// tile.sprite.velocity = Vec2D::new(10.0, 0.0);
+    /// assert_eq!(tile.sprite_at(0.5).pos, Vec2D::new(-5.0, 0.0));
+    /// ```
+    pub fn sprite_at(&self, rewind: f64) -> Sprite {
+        let mut sprite = self.sprite.translated(-(self.sprite.velocity * rewind));
+        if self.hits_required > 1 {
+            let extra = (self.hits_required - 1) as f32;
+            sprite.colour = colours::darken(sprite.colour, (EXTRA_HIT_DARKEN * extra).min(0.75));
+        }
+        sprite
+    }
+}
+
+/// The number of cells along each side of a square `Board` unless another is chosen.
+pub const DEFAULT_GRID_DIM: usize = 3;
+
+/// The arrangement of the cells on a `Board`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BoardLayout {
+    /// A grid of square cells, which may have a different number of rows and columns, see
+    /// `Board::rows` and `Board::cols`.
+    Square,
+    /// Experimental. A hexagon of flat topped hexagonal cells, with `radius` rings of cells
+    /// around the centre cell.
+    Hex { radius: usize },
+}
+
+/// The six directions from a hexagonal cell to its neighbours.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl HexDirection {
+    /// Returns the change in axial coordinates `(q, r)` from moving one cell in this direction.
+    pub fn offset(&self) -> (i32, i32) {
+        match *self {
+            HexDirection::North => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+            HexDirection::SouthEast => (1, 0),
+            HexDirection::South => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::NorthWest => (-1, 0),
+        }
+    }
+}
+
+/// Represents the game board.
+#[derive(Debug)]
+pub struct Board {
+    /// The tiles on the `Board` by position. Tiles should be added with `add_tile_at` and
+    /// removed with `remove_tile`, which keep the spawn counts, tile ids and fading up to
+    /// date, see `Tiles`.
+    pub tiles: Tiles,
+    pub length: f64,
+    /// The screen position of the top left corner of the `Board`.
+    pub origin: Vec2D,
+    layout: BoardLayout,
+    rows: usize,
+    cols: usize,
+    hex_cells: Vec<(i32, i32)>,
+    blocked: Vec<bool>,
+    walls: Vec<bool>,
+    rng: StdRng,
+    spawn_queue: VecDeque<usize>,
+    placement: Box<PlacementStrategy>,
+    placement_context: PlacementContext,
+    recently_removed: Vec<(Sprite, f64)>,
+    spawn_counts: Vec<u32>,
+    theme: Theme,
+}
+
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        (self.tiles == other.tiles) && (self.length == other.length) &&
+        (self.origin == other.origin) && (self.layout == other.layout) &&
+        (self.rows == other.rows) && (self.cols == other.cols) &&
+        (self.blocked == other.blocked) && (self.walls == other.walls)
+    }
+}
+
+impl Board {
+    /// Returns a Board struct with an empty Tiles array
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let board = Board::from_length(300.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is not positive and finite, see `Board::try_from_length`.
+    pub fn from_length(length: f64) -> Board {
+        Board::try_from_length(length).unwrap_or_else(|e| panic!("invalid board: {}", e))
+    }
+
+    /// Returns a Board struct with an empty Tiles array, or an error if `length` is not
+    /// positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{Board, BoardError};
+    ///
+    /// assert!(Board::try_from_length(300.0).is_ok());
+    /// assert_eq!(Board::try_from_length(-1.0), Err(BoardError::InvalidLength(-1.0)));
+    /// ```
+    pub fn try_from_length(length: f64) -> Result<Board, BoardError> {
+        Board::try_seeded(length, rand::thread_rng().gen())
+    }
+
+    /// Returns a Board struct with an empty Tiles array whose tile placement is driven by a
+    /// random number generator initialised with `seed`.
+    ///
+    /// Two boards created with the same seed will spawn tiles in the same positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board1 = Board::seeded(300.0, 42);
+    /// let mut board2 = Board::seeded(300.0, 42);
+    /// assert_eq!(board1.next_spawn(), board2.next_spawn());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is not positive and finite, see `Board::try_seeded`.
+    pub fn seeded(length: f64, seed: usize) -> Board {
+        Board::try_seeded(length, seed).unwrap_or_else(|e| panic!("invalid board: {}", e))
+    }
+
+    /// Returns a seeded Board struct with an empty Tiles array, or an error if `length` is not
+    /// positive and finite.
+    pub fn try_seeded(length: f64, seed: usize) -> Result<Board, BoardError> {
+        Board::with_layout(BoardLayout::Square,
+                           DEFAULT_GRID_DIM,
+                           DEFAULT_GRID_DIM,
+                           length,
+                           seed)
+    }
+
+    /// Returns a square Board with `grid_dim` cells along each side.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let board = Board::with_grid(400.0, 4);
+    /// assert_eq!(board.tiles.len(), 16);
+    /// assert_eq!(board.cell_size(), 100.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is not positive and finite or `grid_dim` is `0`, see
+    /// `Board::try_with_grid`.
+    pub fn with_grid(length: f64, grid_dim: usize) -> Board {
+        Board::try_with_grid(length, grid_dim).unwrap_or_else(|e| panic!("invalid board: {}", e))
+    }
+
+    /// Returns a square Board with `grid_dim` cells along each side, or an error if `length`
+    /// is not positive and finite or `grid_dim` is `0`.
+    pub fn try_with_grid(length: f64, grid_dim: usize) -> Result<Board, BoardError> {
+        Board::try_with_rows_cols(length, grid_dim, grid_dim).map_err(|e| match e {
+            BoardError::InvalidRowsCols(..) => BoardError::InvalidGridDim(grid_dim),
+            e => e,
+        })
+    }
+
+    /// Returns a Board with `rows` rows of `cols` square cells, sized so that its longer side
+    /// is `length`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let board = Board::with_rows_cols(500.0, 2, 5);
+    /// assert_eq!(board.tiles.len(), 10);
+    /// assert_eq!(board.cell_size(), 100.0);
+    /// assert_eq!((board.width(), board.height()), (500.0, 200.0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is not positive and finite or `rows` or `cols` is `0`, see
+    /// `Board::try_with_rows_cols`.
+    pub fn with_rows_cols(length: f64, rows: usize, cols: usize) -> Board {
+        Board::try_with_rows_cols(length, rows, cols)
+            .unwrap_or_else(|e| panic!("invalid board: {}", e))
+    }
+
+    /// Returns a Board with `rows` rows of `cols` square cells, sized so that its longer side
+    /// is `length`, or an error if `length` is not positive and finite or `rows` or `cols` is
+    /// `0`.
+    pub fn try_with_rows_cols(length: f64, rows: usize, cols: usize) -> Result<Board, BoardError> {
+        Board::with_layout(BoardLayout::Square, rows, cols, length, rand::thread_rng().gen())
+    }
+
+    /// Returns an experimental hexagonal Board with `radius_cells` rings of cells around the
+    /// centre cell, fitted inside a square of side `length`.
+    ///
+    /// Cells are stored in axial coordinates `(q, r)`, see `hex_coord`, ordered by row and then
+    /// from left to right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let board = Board::hex(1, 300.0);
+    /// assert_eq!(board.tiles.len(), 7);
+    /// assert_eq!(board.hex_coord(3), Some((0, 0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is not positive and finite.
+    pub fn hex(radius_cells: usize, length: f64) -> Board {
+        Board::with_layout(BoardLayout::Hex { radius: radius_cells },
+                           (2 * radius_cells) + 1,
+                           (2 * radius_cells) + 1,
+                           length,
+                           rand::thread_rng().gen())
+            .unwrap_or_else(|e| panic!("invalid board: {}", e))
+    }
+
+    /// Returns a Board with its longer side `length` described by `code`, see
+    /// `Board::to_code`, or an error if `code` is not a valid board.
+    ///
+    /// A code starting with `H` makes a hexagonal board, with as many rings of cells as fit
+    /// the rest of the characters exactly. A code with its rows separated by `/` makes a board
+    /// of those rows, which must all be the same length. Otherwise the board is square and the
+    /// number of cells along each side is the square root of the number of characters in
+    /// `code`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{Board, BoardLayout};
+    ///
+    /// let board = Board::from_code("X...#...X", 300.0).unwrap();
+    /// assert_eq!(board.iter_tiles().map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 8]);
+    /// assert!(board.is_wall(4));
+    /// assert!(Board::from_code("X..", 300.0).is_err());
+    ///
+    /// let board = Board::from_code("X..../....X", 500.0).unwrap();
+    /// assert_eq!((board.rows(), board.cols()), (2, 5));
+    ///
+    /// let board = Board::from_code("H...X...", 300.0).unwrap();
+    /// assert_eq!(board.layout(), BoardLayout::Hex { radius: 1 });
+    /// ```
+    pub fn from_code(code: &str, length: f64) -> Result<Board, ParseError> {
+        if code.starts_with(CODE_HEX) {
+            let cells: Vec<(usize, char)> = code.chars().enumerate().skip(1).collect();
+            let radius = (0..cells.len() + 1).find(|&r| hex_cells(r).len() >= cells.len());
+            let radius = match radius {
+                Some(r) if hex_cells(r).len() == cells.len() => r,
+                _ => return Err(ParseError::NotHex(cells.len())),
+            };
+            let side = (2 * radius) + 1;
+            let layout = BoardLayout::Hex { radius: radius };
+            let seed = rand::thread_rng().gen();
+            return Board::with_layout(layout, side, side, length, seed)?.fill_from_code(&cells);
+        }
+        let rows: Vec<&str> = code.split(CODE_ROW_SEPARATOR).collect();
+        let cells: Vec<(usize, char)> =
+            code.chars().enumerate().filter(|&(_, c)| c != CODE_ROW_SEPARATOR).collect();
+        let board = if rows.len() > 1 {
+            let cols = rows[0].chars().count();
+            if let Some(r) = rows.iter().position(|row| row.chars().count() != cols) {
+                return Err(ParseError::UnevenRows(r));
+            }
+            Board::try_with_rows_cols(length, rows.len(), cols)?
+        } else {
+            let grid_dim = (cells.len() as f64).sqrt().round() as usize;
+            if grid_dim * grid_dim != cells.len() {
+                return Err(ParseError::NotSquare(cells.len()));
+            }
+            Board::try_with_grid(length, grid_dim)?
+        };
+        board.fill_from_code(&cells)
+    }
+
+    /// Fills the cells of a new `Board` from the characters of a code, one per cell in index
+    /// order and each with its position in the code, see `Board::from_code`.
+    fn fill_from_code(mut self, cells: &[(usize, char)]) -> Result<Board, ParseError> {
+        for (i, &(pos, c)) in cells.iter().enumerate() {
+            match c {
+                CODE_EMPTY => (),
+                CODE_TILE => self.place_tile(i),
+                CODE_WALL => self.add_wall(i),
+                CODE_BLOCKED => self.block_cell(i),
+                _ => return Err(ParseError::InvalidCell(pos, c)),
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns a code describing which cells of the `Board` hold tiles, for sharing or for
+    /// rebuilding the `Board` with `Board::from_code`.
+    ///
+    /// Each cell is written as one character, in index order: `X` for a tile, `#` for a wall,
+    /// `-` for a blocked cell and `.` for an empty one. The rows of a board that isn't square
+    /// are separated by `/`, and the code of a hexagonal board starts with `H`. Only which
+    /// cells are filled is kept, not the position, age or look of the tiles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.add_tile_at(2).unwrap();
+    /// board.add_wall(4);
+    /// assert_eq!(board.to_code(), "..X.#....");
+    ///
+    /// let mut board = Board::with_rows_cols(500.0, 2, 5);
+    /// board.add_tile_at(6).unwrap();
+    /// assert_eq!(board.to_code(), "...../.X...");
+    ///
+    /// let mut board = Board::hex(1, 300.0);
+    /// board.add_tile_at(3).unwrap();
+    /// assert_eq!(board.to_code(), "H...X...");
+    /// ```
+    pub fn to_code(&self) -> String {
+        let mut code = String::with_capacity(self.tiles.len() + self.rows + 1);
+        if let BoardLayout::Hex { .. } = self.layout {
+            code.push(CODE_HEX);
+        }
+        for i in 0..self.tiles.len() {
+            if (i > 0) && (self.rows != self.cols) && (i % self.cols == 0) {
+                code.push(CODE_ROW_SEPARATOR);
+            }
+            code.push(if self.walls[i] {
+                CODE_WALL
+            } else if self.blocked[i] {
+                CODE_BLOCKED
+            } else if self.is_occupied(i) {
+                CODE_TILE
+            } else {
+                CODE_EMPTY
+            });
+        }
+        code
+    }
+
+    /// Returns a seeded Board with the given layout, or an error if `length` is not positive
+    /// and finite or `rows` or `cols` is `0`.
+    fn with_layout(layout: BoardLayout,
+                   rows: usize,
+                   cols: usize,
+                   length: f64,
+                   seed: usize)
+                   -> Result<Board, BoardError> {
+        if !length.is_finite() || (length <= 0.0) {
+            return Err(BoardError::InvalidLength(length));
+        }
+        if (rows == 0) || (cols == 0) {
+            return Err(BoardError::InvalidRowsCols(rows, cols));
+        }
+        let hex_cells = match layout {
+            BoardLayout::Square => Vec::new(),
+            BoardLayout::Hex { radius } => hex_cells(radius),
+        };
+        let n_cells = match layout {
+            BoardLayout::Square => rows * cols,
+            BoardLayout::Hex { .. } => hex_cells.len(),
+        };
+        Ok(Board {
+            tiles: Tiles::new(n_cells),
+            length: length,
+            origin: Vec2D::empty(),
+            layout: layout,
+            rows: rows,
+            cols: cols,
+            hex_cells: hex_cells,
+            blocked: vec![false; n_cells],
+            walls: vec![false; n_cells],
+            rng: StdRng::from_seed(&[seed]),
+            spawn_queue: VecDeque::with_capacity(SPAWN_QUEUE_LENGTH),
+            placement: Box::new(Uniform),
+            placement_context: PlacementContext {
+                cursor_cell: None,
+                rows: rows,
+                cols: cols,
+            },
+            recently_removed: Vec::new(),
+            spawn_counts: vec![0; n_cells],
+            theme: Theme::default(),
+        })
+    }
+
+    /// Moves the `Board` to `origin` and changes its longer side to `length`.
+    ///
+    /// Tiles, including those fading out, keep their positions relative to the `Board` and are
+    /// scaled to match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{Board, Vec2D};
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.add_tile_at(4).unwrap();
+    /// board.set_geometry(Vec2D::new(50.0, 0.0), 600.0);
+    /// assert_eq!(board.tiles[4].unwrap().sprite.get_rect(), [250.0, 200.0, 200.0, 200.0]);
+    /// ```
+    pub fn set_geometry(&mut self, origin: Vec2D, length: f64) {
+        let scale = length / self.length;
+        let old_origin = self.origin;
+        let rescale = |sprite: &mut Sprite| {
+            sprite.pos = origin + ((sprite.pos - old_origin) * scale);
+            sprite.width *= scale;
+            sprite.height *= scale;
+        };
+        for (_, tile) in self.tiles.iter_occupied_mut() {
+            rescale(&mut tile.sprite);
+        }
+        for fading in &mut self.recently_removed {
+            rescale(&mut fading.0);
+        }
+        self.origin = origin;
+        self.length = length;
+    }
+
+    /// Returns the arrangement of the cells on the `Board`.
+    pub fn layout(&self) -> BoardLayout {
+        self.layout
+    }
+
+    /// Returns the number of cells along the longer side of a square layout `Board`, or
+    /// across the widest row of a hex `Board`.
+    pub fn grid_dim(&self) -> usize {
+        self.rows.max(self.cols)
+    }
+
+    /// Returns the number of rows of cells, or the cells across the widest row of a hex
+    /// `Board`.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of cells in each row, or across the widest row of a hex `Board`.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the side length of each cell of a square layout `Board`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// assert_eq!(Board::from_length(300.0).cell_size(), 100.0);
+    /// ```
+    pub fn cell_size(&self) -> f64 {
+        self.length / self.grid_dim() as f64
+    }
+
+    /// Returns the distance from the left edge of the `Board` to its right edge.
+    ///
+    /// Only a square layout `Board` with fewer rows than columns is wider than it is tall, a
+    /// hex `Board` always fits a square of side `length`.
+    pub fn width(&self) -> f64 {
+        match self.layout {
+            BoardLayout::Square => self.cols as f64 * self.cell_size(),
+            BoardLayout::Hex { .. } => self.length,
+        }
+    }
+
+    /// Returns the distance from the top edge of the `Board` to its bottom edge.
+    pub fn height(&self) -> f64 {
+        match self.layout {
+            BoardLayout::Square => self.rows as f64 * self.cell_size(),
+            BoardLayout::Hex { .. } => self.length,
+        }
+    }
+
+    /// Returns a vector containing the indices of all the free positions on the `Board`.
+    ///
+    /// Blocked positions are never free.
+    pub fn free_positions(&self) -> Vec<usize> {
+        let positions: Vec<usize> = (0..self.tiles.len())
+            .filter(|&i| self.is_free(i))
+            .collect();
+        positions
+    }
+
+    /// True if position `i` is neither occupied nor blocked.
+    fn is_free(&self, i: usize) -> bool {
+        !self.is_occupied(i) && !self.blocked[i]
+    }
+
+    /// Permanently prevents tiles from being added at position `i`.
+    ///
+    /// Any tile already at the position is removed. A blocked position is not free, but it
+    /// does not count towards the `Board` being full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not a position on the `Board`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.block_cell(4);
+    /// assert!(board.is_blocked(4));
+    /// assert_eq!(board.free_positions().len(), 8);
+    /// ```
+    pub fn block_cell(&mut self, i: usize) {
+        self.blocked[i] = true;
+        self.tiles.remove(i);
+    }
+
+    /// True if position `i` holds a tile, positions that are not on the `Board` never do.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.add_tile_at(4).unwrap();
+    /// assert!(board.is_occupied(4));
+    /// assert!(!board.is_occupied(0));
+    /// assert!(!board.is_occupied(9));
+    /// ```
+    pub fn is_occupied(&self, i: usize) -> bool {
+        self.tiles.get(i).is_some()
+    }
+
+    /// True if position `i` is blocked, positions that are not on the `Board` are not blocked.
+    pub fn is_blocked(&self, i: usize) -> bool {
+        self.blocked.get(i).map_or(false, |&b| b)
+    }
+
+    /// Turns position `i` into a wall that the cursor cannot enter.
+    ///
+    /// Walls are also blocked, so tiles are never added to them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not a position on the `Board`.
+    pub fn add_wall(&mut self, i: usize) {
+        self.block_cell(i);
+        self.walls[i] = true;
+    }
+
+    /// True if position `i` is a wall, positions that are not on the `Board` are not walls.
+    pub fn is_wall(&self, i: usize) -> bool {
+        self.walls.get(i).map_or(false, |&w| w)
+    }
+
+    /// Returns a `Sprite` in the theme's wall colour covering each wall on the `Board`.
+    pub fn wall_sprites(&self) -> Vec<Sprite> {
+        let colour = self.theme.wall;
+        (0..self.walls.len())
+            .filter(|&i| self.walls[i])
+            .map(|i| {
+                let (width, height) = self.tile_size();
+                Sprite::new(self.x_from_index(i), self.y_from_index(i), width, height, colour)
+            })
+            .collect()
+    }
+
+    /// Sets the colours used for walls and tiles, recolouring the tiles already on the
+    /// `Board`.
+    ///
+    /// New tiles are varied from the theme's tile colour by its `tile_variation`, if any, see
+    /// `colours::vary`, but recoloured tiles all take the tile colour exactly.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.recolour_tiles(theme.tile);
+        for (_, tile) in self.tiles.iter_occupied_mut() {
+            if let Some((ref mut border, _)) = tile.sprite.border {
+                *border = theme.tile_border;
+            }
+        }
+    }
+
+    /// Sets the colour of every tile on the `Board`, keeping their alpha.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::colours;
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.add_tile_at(1).unwrap();
+    /// board.recolour_tiles(colours::GREEN);
+    /// assert_eq!(board.tiles[1].unwrap().sprite.colour, colours::GREEN);
+    /// ```
+    pub fn recolour_tiles(&mut self, colour: Colour) {
+        for (_, tile) in self.tiles.iter_occupied_mut() {
+            let alpha = tile.sprite.colour[3];
+            tile.sprite.colour = colour;
+            tile.sprite.colour[3] = alpha;
+        }
+    }
+
+    /// Returns the colours used for walls and tiles.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// True if there are no free positions on the `Board`.
+    pub fn is_full(&self) -> bool {
+        if self.free_positions().is_empty() {
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Adds a tile to the position returned by `next_spawn`, returning that position or
+    /// `None` if the `Board` is full.
+    pub fn add_tile(&mut self) -> Option<usize> {
+        let new_pos = self.next_spawn();
+        if let Some(i) = new_pos {
+            self.spawn_queue.pop_front();
+            self.place_tile(i);
+        }
+        new_pos
+    }
+
+    /// Returns the id of the tile at position `i`, or `None` if there isn't one.
+    pub fn tile_id(&self, i: usize) -> Option<u64> {
+        self.tiles.get(i).map(|t| t.sprite.id)
+    }
+
+    /// Adds a tile at position `i`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{Board, BoardError};
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// assert!(board.add_tile_at(3).is_ok());
+    /// assert_eq!(board.add_tile_at(3), Err(BoardError::Occupied(3)));
+    /// assert_eq!(board.add_tile_at(9), Err(BoardError::OutOfRange(9)));
+    /// ```
+    pub fn add_tile_at(&mut self, i: usize) -> Result<(), BoardError> {
+        if i >= self.tiles.len() {
+            return Err(BoardError::OutOfRange(i));
+        }
+        if self.blocked[i] {
+            return Err(BoardError::Blocked(i));
+        }
+        if self.is_occupied(i) {
+            return Err(BoardError::Occupied(i));
+        }
+        self.place_tile(i);
+        Ok(())
+    }
+
+    /// Adds tiles to every free position covered by a `Pattern`.
+    ///
+    /// Returns the indices of the tiles that were added, positions that are already occupied
+    /// are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{Board, Pattern};
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// assert_eq!(board.spawn_pattern(Pattern::Diagonal), vec![0, 4, 8]);
+    /// assert_eq!(board.spawn_pattern(Pattern::Row(1)), vec![3, 5]);
+    /// ```
+    pub fn spawn_pattern(&mut self, pattern: Pattern) -> Vec<usize> {
+        let placed: Vec<usize> = self.pattern_indices(pattern)
+            .into_iter()
+            .filter(|&i| self.is_free(i))
+            .collect();
+        for &i in &placed {
+            self.place_tile(i);
+            if let Some(tile) = self.tiles.get_mut(i) {
+                tile.kind = TileKind::Pattern;
+            }
+        }
+        placed
+    }
+
+    /// Returns the indices of the positions on this `Board` covered by a `Pattern`.
+    ///
+    /// Rows and columns that are not on the `Board` cover no positions.
+    ///
+    /// On a hex `Board`, rows and columns follow the `r` and `q` axial axes counted from the
+    /// top left, `Diagonal` is the remaining axis through the centre cell and `AntiDiagonal`
+    /// is the centre column.
+    pub fn pattern_indices(&self, pattern: Pattern) -> Vec<usize> {
+        let radius = match self.layout {
+            BoardLayout::Square => return pattern.rect_indices(self.rows, self.cols),
+            BoardLayout::Hex { radius } => radius as i32,
+        };
+        (0..self.hex_cells.len())
+            .filter(|&i| {
+                let (q, r) = self.hex_cells[i];
+                match pattern {
+                    Pattern::Row(n) => r == n as i32 - radius,
+                    Pattern::Column(n) => q == n as i32 - radius,
+                    Pattern::Diagonal => q + r == 0,
+                    Pattern::AntiDiagonal => q == 0,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a randomly chosen `Pattern`.
+    pub fn random_pattern(&mut self) -> Pattern {
+        match self.rng.gen_range(0, 4) {
+            0 => Pattern::Row(self.rng.gen_range(0, self.rows)),
+            1 => Pattern::Column(self.rng.gen_range(0, self.cols)),
+            2 => Pattern::Diagonal,
+            _ => Pattern::AntiDiagonal,
+        }
+    }
+
+    /// Creates a new tile at the free position `i`.
+    ///
+    /// The tile's colour is varied using the number of tiles added so far as the seed, and it
+    /// is given a new id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` already holds a tile or is not a position.
+    fn place_tile(&mut self, i: usize) {
+        let (width, height) = self.tile_size();
+        let spawned: u32 = self.spawn_counts.iter().sum();
+        let colour = colours::vary(self.theme.tile, spawned as u64, self.theme.tile_variation);
+        let mut new_tile = Sprite::builder()
+            .pos(Vec2D::new(self.x_from_index(i), self.y_from_index(i)))
+            .size(width, height)
+            .colour(colour)
+            .border(self.theme.tile_border, TILE_BORDER_THICKNESS)
+            .build();
+        new_tile.id = NEXT_TILE_ID.fetch_add(1, Ordering::Relaxed) as u64;
+        self.tiles
+            .insert(i, Tile::new(new_tile))
+            .expect("tiles are only placed at free positions");
+        self.spawn_counts[i] += 1;
+    }
+
+    /// Returns the number of tiles that have been added at each position.
+    ///
+    /// The counts survive `clear_board` and are only zeroed by `reset_stats`.
+    pub fn spawn_histogram(&self) -> &[u32] {
+        &self.spawn_counts
+    }
+
+    /// Zeroes the spawn counts returned by `spawn_histogram`.
+    pub fn reset_stats(&mut self) {
+        for count in &mut self.spawn_counts {
+            *count = 0;
+        }
+    }
+
+    /// Returns Pearson's chi-squared statistic for the spawn counts against a uniform
+    /// distribution over the unblocked positions.
+    ///
+    /// Larger values indicate a more skewed distribution, `0.0` is returned if no tiles have
+    /// been added.
+    pub fn chi_squared_uniformity(&self) -> f64 {
+        let counts: Vec<f64> = (0..self.spawn_counts.len())
+            .filter(|&i| !self.blocked[i])
+            .map(|i| self.spawn_counts[i] as f64)
+            .collect();
+        let total: f64 = counts.iter().sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        let expected = total / counts.len() as f64;
+        counts.iter().map(|&c| (c - expected).powi(2) / expected).sum()
+    }
+
+    /// Returns the index of the position where the next tile will be added.
+    ///
+    /// Positions are scheduled in advance so that the upcoming spawn can be shown to the
+    /// player. Any scheduled position that has become occupied since it was chosen is
+    /// discarded and replaced, so the returned index is always free. Returns `None` if
+    /// the `Board` is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// let i = board.next_spawn().unwrap();
+    /// board.add_tile();
+    /// assert!(board.tiles[i].is_some());
+    /// ```
+    pub fn next_spawn(&mut self) -> Option<usize> {
+        while let Some(&i) = self.spawn_queue.front() {
+            if self.is_free(i) {
+                break;
+            }
+            self.spawn_queue.pop_front();
+        }
+        self.fill_spawn_queue();
+        self.spawn_queue.front().cloned()
+    }
+
+    /// Tops up the spawn queue with random free positions.
+    fn fill_spawn_queue(&mut self) {
+        while self.spawn_queue.len() < SPAWN_QUEUE_LENGTH {
+            match self.random_position() {
+                Some(i) => self.spawn_queue.push_back(i),
+                None => break,
+            }
+        }
+    }
+
+    /// Sets the `PlacementStrategy` used to choose where new tiles are added.
+    ///
+    /// Positions that have already been scheduled, see `next_spawn`, are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{AvoidRecent, Board};
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.set_placement(Box::new(AvoidRecent::new(3)));
+    /// board.add_tile();
+    /// ```
+    pub fn set_placement(&mut self, placement: Box<PlacementStrategy>) {
+        self.placement = placement;
+    }
+
+    /// Tells the `PlacementStrategy` which cell the cursor is in.
+    ///
+    /// The `PlacementContext` only describes square grids, so on a hex `Board` the cursor is
+    /// always reported as being off the `Board`.
+    pub fn set_cursor_cell(&mut self, cursor_cell: Option<usize>) {
+        self.placement_context.cursor_cell = match self.layout {
+            BoardLayout::Square => cursor_cell,
+            BoardLayout::Hex { .. } => None,
+        };
+    }
+
+    /// Chooses an index from the free positions that are not already queued using the
+    /// `Board`'s `PlacementStrategy`.
+    fn random_position(&mut self) -> Option<usize> {
+        let free_positions: Vec<usize> = self.free_positions()
+            .into_iter()
+            .filter(|i| !self.spawn_queue.contains(i))
+            .collect();
+        if free_positions.is_empty() {
+            return None;
+        }
+        Some(self.placement.choose(&free_positions, &self.placement_context, &mut self.rng))
+    }
+
+    /// Calculates the x coordinate of the left edge of the tile at a position on the `Board`
+    /// from its index.
+    pub fn x_from_index(&self, i: usize) -> f64 {
+        match self.layout {
+            BoardLayout::Square => self.origin.x + ((i % self.cols) as f64 * self.cell_size()),
+            BoardLayout::Hex { .. } => self.cell_center(i).x - (self.tile_size().0 / 2.0),
+        }
+    }
+
+    /// Calculates the y coordinate of the top edge of the tile at a position on the `Board`
+    /// from its index.
+    pub fn y_from_index(&self, i: usize) -> f64 {
+        match self.layout {
+            BoardLayout::Square => self.origin.y + ((i / self.cols) as f64 * self.cell_size()),
+            BoardLayout::Hex { .. } => self.cell_center(i).y - (self.tile_size().1 / 2.0),
+        }
+    }
+
+    /// Returns the screen position of the centre of the cell at position `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not a position on a hex `Board`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{Board, Vec2D};
+    ///
+    /// assert_eq!(Board::from_length(300.0).cell_center(4), Vec2D::new(150.0, 150.0));
+    /// assert_eq!(Board::hex(1, 300.0).cell_center(3), Vec2D::new(150.0, 150.0));
+    /// ```
+    pub fn cell_center(&self, i: usize) -> Vec2D {
+        match self.layout {
+            BoardLayout::Square => {
+                let tile_length = self.cell_size();
+                Vec2D::new(self.x_from_index(i) + (tile_length / 2.0),
+                           self.y_from_index(i) + (tile_length / 2.0))
+            }
+            BoardLayout::Hex { .. } => {
+                let (q, r) = self.hex_cells[i];
+                let size = self.hex_size();
+                Vec2D::new(self.origin.x + (self.length / 2.0) + (size * 1.5 * q as f64),
+                           self.origin.y + (self.length / 2.0) +
+                           (size * 3f64.sqrt() * (r as f64 + (q as f64 / 2.0))))
+            }
+        }
+    }
+
+    /// Returns the width and height of a tile.
+    ///
+    /// Tiles on a hex `Board` are the largest rectangle that fits inside a cell.
+    pub fn tile_size(&self) -> (f64, f64) {
+        match self.layout {
+            BoardLayout::Square => (self.cell_size(), self.cell_size()),
+            BoardLayout::Hex { .. } => {
+                let size = self.hex_size();
+                (size, size * 3f64.sqrt())
+            }
+        }
+    }
+
+    /// Returns the bounding box of the tile at position `i`.
+    pub fn cell_aabb(&self, i: usize) -> Aabb {
+        let (width, height) = self.tile_size();
+        Aabb::from_pos_size(Vec2D::new(self.x_from_index(i), self.y_from_index(i)),
+                            width,
+                            height)
+    }
+
+    /// Returns the `[x, y, width, height]` rectangle of the tile at position `i`.
+    pub fn cell_rect(&self, i: usize) -> [f64; 4] {
+        self.cell_aabb(i).to_rect()
+    }
+
+    /// Returns the distance from the centre of a hex cell to its corners, chosen so that the
+    /// whole hexagon of cells fits on the `Board`.
+    fn hex_size(&self) -> f64 {
+        let radius = match self.layout {
+            BoardLayout::Square => return 0.0,
+            BoardLayout::Hex { radius } => radius as f64,
+        };
+        (self.length / ((3.0 * radius) + 2.0))
+            .min(self.length / (3f64.sqrt() * ((2.0 * radius) + 1.0)))
+    }
+
+    /// Returns the axial coordinates `(q, r)` of position `i` on a hex `Board`, or `None` if
+    /// the `Board` is not hexagonal or `i` is not on it.
+    ///
+    /// The centre cell is `(0, 0)`, `q` increases to the right and `r` increases downwards.
+    pub fn hex_coord(&self, i: usize) -> Option<(i32, i32)> {
+        self.hex_cells.get(i).cloned()
+    }
+
+    /// Returns the index of the cell with axial coordinates `(q, r)` on a hex `Board`, or
+    /// `None` if there is no such cell.
+    pub fn index_from_hex(&self, q: i32, r: i32) -> Option<usize> {
+        self.hex_cells.iter().position(|&c| c == (q, r))
+    }
+
+    /// Returns the index of the cell next to position `i` in `direction` on a hex `Board`, or
+    /// `None` if that would be off the `Board`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{Board, HexDirection};
+    ///
+    /// let board = Board::hex(1, 300.0);
+    /// assert_eq!(board.hex_neighbour(3, HexDirection::North), Some(0));
+    /// assert_eq!(board.hex_neighbour(0, HexDirection::North), None);
+    /// ```
+    pub fn hex_neighbour(&self, i: usize, direction: HexDirection) -> Option<usize> {
+        let (q, r) = match self.hex_coord(i) {
+            Some(c) => c,
+            None => return None,
+        };
+        let (dq, dr) = direction.offset();
+        self.index_from_hex(q + dq, r + dr)
+    }
+
+    /// Returns the index of the cell whose centre is nearest to `pos`, the first such cell if
+    /// several are equally near.
+    fn nearest_cell(&self, pos: Vec2D) -> usize {
+        let distance = |i: usize| (self.cell_center(i) - pos).length_squared();
+        (1..self.tiles.len()).fold(0, |nearest, i| {
+            if distance(i) < distance(nearest) {
+                i
+            } else {
+                nearest
+            }
+        })
+    }
+
+    /// True if `pos` lies on the `Board`.
+    pub fn contains_pos(&self, pos: Vec2D) -> bool {
+        self.index_from_pos(pos).is_some()
+    }
+
+    /// Returns the index of the cell containing `pos`, or `None` if `pos` is not on the `Board`.
+    ///
+    /// Cells are half-open, so a point on the boundary between two cells belongs to the cell
+    /// to its right or below.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::{Board, Vec2D};
+    ///
+    /// let board = Board::from_length(300.0);
+    /// assert_eq!(board.index_from_pos(Vec2D::new(150.0, 150.0)), Some(4));
+    /// assert_eq!(board.index_from_pos(Vec2D::new(100.0, 0.0)), Some(1));
+    /// assert_eq!(board.index_from_pos(Vec2D::new(300.0, 0.0)), None);
+    /// ```
+    pub fn index_from_pos(&self, pos: Vec2D) -> Option<usize> {
+        if let BoardLayout::Hex { .. } = self.layout {
+            let size = self.hex_size();
+            let x = pos.x - self.origin.x - (self.length / 2.0);
+            let y = pos.y - self.origin.y - (self.length / 2.0);
+            let q = (2.0 / 3.0 * x) / size;
+            let r = ((-x / 3.0) + (3f64.sqrt() / 3.0 * y)) / size;
+            let (q, r) = hex_round(q, r);
+            return self.index_from_hex(q, r);
+        }
+        let x = pos.x - self.origin.x;
+        let y = pos.y - self.origin.y;
+        if (x < 0.0) || (y < 0.0) || (x >= self.width()) || (y >= self.height()) {
+            return None;
+        }
+        let tile_length = self.cell_size();
+        let column = ((x / tile_length).floor() as usize).min(self.cols - 1);
+        let row = ((y / tile_length).floor() as usize).min(self.rows - 1);
+        Some(row * self.cols + column)
+    }
+
+    /// Returns an iterator over the index and sprite of every tile on the `Board`, in index
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.add_tile_at(5).unwrap();
+    /// board.add_tile_at(1).unwrap();
+    /// let indices: Vec<usize> = board.iter_tiles().map(|(i, _)| i).collect();
+    /// assert_eq!(indices, vec![1, 5]);
+    /// ```
+    pub fn iter_tiles(&self) -> impl Iterator<Item = (usize, &Sprite)> {
+        self.tiles.iter_occupied().map(|(i, t)| (i, &t.sprite))
+    }
+
+    /// Removes and returns the tile at position `i`, leaving its sprite to fade out.
+    ///
+    /// At most `MAX_FADING_TILES` sprites fade at once, the oldest is dropped to make room.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.add_tile_at(2).unwrap();
+    /// assert!(board.remove_tile(2).is_some());
+    /// assert!(board.tiles[2].is_none());
+    /// assert_eq!(board.fading_tiles().len(), 1);
+    /// ```
+    pub fn remove_tile(&mut self, i: usize) -> Option<Tile> {
+        let removed = self.tiles.remove(i);
+        if let Some(tile) = removed {
+            if self.recently_removed.len() == MAX_FADING_TILES {
+                self.recently_removed.remove(0);
+            }
+            self.recently_removed.push((tile.sprite, FADE_DURATION));
+        }
+        removed
+    }
+
+    /// Stops the removed tile with sprite `id` from fading out, so that it is no longer drawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.add_tile_at(2).unwrap();
+    /// let tile = board.remove_tile(2).unwrap();
+    /// board.stop_fading(tile.sprite.id);
+    /// assert!(board.fading_tiles().is_empty());
+    /// ```
+    pub fn stop_fading(&mut self, id: u64) {
+        self.recently_removed.retain(|f| f.0.id != id);
+    }
+
+    /// Returns the sprites of recently removed tiles, with their alpha reduced in proportion to
+    /// how far they have faded.
+    pub fn fading_tiles(&self) -> Vec<Sprite> {
+        self.recently_removed
+            .iter()
+            .map(|&(sprite, time_left)| {
+                let alpha = (time_left / FADE_DURATION) as f32;
+                let mut faded = sprite;
+                faded.colour[3] *= alpha;
+                if let Some((ref mut colour, _)) = faded.border {
+                    colour[3] *= alpha;
+                }
+                faded
+            })
+            .collect()
+    }
+
+    /// Advances the age of every tile on the `Board` by `dt` seconds, steps any moving tile
+    /// sprites and fades out removed tiles, discarding any that have disappeared completely.
+    pub fn tick(&mut self, dt: f64) {
+        for (_, tile) in self.tiles.iter_occupied_mut() {
+            tile.age += dt;
+            if tile.sprite.is_moving() {
+                tile.sprite.step(dt);
+            }
+        }
+        for fading in &mut self.recently_removed {
+            fading.1 -= dt;
+        }
+        self.recently_removed.retain(|f| f.1 > 0.0);
+    }
+
+    /// Returns the tile that has been on the `Board` the longest along with its index.
+    ///
+    /// If several tiles share the greatest age, the one with the lowest index is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// assert!(board.oldest_tile().is_none());
+    /// board.add_tile();
+    /// board.tick(1.0);
+    /// board.add_tile();
+    /// assert_eq!(board.oldest_tile().unwrap().1.age, 1.0);
+    /// ```
+    pub fn oldest_tile(&self) -> Option<(usize, &Tile)> {
+        let mut oldest: Option<(usize, &Tile)> = None;
+        for (i, t) in self.tiles.iter_occupied() {
+            if oldest.map_or(true, |o| t.age > o.1.age) {
+                oldest = Some((i, t));
+            }
+        }
+        oldest
+    }
+
+    /// Returns the index and age of every tile on the `Board`, in index order.
+    pub fn ages(&self) -> Vec<(usize, f64)> {
+        self.tiles.iter_occupied().map(|(i, t)| (i, t.age)).collect()
+    }
+
+    /// Moves every tile to a new, randomly chosen set of unblocked positions.
+    ///
+    /// The number of tiles and their ages are preserved. If there is more than one possible
+    /// arrangement, the new arrangement is guaranteed to differ from the current one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Board;
+    ///
+    /// let mut board = Board::from_length(300.0);
+    /// board.add_tile();
+    /// board.add_tile();
+    /// let before = board.free_positions();
+    /// board.shuffle();
+    /// assert_eq!(board.free_positions().len(), 7);
+    /// assert!(board.free_positions() != before);
+    /// ```
+    pub fn shuffle(&mut self) {
+        let occupied: Vec<usize> = (0..self.tiles.len())
+            .filter(|&i| self.is_occupied(i))
+            .collect();
+        let unblocked: Vec<usize> = (0..self.tiles.len())
+            .filter(|&i| !self.blocked[i])
+            .collect();
+        if occupied.is_empty() || (occupied.len() == unblocked.len()) {
+            return;
+        }
+        let mut new_positions = occupied.clone();
+        while new_positions == occupied {
+            new_positions = sample(&mut self.rng, unblocked.iter().cloned(), occupied.len());
+            new_positions.sort();
+        }
+        self.rng.shuffle(&mut new_positions);
+        let mut tiles: Vec<Tile> = occupied.iter().filter_map(|&i| self.tiles.remove(i)).collect();
+        for (tile, &i) in tiles.iter_mut().zip(&new_positions) {
+            tile.sprite.pos = Vec2D::new(self.x_from_index(i), self.y_from_index(i));
+        }
+        for (tile, i) in tiles.into_iter().zip(new_positions) {
+            self.tiles.insert(i, tile).expect("shuffled tiles move to empty positions");
+        }
+    }
+
+    /// Removes all tiles from the `Board`, blocked positions remain blocked.
+    pub fn clear_board(&mut self) {
+        self.tiles.clear();
+        self.recently_removed.clear();
+    }
+}
+
+/// Returns the axial coordinates of every cell in a hexagon with `radius` rings around the
+/// centre cell, ordered by row and then from left to right.
+fn hex_cells(radius: usize) -> Vec<(i32, i32)> {
+    let radius = radius as i32;
+    let mut cells = Vec::new();
+    for r in -radius..(radius + 1) {
+        for q in -radius..(radius + 1) {
+            if (q + r).abs() <= radius {
+                cells.push((q, r));
+            }
+        }
+    }
+    cells
+}
+
+/// Rounds fractional axial coordinates to those of the hex cell containing them.
+fn hex_round(q: f64, r: f64) -> (i32, i32) {
+    let s = -q - r;
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    if (dq > dr) && (dq > ds) {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    (rq as i32, rr as i32)
+}
+
+/// Information about the game that a `PlacementStrategy` can use when choosing a position.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PlacementContext {
+    /// The index of the cell that the cursor is in, if it is on the `Board`.
+    pub cursor_cell: Option<usize>,
+    /// The number of rows of cells on the `Board`.
+    pub rows: usize,
+    /// The number of cells in each row of the `Board`.
+    pub cols: usize,
+}
+
+/// A policy for choosing the position of a new tile on the `Board`.
+pub trait PlacementStrategy: fmt::Debug {
+    /// Returns one of the indices in `free`, which is never empty.
+    fn choose(&mut self, free: &[usize], context: &PlacementContext, rng: &mut Rng) -> usize;
+}
+
+/// Chooses uniformly between all free positions.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Uniform;
+
+impl PlacementStrategy for Uniform {
+    fn choose(&mut self, free: &[usize], _context: &PlacementContext, rng: &mut Rng) -> usize {
+        let i = (rng.next_f64() * free.len() as f64) as usize;
+        free[i.min(free.len() - 1)]
+    }
+}
+
+/// Chooses uniformly between free positions that were not among the last `memory` choices.
+///
+/// If every free position was chosen recently, it chooses uniformly between all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvoidRecent {
+    memory: usize,
+    recent: VecDeque<usize>,
+}
+
+impl AvoidRecent {
+    /// Returns a new `AvoidRecent` that remembers the last `memory` positions it chose.
+    pub fn new(memory: usize) -> AvoidRecent {
+        AvoidRecent {
+            memory: memory,
+            recent: VecDeque::with_capacity(memory),
+        }
+    }
+}
+
+impl PlacementStrategy for AvoidRecent {
+    fn choose(&mut self, free: &[usize], context: &PlacementContext, rng: &mut Rng) -> usize {
+        let fresh: Vec<usize> = free.iter()
+            .cloned()
+            .filter(|i| !self.recent.contains(i))
+            .collect();
+        let choice = if fresh.is_empty() {
+            Uniform.choose(free, context, rng)
+        } else {
+            Uniform.choose(&fresh, context, rng)
+        };
+        if self.memory > 0 {
+            if self.recent.len() == self.memory {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(choice);
+        }
+        choice
+    }
+}
+
+/// Chooses free positions with a probability proportional to their distance, in cells, from
+/// the cursor.
+///
+/// The cursor's own cell is never chosen unless it is the only free position. If the cursor
+/// is not on the `Board`, it chooses uniformly.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WeightedByDistanceFromCursor;
+
+impl PlacementStrategy for WeightedByDistanceFromCursor {
+    fn choose(&mut self, free: &[usize], context: &PlacementContext, rng: &mut Rng) -> usize {
+        let cursor = match context.cursor_cell {
+            Some(c) => c,
+            None => return Uniform.choose(free, context, rng),
+        };
+        let cols = context.cols;
+        let weights: Vec<f64> = free.iter()
+            .map(|&i| {
+                let dx = (i % cols) as f64 - (cursor % cols) as f64;
+                let dy = (i / cols) as f64 - (cursor / cols) as f64;
+                (dx * dx + dy * dy).sqrt()
+            })
+            .collect();
+        choose_weighted(free, &weights, context, rng)
+    }
+}
+
+/// Biases the positions of new tiles towards a region of a square layout `Board`.
+///
+/// # Examples
+///
+/// ```
+/// use whack::gobs::{Board, SpawnBias};
+///
+/// let mut board = Board::from_length(300.0);
+/// board.set_placement(Box::new(SpawnBias::Corners));
+/// board.add_tile();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SpawnBias {
+    /// Every free position is equally likely, as with `Uniform`.
+    Uniform,
+    /// Positions further from the centre of the `Board` are more likely, with the corners the
+    /// most likely of all.
+    Corners,
+    /// Positions closer to the centre of the `Board` are more likely.
+    Center,
+    /// Positions along the sides of the `Board`, other than the corners, are more likely.
+    Edges,
+}
+
+impl SpawnBias {
+    /// Returns the relative likelihood of choosing position `i` on a `Board` with `rows` rows
+    /// of `cols` cells.
+    fn weight(&self, i: usize, rows: usize, cols: usize) -> f64 {
+        let mid_x = (cols as f64 - 1.0) / 2.0;
+        let mid_y = (rows as f64 - 1.0) / 2.0;
+        let dx = ((i % cols) as f64 - mid_x).abs();
+        let dy = ((i / cols) as f64 - mid_y).abs();
+        match *self {
+            SpawnBias::Uniform => 1.0,
+            SpawnBias::Corners => 1.0 + (dx + dy).powi(2),
+            SpawnBias::Center => 1.0 + ((mid_x + mid_y) - (dx + dy)).powi(2),
+            SpawnBias::Edges => {
+                let on_side = (dx == mid_x) || (dy == mid_y);
+                let corner = (dx == mid_x) && (dy == mid_y);
+                if on_side && !corner { 4.0 } else { 1.0 }
+            }
+        }
+    }
+}
+
+impl PlacementStrategy for SpawnBias {
+    fn choose(&mut self, free: &[usize], context: &PlacementContext, rng: &mut Rng) -> usize {
+        if *self == SpawnBias::Uniform {
+            return Uniform.choose(free, context, rng);
+        }
+        let weights: Vec<f64> = free.iter()
+            .map(|&i| self.weight(i, context.rows, context.cols))
+            .collect();
+        choose_weighted(free, &weights, context, rng)
+    }
+}
+
+/// Chooses one of the indices in `free` with a probability proportional to its weight in
+/// `weights`, falling back to `Uniform` if every weight is zero.
+fn choose_weighted(free: &[usize],
+                   weights: &[f64],
+                   context: &PlacementContext,
+                   rng: &mut Rng)
+                   -> usize {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return Uniform.choose(free, context, rng);
+    }
+    let mut target = rng.next_f64() * total;
+    for (&i, &w) in free.iter().zip(weights) {
+        if target < w {
+            return i;
+        }
+        target -= w;
+    }
+    free[free.len() - 1]
+}
+
+/// Which cells of a small `Board` hold tiles, packed into a bitmask so that it can be
+/// compared and hashed cheaply, for example to cache the results of searching for moves.
+///
+/// Bit `i` is set if cell `i` holds a tile.
+///
+/// # Examples
+///
+/// ```
+/// use whack::gobs::{Board, BoardState};
+///
+/// let mut board = Board::from_length(300.0);
+/// board.add_tile_at(0).unwrap();
+/// board.add_tile_at(4).unwrap();
+/// let state = BoardState::from(&board);
+/// assert_eq!(state.bits(), 0b1_0001);
+/// assert!(state.is_occupied(4));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BoardState(u16);
+
+impl BoardState {
+    /// The most cells that a `BoardState` can describe.
+    pub const MAX_CELLS: usize = 16;
+
+    /// Returns the occupancy bitmask.
+    pub fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// True if cell `i` holds a tile, cells past `MAX_CELLS` never do.
+    pub fn is_occupied(&self, i: usize) -> bool {
+        (i < BoardState::MAX_CELLS) && ((self.0 >> i) & 1 == 1)
+    }
+
+    /// Returns the number of cells that hold a tile.
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+}
+
+impl<'a> From<&'a Board> for BoardState {
+    /// Returns the `BoardState` of `board`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `board` has more than `BoardState::MAX_CELLS` cells.
+    fn from(board: &'a Board) -> BoardState {
+        assert!(board.tiles.len() <= BoardState::MAX_CELLS,
+                "a board with {} cells is too big for a BoardState",
+                board.tiles.len());
+        BoardState(board.tiles
+            .iter_occupied()
+            .fold(0, |bits, (i, _)| bits | (1 << i)))
+    }
+}
+
+/// Errors that can occur when reading a `Board` from a code, see `Board::from_code`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ParseError {
+    /// The code has this many cells, which cannot be arranged in a square.
+    NotSquare(usize),
+    /// The code of a hexagonal board has this many cells, which cannot be arranged in a
+    /// hexagon.
+    NotHex(usize),
+    /// The row at this index has a different number of cells to the first row.
+    UnevenRows(usize),
+    /// The character at this position of the code, counting from `0`, is not a cell.
+    InvalidCell(usize, char),
+    /// The `Board` itself could not be made.
+    Board(BoardError),
+}
+
+impl From<BoardError> for ParseError {
+    fn from(e: BoardError) -> ParseError {
+        ParseError::Board(e)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::NotSquare(n) => write!(f, "{} cells cannot make a square board", n),
+            ParseError::NotHex(n) => write!(f, "{} cells cannot make a hexagonal board", n),
+            ParseError::UnevenRows(r) => {
+                write!(f, "row {} of the code is a different length to the first", r)
+            }
+            ParseError::InvalidCell(i, c) => {
+                write!(f, "unknown cell {:?} at position {} of the code", c, i)
+            }
+            ParseError::Board(ref e) => write!(f, "invalid board: {}", e),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        match *self {
+            ParseError::NotSquare(_) => "cells cannot make a square board",
+            ParseError::NotHex(_) => "cells cannot make a hexagonal board",
+            ParseError::UnevenRows(_) => "rows of the code are different lengths",
+            ParseError::InvalidCell(..) => "unknown cell in the code",
+            ParseError::Board(_) => "invalid board",
+        }
+    }
+}
+
+/// Errors that can occur when modifying a `Board`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BoardError {
+    /// The length of the `Board` is not positive and finite.
+    InvalidLength(f64),
+    /// The `Board` would have no cells along each side.
+    InvalidGridDim(usize),
+    /// The `Board` would have this many rows and columns, at least one of which is `0`.
+    InvalidRowsCols(usize, usize),
+    /// The index is not a position on the `Board`.
+    OutOfRange(usize),
+    /// The position already holds a tile.
+    Occupied(usize),
+    /// The position is blocked and can never hold a tile.
+    Blocked(usize),
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BoardError::InvalidLength(l) => {
+                write!(f, "board length must be positive and finite, got {}", l)
+            }
+            BoardError::InvalidGridDim(n) => {
+                write!(f, "board must have at least one cell along each side, got {}", n)
+            }
+            BoardError::InvalidRowsCols(rows, cols) => {
+                write!(f,
+                       "board must have at least one row and column, got {} rows and {} columns",
+                       rows,
+                       cols)
+            }
+            BoardError::OutOfRange(i) => write!(f, "position {} is not on the board", i),
+            BoardError::Occupied(i) => write!(f, "position {} is already occupied", i),
+            BoardError::Blocked(i) => write!(f, "position {} is blocked", i),
+        }
+    }
+}
+
+impl Error for BoardError {
+    fn description(&self) -> &str {
+        match *self {
+            BoardError::InvalidLength(_) => "board length must be positive and finite",
+            BoardError::InvalidGridDim(_) => "board must have at least one cell along each side",
+            BoardError::InvalidRowsCols(..) => "board must have at least one row and column",
+            BoardError::OutOfRange(_) => "position is not on the board",
+            BoardError::Occupied(_) => "position is already occupied",
+            BoardError::Blocked(_) => "position is blocked",
+        }
+    }
+}
+
+/// Represents a shape of tiles that can be spawned on the `Board` in one go.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Pattern {
+    /// A full row, counted from the top.
+    Row(usize),
+    /// A full column, counted from the left.
+    Column(usize),
+    /// The diagonal from the top left to the bottom right.
+    Diagonal,
+    /// The diagonal from the top right to the bottom left.
+    AntiDiagonal,
+}
+
+impl Pattern {
+    /// Returns the indices of the `Board` positions covered by the `Pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Pattern;
+    ///
+    /// assert_eq!(Pattern::Column(2).indices(), vec![2, 5, 8]);
+    /// assert_eq!(Pattern::AntiDiagonal.indices(), vec![2, 4, 6]);
+    /// ```
+    pub fn indices(&self) -> Vec<usize> {
+        self.grid_indices(DEFAULT_GRID_DIM)
+    }
+
+    /// Returns the indices of the positions covered by the `Pattern` on a square `Board` with
+    /// `grid_dim` cells along each side.
+    ///
+    /// Rows and columns that are not on the `Board` cover no positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Pattern;
+    ///
+    /// assert_eq!(Pattern::Row(3).grid_indices(4), vec![12, 13, 14, 15]);
+    /// assert_eq!(Pattern::AntiDiagonal.grid_indices(4), vec![3, 6, 9, 12]);
+    /// assert!(Pattern::Column(4).grid_indices(4).is_empty());
+    /// ```
+    pub fn grid_indices(&self, grid_dim: usize) -> Vec<usize> {
+        self.rect_indices(grid_dim, grid_dim)
+    }
+
+    /// Returns the indices of the positions covered by the `Pattern` on a `Board` with `rows`
+    /// rows of `cols` cells.
+    ///
+    /// The diagonals start in the top corners and stop at whichever of the bottom row or the
+    /// far column they reach first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::gobs::Pattern;
+    ///
+    /// assert_eq!(Pattern::Row(1).rect_indices(2, 5), vec![5, 6, 7, 8, 9]);
+    /// assert_eq!(Pattern::Column(4).rect_indices(2, 5), vec![4, 9]);
+    /// assert_eq!(Pattern::AntiDiagonal.rect_indices(2, 5), vec![4, 8]);
+    /// ```
+    pub fn rect_indices(&self, rows: usize, cols: usize) -> Vec<usize> {
+        let n = rows.min(cols);
+        match *self {
+            Pattern::Row(r) if r < rows => (0..cols).map(|c| (r * cols) + c).collect(),
+            Pattern::Column(c) if c < cols => (0..rows).map(|r| (r * cols) + c).collect(),
+            Pattern::Row(_) | Pattern::Column(_) => Vec::new(),
+            Pattern::Diagonal => (0..n).map(|i| (i * cols) + i).collect(),
+            Pattern::AntiDiagonal => (0..n).map(|i| (i * cols) + (cols - 1 - i)).collect(),
+        }
+    }
+}
+
+/// Represents the tile positions of the game `Board`, each of which may hold a `Tile`.
+///
+/// # Migrating from 0.1
+///
+/// Before version 0.2.0 `Tiles` was an alias for `[Option<Sprite>; 9]`. Indexing still works,
+/// but each position now holds an `Option<Tile>`, with the sprite available as `tile.sprite`.
+/// Code that filtered `tiles.iter()` for occupied positions should use `iter_occupied`, and
+/// code that built an array literal should use `Tiles::new`.
+///
+/// Indexing is read only: positions are filled with `insert` and emptied with `remove`, and
+/// the tiles themselves are changed through `get_mut` or `iter_occupied_mut`.
+///
+/// `Tiles` is a raw store that only keeps one tile per position. It does not count spawns,
+/// give tiles ids or fade removed tiles, so the tiles of a `Board` should go through
+/// `Board::add_tile_at` and `Board::remove_tile` rather than `insert` and `remove`.
+///
+/// # Examples
+///
+/// ```
+/// use whack::colours;
+/// use whack::gobs::{Sprite, Tile, Tiles};
+///
+/// let mut tiles = Tiles::new(9);
+/// let tile = Tile::new(Sprite::new(100.0, 100.0, 100.0, 100.0, colours::RED));
+/// assert!(tiles.insert(4, tile).is_ok());
+/// assert!(tiles.insert(4, tile).is_err());
+/// assert!(tiles[4].is_some());
+/// assert_eq!(tiles.iter_occupied().map(|(i, _)| i).collect::<Vec<usize>>(), vec![4]);
+/// assert!(tiles.remove(4).is_some());
+/// assert!(tiles.get(4).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tiles(Vec<Option<Tile>>);
+
+impl Tiles {
+    /// Returns `Tiles` with `n` empty positions.
+    pub fn new(n: usize) -> Tiles {
+        Tiles(vec![None; n])
+    }
+
+    /// Returns the `Tile` at position `i`, or `None` if it is empty or not a position.
+    pub fn get(&self, i: usize) -> Option<&Tile> {
+        self.0.get(i).and_then(|t| t.as_ref())
+    }
+
+    /// Returns the `Tile` at position `i` to be changed, or `None` if it is empty or not a
+    /// position.
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut Tile> {
+        self.0.get_mut(i).and_then(|t| t.as_mut())
+    }
+
+    /// Places a `Tile` at the empty position `i`.
+    ///
+    /// Returns an `Occupied` error, leaving the position unchanged, if it already holds a
+    /// `Tile`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not a position.
+    pub fn insert(&mut self, i: usize, tile: Tile) -> Result<(), Occupied> {
+        match self.0[i] {
+            Some(_) => Err(Occupied(i)),
+            None => {
+                self.0[i] = Some(tile);
+                Ok(())
+            }
+        }
+    }
+
+    /// Removes and returns the `Tile` at position `i`, or `None` if it is empty or not a
+    /// position.
+    pub fn remove(&mut self, i: usize) -> Option<Tile> {
+        self.0.get_mut(i).and_then(|t| t.take())
+    }
+
+    /// Places a `Tile` at position `i`, returning the `Tile` it replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not a position.
+    #[deprecated(since = "0.2.0", note = "use `insert`, which does not replace tiles")]
+    pub fn set(&mut self, i: usize, tile: Tile) -> Option<Tile> {
+        mem::replace(&mut self.0[i], Some(tile))
+    }
+
+    /// Removes and returns the `Tile` at position `i`, or `None` if it is empty or not a
+    /// position.
+    #[deprecated(since = "0.2.0", note = "use `remove` instead")]
+    pub fn take(&mut self, i: usize) -> Option<Tile> {
+        self.remove(i)
+    }
+
+    /// Returns an iterator over the index and `Tile` of every occupied position.
+    pub fn iter_occupied(&self) -> impl Iterator<Item = (usize, &Tile)> {
+        self.0.iter().enumerate().filter_map(|(i, t)| t.as_ref().map(|t| (i, t)))
+    }
+
+    /// Returns an iterator over the index and `Tile` of every occupied position, allowing
+    /// the tiles to be changed.
+    pub fn iter_occupied_mut(&mut self) -> impl Iterator<Item = (usize, &mut Tile)> {
+        self.0.iter_mut().enumerate().filter_map(|(i, t)| t.as_mut().map(|t| (i, t)))
+    }
+
+    /// Returns an iterator over every position.
+    pub fn iter<'a>(&'a self) -> slice::Iter<'a, Option<Tile>> {
+        self.0.iter()
+    }
+
+    /// Returns the number of positions.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// True if there are no positions.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes every `Tile`.
+    pub fn clear(&mut self) {
+        for t in &mut self.0 {
+            *t = None;
+        }
+    }
+}
+
+impl Index<usize> for Tiles {
+    type Output = Option<Tile>;
+
+    fn index(&self, i: usize) -> &Option<Tile> {
+        &self.0[i]
+    }
+}
+
+/// The error returned by `Tiles::insert` when the position already holds a `Tile`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Occupied(pub usize);
+
+impl fmt::Display for Occupied {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "position {} already holds a tile", self.0)
+    }
+}
+
+impl Error for Occupied {
+    fn description(&self) -> &str {
+        "position already holds a tile"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use colours::{self, RED};
+
+    #[test]
+    fn add_tile() {
+        let mut board = Board::from_length(300.0);
+        board.add_tile();
+        let is_some_array: Vec<bool> = board.tiles.iter().map(|x| x.is_some()).collect();
+        assert!(is_some_array.contains(&true));
+    }
+
+    #[test]
+    fn tiles_accessors() {
+        let tile = Tile::new(Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED));
+        let mut tiles = Tiles::new(4);
+        assert_eq!(tiles.len(), 4);
+        assert!(tiles.get(0).is_none());
+        assert!(tiles.get(10).is_none());
+        assert_eq!(tiles.insert(0, tile), Ok(()));
+        assert_eq!(tiles.insert(2, tile), Ok(()));
+        assert_eq!(tiles.get(2), Some(&tile));
+        assert_eq!(tiles.iter_occupied().map(|(i, _)| i).collect::<Vec<usize>>(), vec![0, 2]);
+        assert_eq!(tiles.remove(2), Some(tile));
+        assert_eq!(tiles.remove(2), None);
+        assert_eq!(tiles.remove(10), None);
+        tiles.clear();
+        assert_eq!(tiles.iter_occupied().count(), 0);
+        assert_eq!(tiles.len(), 4);
+    }
+
+    #[test]
+    fn tiles_insert_occupied() {
+        let red = Tile::new(Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED));
+        let green = Tile::new(Sprite::new(0.0, 0.0, 100.0, 100.0, colours::GREEN));
+        let mut tiles = Tiles::new(3);
+        assert_eq!(tiles.insert(1, red), Ok(()));
+        assert_eq!(tiles.insert(1, green), Err(Occupied(1)));
+        assert_eq!(tiles.get(1), Some(&red));
+        assert_eq!(Occupied(1).to_string(), "position 1 already holds a tile");
+        tiles.remove(1);
+        assert_eq!(tiles.insert(1, green), Ok(()));
+        assert_eq!(tiles.get(1), Some(&green));
+    }
+
+    #[test]
+    #[should_panic]
+    fn tiles_insert_out_of_range() {
+        let tile = Tile::new(Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED));
+        let _ = Tiles::new(3).insert(3, tile);
+    }
+
+    #[test]
+    fn tiles_mutable_access() {
+        let tile = Tile::new(Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED));
+        let mut tiles = Tiles::new(4);
+        assert!(tiles.get_mut(0).is_none());
+        assert!(tiles.get_mut(10).is_none());
+        tiles.insert(1, tile).unwrap();
+        tiles.insert(3, tile).unwrap();
+        tiles.get_mut(1).unwrap().age = 2.0;
+        for (i, t) in tiles.iter_occupied_mut() {
+            t.age += i as f64;
+        }
+        assert_eq!(tiles.get(1).unwrap().age, 3.0);
+        assert_eq!(tiles.get(3).unwrap().age, 3.0);
+        assert_eq!(tiles.iter().filter(|t| t.is_some()).count(), 2);
+        assert!(tiles[0].is_none() && tiles[1].is_some());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn tiles_set_and_take() {
+        let tile = Tile::new(Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED));
+        let mut tiles = Tiles::new(2);
+        assert!(tiles.set(0, tile).is_none());
+        assert_eq!(tiles.set(0, tile), Some(tile));
+        assert_eq!(tiles.take(0), Some(tile));
+        assert_eq!(tiles.take(0), None);
+    }
+
+    #[test]
+    fn iter_tiles() {
+        let mut board = Board::from_length(300.0);
+        assert_eq!(board.iter_tiles().count(), 0);
+        for &i in &[8, 0, 4, 6] {
+            board.add_tile_at(i).unwrap();
+        }
+        board.remove_tile(4);
+        let tiles: Vec<(usize, Vec2D)> = board.iter_tiles().map(|(i, s)| (i, s.pos)).collect();
+        assert_eq!(tiles,
+                   vec![(0, Vec2D::new(0.0, 0.0)),
+                        (6, Vec2D::new(0.0, 200.0)),
+                        (8, Vec2D::new(200.0, 200.0))]);
+    }
+
+    #[test]
+    fn free_positions() {
+        let mut board = Board::from_length(300.0);
+        board.add_tile();
+        assert_eq!(board.free_positions().len(), 8);
+    }
+
+    #[test]
+    fn clear_board() {
+        let mut board = Board::from_length(300.0);
+        for _ in 0..8 {
+            board.add_tile();
+        }
+        assert!(!board.is_full());
+        board.add_tile();
+        assert!(board.is_full());
+        board.clear_board();
+        assert!(!board.is_full());
+    }
+
+    #[test]
+    fn is_overlapping() {
+        let window_size = 300.0;
+        let mut board = Board::from_length(window_size);
+        let mut cursor = Sprite::new(window_size / 2.0,
+                                     window_size / 2.0,
+                                     window_size / 16.0,
+                                     window_size / 16.0,
+                                     colours::YELLOW);
+        for _ in 0..9 {
+            board.add_tile();
+        }
+        let overlapping: Vec<bool> = board.tiles
+            .iter()
+            .map(|x| x.unwrap())
+            .map(|x| cursor.is_overlapping(&x.sprite))
+            .collect();
+        assert_eq!(overlapping,
+                   [false, false, false, false, true, false, false, false, false]);
+        cursor.pos.x -= 100.0;
+        let overlapping: Vec<bool> = board.tiles
+            .iter()
+            .map(|x| x.unwrap())
+            .map(|x| cursor.is_overlapping(&x.sprite))
+            .collect();
+        assert_eq!(overlapping,
+                   [false, false, false, true, false, false, false, false, false]);
+        cursor.pos.y -= 100.0;
+        let overlapping: Vec<bool> = board.tiles
+            .iter()
+            .map(|x| x.unwrap())
+            .map(|x| cursor.is_overlapping(&x.sprite))
+            .collect();
+        assert_eq!(overlapping,
+                   [true, false, false, false, false, false, false, false, false]);
+    }
+
+    #[test]
+    fn touching_sprites_do_not_overlap() {
+        let s1 = Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED);
+        let right = Sprite::new(100.0, 0.0, 100.0, 100.0, colours::RED);
+        let below = Sprite::new(0.0, 100.0, 100.0, 100.0, colours::RED);
+        let corner = Sprite::new(100.0, 100.0, 100.0, 100.0, colours::RED);
+        assert!(!s1.is_overlapping(&right));
+        assert!(!right.is_overlapping(&s1));
+        assert!(!s1.is_overlapping(&below));
+        assert!(!below.is_overlapping(&s1));
+        assert!(!s1.is_overlapping(&corner));
+        let nudged = Sprite::new(99.9, 99.9, 100.0, 100.0, colours::RED);
+        assert!(s1.is_overlapping(&nudged));
+    }
+
+    #[test]
+    fn overlap_boundaries() {
+        let tile = Sprite::new(100.0, 100.0, 100.0, 100.0, colours::RED);
+        let size = 300.0 / 16.0;
+        let left = Sprite::new(100.0 - size, 150.0, size, size, colours::YELLOW);
+        let above = Sprite::new(150.0, 100.0 - size, size, size, colours::YELLOW);
+        let right = Sprite::new(200.0, 150.0, size, size, colours::YELLOW);
+        let below = Sprite::new(150.0, 200.0, size, size, colours::YELLOW);
+        for cursor in &[left, above, right, below] {
+            assert!(!tile.is_overlapping(cursor));
+            assert!(!cursor.is_overlapping(&tile));
+            assert_eq!(tile.overlap_area(cursor), 0.0);
+        }
+        assert!(tile.is_overlapping(&left.translated(Vec2D::new(1e-9, 0.0))));
+        assert!(tile.is_overlapping(&above.translated(Vec2D::new(0.0, 1e-9))));
+        let beside = Sprite::circle(Vec2D::new(210.0, 150.0), 10.0, colours::YELLOW);
+        let on_top = Sprite::circle(Vec2D::new(150.0, 90.0), 10.0, colours::YELLOW);
+        assert!(!tile.is_overlapping(&beside));
+        assert!(!on_top.is_overlapping(&tile));
+        assert!(!beside.is_overlapping(&Sprite::circle(Vec2D::new(230.0, 150.0),
+                                                       10.0,
+                                                       colours::YELLOW)));
+    }
+
+    #[test]
+    fn contains_point() {
+        let sprite = Sprite::new(100.0, 50.0, 100.0, 20.0, colours::RED);
+        assert!(sprite.contains(Vec2D::new(150.0, 60.0)));
+        assert!(sprite.contains(Vec2D::new(100.0, 50.0)));
+        assert!(sprite.contains(Vec2D::new(200.0, 70.0)));
+        assert!(sprite.contains(Vec2D::new(100.0, 70.0)));
+        assert!(sprite.contains(Vec2D::new(150.0, 50.0)));
+        assert!(!sprite.contains(Vec2D::new(99.9, 60.0)));
+        assert!(!sprite.contains(Vec2D::new(200.1, 60.0)));
+        assert!(!sprite.contains(Vec2D::new(150.0, 49.9)));
+        assert!(!sprite.contains(Vec2D::new(150.0, 70.1)));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn move_cursor() {
+        let window_size = 300.0;
+        let mut cursor = Sprite::new(window_size / 2.0,
+                                     window_size / 2.0,
+                                     window_size / 16.0,
+                                     window_size / 16.0,
+                                     colours::YELLOW);
+        cursor.pos.add(Vec2D {
+            x: -100.0,
+            y: 0.0,
+        });
+        assert_eq!(cursor.pos.x, 50.0);
+        assert_eq!(cursor.pos.y, 150.0);
+        cursor.pos.add(Vec2D {
+            x: 100.0,
+            y: 100.0,
+        });
+        assert_eq!(cursor.pos.x, 150.0);
+        assert_eq!(cursor.pos.y, 250.0);
+    }
+
+    #[test]
+    fn vec2d_arithmetic() {
+        let a = Vec2D::new(3.0, -4.0);
+        let b = Vec2D::new(0.5, 2.0);
+        assert_eq!(a + b, Vec2D::new(3.5, -2.0));
+        assert_eq!(a - b, Vec2D::new(2.5, -6.0));
+        assert_eq!(-a, Vec2D::new(-3.0, 4.0));
+        assert_eq!(a * 2.0, Vec2D::new(6.0, -8.0));
+        assert_eq!(a * -0.5, Vec2D::new(-1.5, 2.0));
+        assert_eq!(a / 2.0, Vec2D::new(1.5, -2.0));
+        assert_eq!(a / -0.25, Vec2D::new(-12.0, 16.0));
+        assert_eq!(a + b - b, a);
+    }
+
+    #[test]
+    fn vec2d_assign_arithmetic() {
+        let mut v = Vec2D::new(1.0, 2.0);
+        v += Vec2D::new(1.0, 1.0);
+        assert_eq!(v, Vec2D::new(2.0, 3.0));
+        v -= Vec2D::new(4.0, 0.5);
+        assert_eq!(v, Vec2D::new(-2.0, 2.5));
+        v *= -1.5;
+        assert_eq!(v, Vec2D::new(3.0, -3.75));
+        v /= 0.5;
+        assert_eq!(v, Vec2D::new(6.0, -7.5));
+    }
+
+    #[test]
+    fn vec2d_operators_copy() {
+        let a = Vec2D::new(1.0, 1.0);
+        let b = a + a;
+        let c = a * 3.0 - b;
+        assert_eq!(a, Vec2D::new(1.0, 1.0));
+        assert_eq!(b, Vec2D::new(2.0, 2.0));
+        assert_eq!(c, a);
+        let mut d = a;
+        d += b;
+        assert_eq!(a, Vec2D::new(1.0, 1.0));
+        assert_eq!(d, Vec2D::new(3.0, 3.0));
+    }
+
+    #[test]
+    fn vec2d_length() {
+        let v = Vec2D::new(-3.0, 4.0);
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.length_squared(), 25.0);
+        assert_eq!(Vec2D::empty().length(), 0.0);
+    }
+
+    #[test]
+    fn vec2d_distance() {
+        let a = Vec2D::new(2.0, -1.0);
+        let b = Vec2D::new(-4.0, 7.0);
+        assert_eq!(a.distance(b), 10.0);
+        assert_eq!(b.distance(a), 10.0);
+        assert_eq!(a.distance(a), 0.0);
+    }
+
+    #[test]
+    fn vec2d_normalized() {
+        let n = Vec2D::new(0.0, -8.0).normalized();
+        assert_eq!(n, Vec2D::new(0.0, -1.0));
+        let n = Vec2D::new(3.0, 4.0).normalized();
+        assert!((n.length() - 1.0).abs() < 1e-12);
+        assert_eq!(n, Vec2D::new(0.6, 0.8));
+    }
+
+    #[test]
+    fn vec2d_normalized_zero() {
+        let n = Vec2D::empty().normalized();
+        assert!(!n.x.is_nan() && !n.y.is_nan());
+        assert_eq!(n, Vec2D::empty());
+    }
+
+    #[test]
+    fn vec2d_dot() {
+        assert_eq!(Vec2D::new(1.0, 2.0).dot(Vec2D::new(3.0, -4.0)), -5.0);
+        assert_eq!(Vec2D::new(1.0, 0.0).dot(Vec2D::new(0.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    fn vec2d_lerp() {
+        let a = Vec2D::new(-2.0, 4.0);
+        let b = Vec2D::new(6.0, 0.0);
+        assert_eq!(Vec2D::lerp(a, b, 0.0), a);
+        assert_eq!(Vec2D::lerp(a, b, 1.0), b);
+        assert_eq!(Vec2D::lerp(a, b, 0.5), Vec2D::new(2.0, 2.0));
+        assert_eq!(Vec2D::lerp(a, b, -1.0), a);
+        assert_eq!(Vec2D::lerp(a, b, 3.0), b);
+    }
+
+    #[test]
+    fn sprite_from_center() {
+        let center = Vec2D::new(12.5, -40.0);
+        let sprite = Sprite::from_center(center, 5.0, 30.0, colours::RED);
+        assert_eq!(sprite.center(), center);
+        assert_eq!(sprite.pos, Vec2D::new(10.0, -55.0));
+        assert_eq!(sprite.get_rect(), [10.0, -55.0, 5.0, 30.0]);
+    }
+
+    #[test]
+    fn sprite_set_center() {
+        let mut sprite = Sprite::new(0.0, 0.0, 10.0, 20.0, colours::RED);
+        assert_eq!(sprite.center(), Vec2D::new(5.0, 10.0));
+        sprite.set_center(Vec2D::new(100.0, 100.0));
+        assert_eq!(sprite.center(), Vec2D::new(100.0, 100.0));
+        assert_eq!(sprite.get_rect(), [95.0, 90.0, 10.0, 20.0]);
+    }
+
+    #[test]
+    fn circle_overlaps_rect() {
+        let rect = Sprite::new(0.0, 0.0, 10.0, 10.0, colours::RED);
+        let inside = Sprite::circle(Vec2D::new(5.0, 5.0), 1.0, colours::YELLOW);
+        let edge = Sprite::circle(Vec2D::new(12.0, 5.0), 3.0, colours::YELLOW);
+        let touching = Sprite::circle(Vec2D::new(13.0, 5.0), 3.0, colours::YELLOW);
+        assert!(inside.is_overlapping(&rect));
+        assert!(rect.is_overlapping(&inside));
+        assert!(edge.is_overlapping(&rect));
+        assert!(!touching.is_overlapping(&rect));
+        assert!(!rect.is_overlapping(&touching));
+    }
+
+    #[test]
+    fn circle_near_rect_corner() {
+        let rect = Sprite::new(0.0, 0.0, 10.0, 10.0, colours::RED);
+        let near_miss = Sprite::circle(Vec2D::new(17.0, 17.0), 9.0, colours::YELLOW);
+        assert!(rect.rect_overlaps_rect(&near_miss));
+        assert!(!near_miss.is_overlapping(&rect));
+        assert!(!rect.is_overlapping(&near_miss));
+        let hit = Sprite::circle(Vec2D::new(17.0, 17.0), 10.0, colours::YELLOW);
+        assert!(hit.is_overlapping(&rect));
+    }
+
+    #[test]
+    fn circle_overlaps_circle() {
+        let a = Sprite::circle(Vec2D::new(0.0, 0.0), 5.0, colours::YELLOW);
+        let b = Sprite::circle(Vec2D::new(6.0, 8.0), 5.5, colours::YELLOW);
+        let c = Sprite::circle(Vec2D::new(6.0, 8.0), 5.0, colours::YELLOW);
+        let d = Sprite::circle(Vec2D::new(7.0, 7.0), 4.0, colours::YELLOW);
+        assert!(a.is_overlapping(&b));
+        assert!(b.is_overlapping(&a));
+        assert!(!a.is_overlapping(&c));
+        assert!(!a.is_overlapping(&d));
+    }
+
+    #[test]
+    fn overlap_area_none() {
+        let a = Sprite::new(0.0, 0.0, 10.0, 10.0, colours::RED);
+        let b = Sprite::new(20.0, 0.0, 10.0, 10.0, colours::RED);
+        let touching = Sprite::new(10.0, 0.0, 10.0, 10.0, colours::RED);
+        assert_eq!(a.overlap_area(&b), 0.0);
+        assert_eq!(a.overlap_fraction(&b), 0.0);
+        assert_eq!(a.overlap_area(&touching), 0.0);
+        assert_eq!(a.overlap_fraction(&touching), 0.0);
+    }
+
+    #[test]
+    fn overlap_area_partial() {
+        let a = Sprite::new(0.0, 0.0, 10.0, 10.0, colours::RED);
+        let b = Sprite::new(5.0, -5.0, 20.0, 10.0, colours::RED);
+        assert_eq!(a.overlap_area(&b), 25.0);
+        assert_eq!(b.overlap_area(&a), 25.0);
+        assert_eq!(a.overlap_fraction(&b), 0.25);
+        assert_eq!(b.overlap_fraction(&a), 0.25);
+    }
+
+    #[test]
+    fn overlap_area_contained() {
+        let outer = Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED);
+        let inner = Sprite::new(30.0, 40.0, 10.0, 5.0, colours::RED);
+        assert_eq!(outer.overlap_area(&inner), 50.0);
+        assert_eq!(outer.overlap_fraction(&inner), 1.0);
+        assert_eq!(inner.overlap_fraction(&outer), 1.0);
+    }
+
+    #[test]
+    fn overlap_area_zero_size() {
+        let a = Sprite::new(0.0, 0.0, 10.0, 10.0, colours::RED);
+        let line = Sprite::new(5.0, 0.0, 0.0, 10.0, colours::RED);
+        let point = Sprite::new(5.0, 5.0, 0.0, 0.0, colours::RED);
+        assert_eq!(a.overlap_area(&line), 0.0);
+        assert_eq!(a.overlap_fraction(&line), 0.0);
+        assert_eq!(point.overlap_fraction(&a), 0.0);
+        assert_eq!(point.overlap_fraction(&point), 0.0);
+    }
+
+    fn full_board() -> Board {
+        let mut board = Board::from_length(300.0);
+        while !board.is_full() {
+            board.add_tile();
+        }
+        board
+    }
+
+    #[test]
+    fn tiles_contain_only_their_own_centre() {
+        let board = full_board();
+        for (i, tile) in board.iter_tiles() {
+            for (j, other) in board.iter_tiles() {
+                assert_eq!(tile.contains_point(other.center()), i == j);
+            }
+        }
+    }
+
+    #[test]
+    fn tile_corners_resolve_to_one_tile() {
+        let board = full_board();
+        for row in 0..4 {
+            for column in 0..4 {
+                let corner = Vec2D::new(column as f64 * 100.0, row as f64 * 100.0);
+                let owners: Vec<usize> = board.iter_tiles()
+                    .filter(|&(_, t)| t.contains_point(corner))
+                    .map(|(i, _)| i)
+                    .collect();
+                if (row == 3) || (column == 3) {
+                    assert!(owners.is_empty());
+                } else {
+                    assert_eq!(owners, vec![row * 3 + column]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn contains_point_matches_index_from_pos() {
+        let board = full_board();
+        for row in 0..31 {
+            for column in 0..31 {
+                let p = Vec2D::new(column as f64 * 10.0, row as f64 * 10.0);
+                let owner = board.iter_tiles()
+                    .find(|&(_, t)| t.contains_point(p))
+                    .map(|(i, _)| i);
+                assert_eq!(owner, board.index_from_pos(p));
+            }
+        }
+    }
+
+    #[test]
+    fn translations_compose() {
+        let sprite = Sprite::new(3.0, 4.0, 10.0, 20.0, colours::RED);
+        let a = Vec2D::new(1.5, -2.0);
+        let b = Vec2D::new(-4.0, 8.0);
+        assert_eq!(sprite.translated(a).translated(b), sprite.translated(a + b));
+        assert_eq!(sprite.translated(a).translated(-a), sprite);
+        assert_eq!(sprite.translated(a).width, 10.0);
+        assert_eq!(sprite.pos, Vec2D::new(3.0, 4.0));
+    }
+
+    #[test]
+    fn scaling_keeps_centre() {
+        let sprite = Sprite::new(3.0, 4.0, 10.0, 20.0, colours::RED);
+        for &factor in &[0.0, 0.25, 1.0, 2.0, 3.5] {
+            let scaled = sprite.scaled_about_center(factor);
+            assert_eq!(scaled.center(), sprite.center());
+            assert_eq!((scaled.width, scaled.height), (10.0 * factor, 20.0 * factor));
+        }
+        assert_eq!(sprite.get_rect(), [3.0, 4.0, 10.0, 20.0]);
+        let circle = Sprite::circle(Vec2D::new(5.0, 5.0), 2.0, colours::RED);
+        let scaled = circle.scaled_about_center(1.5);
+        assert_eq!(scaled.shape, Shape::Circle { radius: 3.0 });
+        assert_eq!(scaled.center(), Vec2D::new(5.0, 5.0));
+    }
+
+    #[test]
+    fn vec2d_clamped() {
+        let min = Vec2D::new(-1.0, 2.0);
+        let max = Vec2D::new(1.0, 4.0);
+        assert_eq!(Vec2D::new(0.5, 3.0).clamped(min, max), Vec2D::new(0.5, 3.0));
+        assert_eq!(Vec2D::new(-7.0, 9.0).clamped(min, max), Vec2D::new(-1.0, 4.0));
+        assert_eq!(Vec2D::new(7.0, -9.0).clamped(min, max), Vec2D::new(1.0, 2.0));
+        assert_eq!(Vec2D::new(0.0, 0.0).clamped(max, min), max);
+    }
+
+    #[test]
+    fn vec2d_clamp_each_axis() {
+        let min = Vec2D::new(0.0, 10.0);
+        let max = Vec2D::new(5.0, 20.0);
+        let cases = [((-1.0, 15.0), (0.0, 15.0)),
+                     ((6.0, 15.0), (5.0, 15.0)),
+                     ((2.0, 9.0), (2.0, 10.0)),
+                     ((2.0, 21.0), (2.0, 20.0)),
+                     ((2.0, 15.0), (2.0, 15.0)),
+                     ((0.0, 20.0), (0.0, 20.0))];
+        for &((x, y), (ex, ey)) in &cases {
+            let mut v = Vec2D::new(x, y);
+            v.clamp(min, max);
+            assert_eq!(v, Vec2D::new(ex, ey));
+        }
+    }
+
+    #[test]
+    fn vec2d_min_max() {
+        let a = Vec2D::new(-3.0, 4.0);
+        let b = Vec2D::new(2.0, -1.0);
+        assert_eq!(a.min(b), Vec2D::new(-3.0, -1.0));
+        assert_eq!(a.max(b), Vec2D::new(2.0, 4.0));
+        assert_eq!(a.min(b), b.min(a));
+        assert_eq!(a.max(a), a);
+    }
+
+    #[test]
+    fn clamp_sprite_inside_rect() {
+        let rect = [10.0, 20.0, 100.0, 50.0];
+        let mut sprite = Sprite::new(30.0, 30.0, 10.0, 10.0, colours::YELLOW);
+        sprite.clamp_to_rect(rect);
+        assert_eq!(sprite.pos, Vec2D::new(30.0, 30.0));
+    }
+
+    #[test]
+    fn clamp_sprite_past_each_edge() {
+        let rect = [10.0, 20.0, 100.0, 50.0];
+        let cases = [(Vec2D::new(0.0, 30.0), Vec2D::new(10.0, 30.0)),
+                     (Vec2D::new(105.0, 30.0), Vec2D::new(100.0, 30.0)),
+                     (Vec2D::new(50.0, 5.0), Vec2D::new(50.0, 20.0)),
+                     (Vec2D::new(50.0, 65.0), Vec2D::new(50.0, 60.0))];
+        for &(start, end) in &cases {
+            let mut sprite = Sprite::new(start.x, start.y, 10.0, 10.0, colours::YELLOW);
+            sprite.clamp_to_rect(rect);
+            assert_eq!(sprite.pos, end);
+        }
+    }
+
+    #[test]
+    fn clamp_sprite_exact_fit() {
+        let mut sprite = Sprite::new(-5.0, 7.0, 100.0, 50.0, colours::YELLOW);
+        sprite.clamp_to_rect([10.0, 20.0, 100.0, 50.0]);
+        assert_eq!(sprite.pos, Vec2D::new(10.0, 20.0));
+    }
+
+    #[test]
+    fn clamp_sprite_larger_than_rect() {
+        let mut sprite = Sprite::new(50.0, 50.0, 200.0, 80.0, colours::YELLOW);
+        sprite.clamp_to_rect([10.0, 20.0, 100.0, 50.0]);
+        assert_eq!(sprite.pos, Vec2D::new(10.0, 20.0));
+        let mut sprite = Sprite::new(-50.0, -50.0, 200.0, 80.0, colours::YELLOW);
+        sprite.clamp_to_rect([10.0, 20.0, 100.0, 50.0]);
+        assert_eq!(sprite.pos, Vec2D::new(10.0, 20.0));
+    }
+
+    #[test]
+    fn gen_random_index() {
+        let mut board = Board::from_length(300.0);
+        for _ in 1..10 {
+            if let Some(i) = board.random_position() {
+                assert!(i <= 8);
+            }
+        }
+    }
+
+    #[test]
+    fn next_spawn_matches_add_tile() {
+        let mut board = Board::seeded(300.0, 7);
+        for _ in 0..9 {
+            let preview = board.next_spawn().unwrap();
+            assert!(board.tiles[preview].is_none());
+            board.add_tile();
+            assert!(board.tiles[preview].is_some());
+        }
+        assert!(board.is_full());
+        assert_eq!(board.next_spawn(), None);
+    }
+
+    #[test]
+    fn next_spawn_rerolls_occupied() {
+        let mut board = Board::seeded(300.0, 7);
+        for _ in 0..4 {
+            let preview = board.next_spawn().unwrap();
+            let blocker = Sprite::new(0.0, 0.0, 100.0, 100.0, colours::RED);
+            board.tiles.insert(preview, Tile::new(blocker)).unwrap();
+            let rerolled = board.next_spawn().unwrap();
+            assert!(rerolled != preview);
+            assert!(board.tiles[rerolled].is_none());
+            let free_before = board.free_positions().len();
+            board.add_tile();
+            assert!(board.tiles[rerolled].is_some());
+            assert_eq!(board.free_positions().len(), free_before - 1);
+        }
+    }
+
+    #[test]
+    fn seeded_boards_match() {
+        let mut board1 = Board::seeded(300.0, 1234);
+        let mut board2 = Board::seeded(300.0, 1234);
+        for _ in 0..9 {
+            assert_eq!(board1.next_spawn(), board2.next_spawn());
+            board1.add_tile();
+            board2.add_tile();
+        }
+    }
+
+    #[test]
+    fn spawn_pattern_partially_occupied() {
+        let mut board = Board::from_length(300.0);
+        let tile = |x, y| Tile::new(Sprite::new(x, y, 100.0, 100.0, colours::RED));
+        board.tiles.insert(1, tile(100.0, 0.0)).unwrap();
+        board.tiles.insert(4, tile(100.0, 100.0)).unwrap();
+        assert_eq!(board.spawn_pattern(Pattern::Row(0)), vec![0, 2]);
+        assert_eq!(board.spawn_pattern(Pattern::Column(1)), vec![7]);
+        assert_eq!(board.spawn_pattern(Pattern::AntiDiagonal), vec![6]);
+        assert_eq!(board.spawn_pattern(Pattern::Diagonal), vec![8]);
+        assert_eq!(board.free_positions(), vec![3, 5]);
+        assert_eq!(board.tiles[7].unwrap().sprite.pos, Vec2D::new(100.0, 200.0));
+        assert_eq!(board.tiles[7].unwrap().kind, TileKind::Pattern);
+        assert_eq!(board.tiles[4].unwrap().kind, TileKind::Normal);
+    }
+
+    #[test]
+    fn oldest_tile() {
+        let mut board = Board::seeded(300.0, 3);
+        let first = board.next_spawn().unwrap();
+        board.add_tile();
+        board.tick(2.0);
+        let second = board.next_spawn().unwrap();
+        board.add_tile();
+        board.tick(1.0);
+        let third = board.next_spawn().unwrap();
+        board.add_tile();
+        board.tick(0.5);
+        assert_eq!(board.oldest_tile().map(|t| (t.0, t.1.age)), Some((first, 3.5)));
+        board.tiles.remove(first);
+        assert_eq!(board.oldest_tile().map(|t| (t.0, t.1.age)), Some((second, 1.5)));
+        board.tiles.remove(second);
+        assert_eq!(board.oldest_tile().map(|t| (t.0, t.1.age)), Some((third, 0.5)));
+        assert_eq!(board.ages(), vec![(third, 0.5)]);
+        board.tiles.remove(third);
+        assert!(board.oldest_tile().is_none());
+        assert!(board.ages().is_empty());
+    }
+
+    #[test]
+    fn oldest_tile_ties() {
+        let mut board = Board::from_length(300.0);
+        board.spawn_pattern(Pattern::AntiDiagonal);
+        board.tick(1.0);
+        assert_eq!(board.oldest_tile().unwrap().0, 2);
+        assert_eq!(board.ages(), vec![(2, 1.0), (4, 1.0), (6, 1.0)]);
+    }
+
+    #[test]
+    fn blocked_cells() {
+        let mut board = Board::from_length(300.0);
+        board.block_cell(0);
+        board.block_cell(4);
+        board.block_cell(8);
+        assert_eq!(board.free_positions().len(), 6);
+        for _ in 0..5 {
+            board.add_tile();
+        }
+        assert!(!board.is_full());
+        board.add_tile();
+        assert!(board.is_full());
+        assert!(board.tiles[0].is_none() && board.tiles[4].is_none() && board.tiles[8].is_none());
+        board.clear_board();
+        assert!(board.is_blocked(4));
+        assert!(!board.is_blocked(3));
+        assert!(!board.is_blocked(20));
+        assert_eq!(board.add_tile_at(4), Err(BoardError::Blocked(4)));
+        assert_eq!(board.spawn_pattern(Pattern::Diagonal), vec![]);
+    }
+
+    #[test]
+    fn walls() {
+        let mut board = Board::from_length(300.0);
+        board.add_wall(3);
+        assert!(board.is_wall(3));
+        assert!(board.is_blocked(3));
+        assert!(!board.is_wall(4));
+        assert!(!board.is_wall(30));
+        assert_eq!(board.add_tile_at(3), Err(BoardError::Blocked(3)));
+        let walls = board.wall_sprites();
+        assert_eq!(walls.len(), 1);
+        assert_eq!(walls[0].pos, Vec2D::new(0.0, 100.0));
+    }
+
+    #[test]
+    fn block_occupied_cell() {
+        let mut board = Board::from_length(300.0);
+        board.add_tile_at(2).unwrap();
+        board.block_cell(2);
+        assert!(board.tiles[2].is_none());
+        assert_eq!(board.free_positions().len(), 8);
+    }
+
+    #[test]
+    fn invalid_board_lengths() {
+        use std::f64;
+        assert_eq!(Board::try_from_length(0.0), Err(BoardError::InvalidLength(0.0)));
+        assert_eq!(Board::try_from_length(-300.0),
+                   Err(BoardError::InvalidLength(-300.0)));
+        assert_eq!(Board::try_from_length(f64::INFINITY),
+                   Err(BoardError::InvalidLength(f64::INFINITY)));
+        assert_eq!(Board::try_from_length(f64::NEG_INFINITY),
+                   Err(BoardError::InvalidLength(f64::NEG_INFINITY)));
+        match Board::try_from_length(f64::NAN) {
+            Err(BoardError::InvalidLength(l)) => assert!(l.is_nan()),
+            _ => panic!("NaN length was accepted"),
+        }
+        assert!(Board::try_from_length(0.001).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid board: board length must be positive and finite, got 0")]
+    fn from_length_panics_on_zero() {
+        Board::from_length(0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid board: board length must be positive and finite, got -5")]
+    fn seeded_panics_on_negative() {
+        Board::seeded(-5.0, 1);
+    }
+
+    #[test]
+    fn board_error_messages() {
+        assert_eq!(BoardError::InvalidLength(-1.5).to_string(),
+                   "board length must be positive and finite, got -1.5");
+        assert_eq!(BoardError::OutOfRange(9).to_string(), "position 9 is not on the board");
+        assert_eq!(BoardError::Occupied(1).to_string(), "position 1 is already occupied");
+        assert_eq!(BoardError::Blocked(4).to_string(), "position 4 is blocked");
+    }
+
+    #[test]
+    fn shuffle_tiles() {
+        let mut board = Board::seeded(300.0, 11);
+        board.block_cell(0);
+        for _ in 0..4 {
+            board.add_tile();
+        }
+        board.tick(2.0);
+        for _ in 0..20 {
+            let before = board.free_positions();
+            board.shuffle();
+            let after = board.free_positions();
+            assert_eq!(after.len(), 4);
+            assert!(after != before);
+            assert!(board.tiles[0].is_none());
+            for (i, tile) in board.tiles.iter().enumerate() {
+                if let Some(t) = *tile {
+                    assert_eq!(t.sprite.pos, Vec2D::new(board.x_from_index(i), board.y_from_index(i)));
+                    assert_eq!(t.age, 2.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shuffle_single_arrangement() {
+        let mut board = Board::seeded(300.0, 11);
+        board.shuffle();
+        assert_eq!(board.free_positions().len(), 9);
+        for _ in 0..9 {
+            board.add_tile();
+        }
+        board.shuffle();
+        assert!(board.is_full());
+    }
+
+    fn context(cursor_cell: Option<usize>) -> PlacementContext {
+        PlacementContext {
+            cursor_cell: cursor_cell,
+            rows: 3,
+            cols: 3,
+        }
+    }
+
+    #[test]
+    fn uniform_placement() {
+        let mut rng = StdRng::from_seed(&[5]);
+        let free = [1, 4, 7];
+        let mut counts = [0; 9];
+        for _ in 0..3000 {
+            counts[Uniform.choose(&free, &context(None), &mut rng)] += 1;
+        }
+        assert_eq!(counts[1] + counts[4] + counts[7], 3000);
+        assert!(free.iter().all(|&i| counts[i] > 800));
+    }
+
+    #[test]
+    fn avoid_recent_placement() {
+        let mut rng = StdRng::from_seed(&[5]);
+        let mut strategy = AvoidRecent::new(2);
+        let free = [0, 3, 6];
+        let mut chosen = Vec::new();
+        for _ in 0..30 {
+            chosen.push(strategy.choose(&free, &context(None), &mut rng));
+        }
+        for window in chosen.windows(3) {
+            assert!(window[0] != window[1] && window[1] != window[2] && window[0] != window[2]);
+        }
+        assert_eq!(strategy.choose(&[8], &context(None), &mut rng), 8);
+        assert_eq!(strategy.choose(&[8], &context(None), &mut rng), 8);
+    }
+
+    #[test]
+    fn distance_weighted_placement() {
+        let mut rng = StdRng::from_seed(&[5]);
+        let mut strategy = WeightedByDistanceFromCursor;
+        let mut near = 0;
+        let mut far = 0;
+        for _ in 0..2000 {
+            match strategy.choose(&[0, 1, 8], &context(Some(0)), &mut rng) {
+                0 => panic!("chose the cursor cell"),
+                1 => near += 1,
+                _ => far += 1,
+            }
+        }
+        assert!(far > 2 * near);
+        assert!(near > 0);
+        assert_eq!(strategy.choose(&[0], &context(Some(0)), &mut rng), 0);
+        let i = strategy.choose(&[2, 6], &context(None), &mut rng);
+        assert!(i == 2 || i == 6);
+    }
+
+    #[test]
+    fn spawn_bias_placement() {
+        let mut rng = StdRng::from_seed(&[5]);
+        let free: Vec<usize> = (0..9).collect();
+        let mut counts = [0; 9];
+        for _ in 0..3000 {
+            counts[SpawnBias::Corners.choose(&free, &context(None), &mut rng)] += 1;
+        }
+        assert!([0, 2, 6, 8].iter().all(|&i| counts[i] > 3 * counts[4]));
+        let mut counts = [0; 9];
+        for _ in 0..3000 {
+            counts[SpawnBias::Center.choose(&free, &context(None), &mut rng)] += 1;
+        }
+        assert!([0, 2, 6, 8].iter().all(|&i| 3 * counts[i] < counts[4]));
+        let mut counts = [0; 9];
+        for _ in 0..3000 {
+            counts[SpawnBias::Edges.choose(&free, &context(None), &mut rng)] += 1;
+        }
+        assert!([1, 3, 5, 7].iter().all(|&i| counts[i] > 2 * counts[0]));
+        assert!([1, 3, 5, 7].iter().all(|&i| counts[i] > 2 * counts[4]));
+    }
+
+    #[test]
+    fn uniform_spawn_bias_matches_uniform() {
+        let free = [1, 4, 7];
+        let mut a = StdRng::from_seed(&[3]);
+        let mut b = StdRng::from_seed(&[3]);
+        for _ in 0..50 {
+            assert_eq!(SpawnBias::Uniform.choose(&free, &context(None), &mut a),
+                       Uniform.choose(&free, &context(None), &mut b));
+        }
+    }
+
+    #[test]
+    fn board_uses_placement() {
+        let mut board = Board::seeded(300.0, 9);
+        board.set_placement(Box::new(WeightedByDistanceFromCursor));
+        board.set_cursor_cell(Some(4));
+        for _ in 0..8 {
+            board.add_tile();
+        }
+        assert_eq!(board.free_positions(), vec![4]);
+    }
+
+    #[test]
+    fn fading_tiles() {
+        let mut board = Board::from_length(300.0);
+        board.add_tile_at(0).unwrap();
+        board.add_tile_at(1).unwrap();
+        assert!(board.remove_tile(0).is_some());
+        assert!(board.remove_tile(0).is_none());
+        assert_eq!(board.fading_tiles().len(), 1);
+        assert_eq!(board.fading_tiles()[0].colour[3], 1.0);
+        assert_eq!(board.fading_tiles()[0].pos, Vec2D::new(0.0, 0.0));
+        board.tick(FADE_DURATION / 2.0);
+        assert_eq!(board.fading_tiles()[0].colour[3], 0.5);
+        board.remove_tile(1);
+        board.tick(FADE_DURATION / 4.0);
+        let alphas: Vec<f32> = board.fading_tiles().iter().map(|s| s.colour[3]).collect();
+        assert_eq!(alphas, vec![0.25, 0.75]);
+        board.tick(FADE_DURATION / 4.0);
+        assert_eq!(board.fading_tiles().len(), 1);
+        board.tick(FADE_DURATION);
+        assert!(board.fading_tiles().is_empty());
+    }
+
+    #[test]
+    fn fading_tiles_bounded() {
+        let mut board = Board::from_length(300.0);
+        for _ in 0..MAX_FADING_TILES + 5 {
+            board.add_tile_at(3).unwrap();
+            board.remove_tile(3);
+        }
+        assert_eq!(board.fading_tiles().len(), MAX_FADING_TILES);
+        board.clear_board();
+        assert!(board.fading_tiles().is_empty());
+    }
+
+    #[test]
+    fn spawn_histogram() {
+        let mut board = Board::from_length(300.0);
+        board.add_tile_at(2).unwrap();
+        board.spawn_pattern(Pattern::Row(0));
+        board.clear_board();
+        board.add_tile_at(2).unwrap();
+        assert_eq!(board.spawn_histogram(), &[1, 1, 2, 0, 0, 0, 0, 0, 0]);
+        board.reset_stats();
+        assert_eq!(board.spawn_histogram(), &[0; 9]);
+        assert_eq!(board.chi_squared_uniformity(), 0.0);
+    }
+
+    #[test]
+    fn spawn_distribution_is_uniform() {
+        let mut board = Board::seeded(300.0, 2017);
+        for _ in 0..10000 {
+            board.add_tile();
+            board.clear_board();
+        }
+        assert_eq!(board.spawn_histogram().iter().sum::<u32>(), 10000);
+        // Critical value for 8 degrees of freedom at p = 0.001.
+        assert!(board.chi_squared_uniformity() < 26.12);
+        board.reset_stats();
+        for _ in 0..100 {
+            board.add_tile_at(0).unwrap();
+            board.clear_board();
+        }
+        assert!(board.chi_squared_uniformity() > 26.12);
+    }
+
+    #[test]
+    fn check_index_from_pos() {
+        let board = Board::from_length(300.0);
+        assert_eq!(board.index_from_pos(Vec2D::new(0.0, 0.0)), Some(0));
+        assert_eq!(board.index_from_pos(Vec2D::new(99.9, 99.9)), Some(0));
+        assert_eq!(board.index_from_pos(Vec2D::new(250.0, 50.0)), Some(2));
+        assert_eq!(board.index_from_pos(Vec2D::new(299.9, 299.9)), Some(8));
+        assert_eq!(board.index_from_pos(Vec2D::new(-0.1, 50.0)), None);
+        assert_eq!(board.index_from_pos(Vec2D::new(50.0, 300.0)), None);
+    }
+
+    #[test]
+    fn board_origin() {
+        let mut board = Board::from_length(300.0);
+        board.origin = Vec2D::new(300.0, 50.0);
+        assert_eq!(board.x_from_index(4), 400.0);
+        assert_eq!(board.y_from_index(4), 150.0);
+        assert_eq!(board.index_from_pos(Vec2D::new(450.0, 150.0)), Some(4));
+        assert_eq!(board.index_from_pos(Vec2D::new(150.0, 150.0)), None);
+        board.add_tile_at(8).unwrap();
+        assert_eq!(board.tiles[8].unwrap().sprite.pos, Vec2D::new(500.0, 250.0));
+    }
+
+    #[test]
+    fn check_x_from_i() {
+        let board = Board::from_length(300.0);
+        assert_eq!(board.x_from_index(0), 0.0);
+        assert_eq!(board.x_from_index(1), 100.0);
+        assert_eq!(board.x_from_index(2), 200.0);
+        assert_eq!(board.x_from_index(8), 200.0);
+    }
+
+    #[test]
+    fn check_y_from_i() {
+        let board = Board::from_length(300.0);
+        assert_eq!(board.y_from_index(0), 0.0);
+        assert_eq!(board.y_from_index(1), 0.0);
+        assert_eq!(board.y_from_index(2), 0.0);
+        assert_eq!(board.y_from_index(8), 200.0);
+    }
+
+    #[test]
+    fn hex_cells() {
+        let board = Board::hex(2, 300.0);
+        assert_eq!(board.layout(), BoardLayout::Hex { radius: 2 });
+        assert_eq!(board.tiles.len(), 19);
+        assert_eq!(board.hex_coord(0), Some((0, -2)));
+        assert_eq!(board.hex_coord(9), Some((0, 0)));
+        assert_eq!(board.hex_coord(18), Some((0, 2)));
+        assert_eq!(board.hex_coord(19), None);
+        assert_eq!(Board::hex(0, 300.0).tiles.len(), 1);
+        assert_eq!(Board::from_length(300.0).hex_coord(0), None);
+    }
+
+    #[test]
+    fn hex_index_coordinate_round_trip() {
+        let mut board = Board::hex(2, 300.0);
+        board.origin = Vec2D::new(50.0, 20.0);
+        for i in 0..board.tiles.len() {
+            let (q, r) = board.hex_coord(i).unwrap();
+            assert_eq!(board.index_from_hex(q, r), Some(i));
+            assert_eq!(board.index_from_pos(board.cell_center(i)), Some(i));
+            let rect = board.cell_rect(i);
+            let corner = Vec2D::new(rect[0] + 0.01, rect[1] + 0.01);
+            assert_eq!(board.index_from_pos(corner), Some(i));
+        }
+        assert_eq!(board.index_from_hex(2, 2), None);
+        assert_eq!(board.index_from_pos(Vec2D::new(50.0, 20.0)), None);
+        assert_eq!(board.cell_center(9), Vec2D::new(200.0, 170.0));
+    }
+
+    #[test]
+    fn hex_cells_fit_on_board() {
+        let board = Board::hex(3, 300.0);
+        for i in 0..board.tiles.len() {
+            let rect = board.cell_rect(i);
+            assert!((rect[0] >= -1e-9) && (rect[0] + rect[2] <= 300.0 + 1e-9));
+            assert!((rect[1] >= -1e-9) && (rect[1] + rect[3] <= 300.0 + 1e-9));
+        }
+    }
+
+    #[test]
+    fn hex_neighbours() {
+        let board = Board::hex(1, 300.0);
+        let around: Vec<Option<usize>> = [HexDirection::North,
+                                          HexDirection::NorthEast,
+                                          HexDirection::SouthEast,
+                                          HexDirection::South,
+                                          HexDirection::SouthWest,
+                                          HexDirection::NorthWest]
+            .iter()
+            .map(|&d| board.hex_neighbour(3, d))
+            .collect();
+        assert_eq!(around, vec![Some(0), Some(1), Some(4), Some(6), Some(5), Some(2)]);
+        assert_eq!(board.hex_neighbour(6, HexDirection::South), None);
+        assert_eq!(board.hex_neighbour(7, HexDirection::North), None);
+    }
+
+    #[test]
+    fn hex_patterns() {
+        let mut board = Board::hex(1, 300.0);
+        assert_eq!(board.spawn_pattern(Pattern::Row(1)), vec![2, 3, 4]);
+        assert_eq!(board.pattern_indices(Pattern::Column(2)), vec![1, 4]);
+        assert_eq!(board.pattern_indices(Pattern::Diagonal), vec![1, 3, 5]);
+        for _ in 0..20 {
+            let pattern = board.random_pattern();
+            assert!(!board.pattern_indices(pattern).is_empty());
+        }
+    }
+
+    #[test]
+    fn three_by_three_grid() {
+        let board = Board::from_length(300.0);
+        assert_eq!(board.grid_dim(), 3);
+        assert_eq!(board.cell_size(), 100.0);
+        assert_eq!(board.tiles.len(), 9);
+        assert_eq!(board.x_from_index(5), 200.0);
+        assert_eq!(board.y_from_index(5), 100.0);
+        assert_eq!(board.index_from_pos(Vec2D::new(250.0, 150.0)), Some(5));
+        assert_eq!(board.pattern_indices(Pattern::Diagonal), vec![0, 4, 8]);
+    }
+
+    #[test]
+    fn four_by_four_grid() {
+        let mut board = Board::with_grid(400.0, 4);
+        assert_eq!(board.grid_dim(), 4);
+        assert_eq!(board.cell_size(), 100.0);
+        assert_eq!(board.tiles.len(), 16);
+        assert_eq!(board.x_from_index(7), 300.0);
+        assert_eq!(board.y_from_index(7), 100.0);
+        assert_eq!(board.cell_center(15), Vec2D::new(350.0, 350.0));
+        assert_eq!(board.index_from_pos(Vec2D::new(350.0, 150.0)), Some(7));
+        assert_eq!(board.index_from_pos(Vec2D::new(399.0, 399.0)), Some(15));
+        assert_eq!(board.spawn_pattern(Pattern::Column(3)), vec![3, 7, 11, 15]);
+        assert_eq!(board.pattern_indices(Pattern::Diagonal), vec![0, 5, 10, 15]);
+        for _ in 0..20 {
+            let pattern = board.random_pattern();
+            assert_eq!(board.pattern_indices(pattern).len(), 4);
+        }
+    }
+
+    #[test]
+    fn empty_grid_is_rejected() {
+        match Board::try_with_grid(300.0, 0) {
+            Err(e) => assert_eq!(e, BoardError::InvalidGridDim(0)),
+            Ok(_) => panic!("a board with no cells should be rejected"),
+        }
+    }
+
+    #[test]
+    fn stepping_is_frame_rate_independent() {
+        let start = Sprite::new(10.0, 20.0, 5.0, 5.0, colours::RED)
+            .with_velocity(Vec2D::new(30.0, -12.0));
+        let mut once = start;
+        once.step(1.0);
+        let mut tenths = start;
+        for _ in 0..10 {
+            tenths.step(0.1);
+        }
+        assert_eq!(once.pos, Vec2D::new(40.0, 8.0));
+        assert!((tenths.pos - once.pos).length() < 1e-9);
+    }
+
+    #[test]
+    fn tick_steps_moving_tiles() {
+        let mut board = Board::from_length(300.0);
+        board.place_tile(0);
+        board.place_tile(1);
+        if let Some(tile) = board.tiles.get_mut(0) {
+            tile.sprite.velocity = Vec2D::new(5.0, 0.0);
+        }
+        let still = board.tiles[1].unwrap().sprite.pos;
+        board.tick(2.0);
+        assert_eq!(board.tiles[0].unwrap().sprite.pos, Vec2D::new(10.0, 0.0));
+        assert_eq!(board.tiles[1].unwrap().sprite.pos, still);
+    }
+
+    #[test]
+    fn inflation() {
+        let sprite = Sprite::new(10.0, 20.0, 30.0, 40.0, colours::RED);
+        assert_eq!(sprite.inflated(0.0), sprite);
+        assert_eq!(sprite.inflated(2.5).get_rect(), [7.5, 17.5, 35.0, 45.0]);
+        assert_eq!(sprite.inflated(-10.0).get_rect(), [20.0, 30.0, 10.0, 20.0]);
+        let crushed = sprite.inflated(-100.0);
+        assert_eq!(crushed.get_rect(), [25.0, 40.0, 0.0, 0.0]);
+        assert_eq!(crushed.center(), sprite.center());
+        let circle = Sprite::circle(Vec2D::new(50.0, 50.0), 10.0, colours::RED);
+        assert_eq!(circle.inflated(5.0).shape, Shape::Circle { radius: 15.0 });
+        assert_eq!(circle.inflated(-20.0).shape, Shape::Circle { radius: 0.0 });
+        assert_eq!(circle.inflated(5.0).get_rect(), [35.0, 35.0, 30.0, 30.0]);
+    }
+
+    fn assert_matrix_eq(a: Matrix2d, b: Matrix2d) {
+        for (row_a, row_b) in a.iter().zip(&b) {
+            for (x, y) in row_a.iter().zip(row_b) {
+                assert!((x - y).abs() < 1e-9, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn render_transforms() {
+        use std::f64::consts::PI;
+        let identity = graphics::math::identity();
+        let mut sprite = Sprite::new(0.0, 0.0, 2.0, 2.0, colours::RED);
+        assert_eq!(sprite.render_transform(identity), identity);
+        sprite.rotation = PI / 2.0;
+        assert_matrix_eq(sprite.render_transform(identity),
+                         [[0.0, -1.0, 2.0], [1.0, 0.0, 0.0]]);
+        sprite.rotation = PI / 4.0;
+        let h = 0.5f64.sqrt();
+        assert_matrix_eq(sprite.render_transform(identity),
+                         [[h, -h, 1.0], [h, h, 1.0 - 2.0f64.sqrt()]]);
+        let shifted = graphics::math::translate([10.0, 0.0]);
+        assert_matrix_eq(sprite.render_transform(shifted),
+                         [[h, -h, 11.0], [h, h, 1.0 - 2.0f64.sqrt()]]);
+    }
+
+    #[test]
+    fn sprite_builder_defaults() {
+        let sprite = Sprite::builder().build();
+        assert_eq!(sprite, Sprite::new(0.0, 0.0, 0.0, 0.0, colours::WHITE));
+        assert_eq!(sprite.shape, Shape::Rect);
+        assert_eq!(sprite.layer, TILE_LAYER);
+        assert_eq!(sprite.velocity, Vec2D::empty());
+        assert_eq!(sprite.rotation, 0.0);
+    }
+
+    #[test]
+    fn sprite_builder_matches_constructors() {
+        let built = Sprite::builder()
+            .pos(Vec2D::new(10.0, 20.0))
+            .size(30.0, 40.0)
+            .colour(colours::RED)
+            .build();
+        assert_eq!(built, Sprite::new(10.0, 20.0, 30.0, 40.0, colours::RED));
+        let center = Vec2D::new(50.0, 50.0);
+        let built = Sprite::builder().center(center).size(20.0, 20.0).colour(colours::RED);
+        assert_eq!(built.build(), Sprite::from_center(center, 20.0, 20.0, colours::RED));
+        let circle = built.shape(Shape::Circle { radius: 10.0 }).build();
+        assert_eq!(circle, Sprite::circle(center, 10.0, colours::RED));
+        let mut moving = Sprite::new(0.0, 0.0, 5.0, 5.0, colours::BLUE);
+        moving.layer = CURSOR_LAYER;
+        moving.velocity = Vec2D::new(1.0, 2.0);
+        moving.rotation = 0.5;
+        let built = Sprite::builder()
+            .size(5.0, 5.0)
+            .colour(colours::BLUE)
+            .layer(CURSOR_LAYER)
+            .velocity(Vec2D::new(1.0, 2.0))
+            .rotation(0.5)
+            .build();
+        assert_eq!(built, moving);
+    }
+
+    #[test]
+    fn sprite_outline_style() {
+        let plain = Sprite::new(0.0, 0.0, 10.0, 10.0, colours::RED);
+        assert!(plain.filled);
+        assert_eq!(plain.border, None);
+        let outline = Sprite::builder().border(colours::BLACK, 2.0).filled(false).build();
+        assert!(!outline.filled);
+        assert_eq!(outline.border, Some((colours::BLACK, 2.0)));
+        let mut board = Board::from_length(300.0);
+        board.add_tile_at(0).unwrap();
+        let tile = board.tiles[0].unwrap().sprite;
+        assert_eq!(tile.border, Some((colours::darken(RED, 0.5), TILE_BORDER_THICKNESS)));
+        board.remove_tile(0);
+        board.tick(FADE_DURATION / 2.0);
+        let faded = board.fading_tiles()[0];
+        assert_eq!(faded.border.unwrap().0[3], 0.5);
+    }
+
+    #[test]
+    fn fading_is_frame_rate_independent() {
+        let mut once = Board::from_length(300.0);
+        let mut steps = Board::from_length(300.0);
+        for board in [&mut once, &mut steps].iter_mut() {
+            board.add_tile_at(4).unwrap();
+            board.remove_tile(4);
+        }
+        once.tick(0.1);
+        for _ in 0..10 {
+            steps.tick(0.01);
+        }
+        let (a, b) = (once.fading_tiles()[0], steps.fading_tiles()[0]);
+        assert!((a.colour[3] - b.colour[3]).abs() < 1e-6);
+        assert!(a.colour[3] > 0.0 && a.colour[3] < 1.0);
+    }
+
+    #[test]
+    fn aabb_conversions() {
+        let aabb = Aabb::from_pos_size(Vec2D::new(10.0, 20.0), 30.0, 40.0);
+        assert_eq!(aabb, Aabb::new(Vec2D::new(10.0, 20.0), Vec2D::new(40.0, 60.0)));
+        assert_eq!(aabb.to_rect(), [10.0, 20.0, 30.0, 40.0]);
+        assert_eq!(Aabb::from_rect(aabb.to_rect()), aabb);
+        assert_eq!(Aabb::from([10.0, 20.0, 30.0, 40.0]), aabb);
+        let rect: [f64; 4] = aabb.into();
+        assert_eq!(rect, [10.0, 20.0, 30.0, 40.0]);
+        assert_eq!((aabb.width(), aabb.height(), aabb.area()), (30.0, 40.0, 1200.0));
+        assert_eq!(aabb.center(), Vec2D::new(25.0, 40.0));
+    }
+
+    #[test]
+    fn aabb_intersections() {
+        let a = Aabb::from_rect([0.0, 0.0, 10.0, 10.0]);
+        let b = Aabb::from_rect([5.0, -5.0, 10.0, 10.0]);
+        assert!(a.intersects(&b) && b.intersects(&a));
+        assert_eq!(a.intersection(&b), Some(Aabb::from_rect([5.0, 0.0, 5.0, 5.0])));
+        assert_eq!(a.intersection(&b), b.intersection(&a));
+        let inner = Aabb::from_rect([2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(a.intersection(&inner), Some(inner));
+        for touching in &[[10.0, 0.0, 5.0, 5.0], [0.0, 10.0, 5.0, 5.0], [10.0, 10.0, 5.0, 5.0]] {
+            let t = Aabb::from_rect(*touching);
+            assert!(!a.intersects(&t) && !t.intersects(&a));
+            assert_eq!(a.intersection(&t), None);
+        }
+        assert_eq!(a.intersection(&Aabb::from_rect([20.0, 20.0, 1.0, 1.0])), None);
+    }
+
+    #[test]
+    fn aabb_union() {
+        let a = Aabb::from_rect([0.0, 0.0, 10.0, 10.0]);
+        let b = Aabb::from_rect([20.0, -5.0, 5.0, 5.0]);
+        assert_eq!(a.union(&b), Aabb::from_rect([0.0, -5.0, 25.0, 15.0]));
+        assert_eq!(a.union(&b), b.union(&a));
+        assert_eq!(a.union(&a), a);
+    }
+
+    #[test]
+    fn aabb_contains() {
+        let a = Aabb::from_rect([0.0, 0.0, 10.0, 10.0]);
+        assert!(a.contains(Vec2D::new(5.0, 5.0)));
+        assert!(a.contains(Vec2D::new(10.0, 10.0)));
+        assert!(a.contains_half_open(Vec2D::new(0.0, 0.0)));
+        assert!(!a.contains_half_open(Vec2D::new(10.0, 5.0)));
+        assert!(!a.contains_half_open(Vec2D::new(5.0, 10.0)));
+        assert!(!a.contains(Vec2D::new(-0.1, 5.0)));
+        assert_eq!(a.nearest_point(Vec2D::new(15.0, 5.0)), Vec2D::new(10.0, 5.0));
+        assert_eq!(a.nearest_point(Vec2D::new(3.0, 4.0)), Vec2D::new(3.0, 4.0));
+    }
+
+    #[test]
+    fn aabb_inset_and_move() {
+        let a = Aabb::from_rect([0.0, 0.0, 10.0, 20.0]);
+        assert_eq!(a.inset(0.0), a);
+        assert_eq!(a.inset(2.0), Aabb::from_rect([2.0, 2.0, 6.0, 16.0]));
+        assert_eq!(a.inset(-1.0), Aabb::from_rect([-1.0, -1.0, 12.0, 22.0]));
+        assert_eq!(a.inset(100.0), Aabb::from_rect([5.0, 10.0, 0.0, 0.0]));
+        let bounds = Aabb::from_rect([0.0, 0.0, 100.0, 100.0]);
+        let outside = Aabb::from_rect([95.0, -5.0, 10.0, 10.0]);
+        assert_eq!(outside.moved_within(&bounds), Aabb::from_rect([90.0, 0.0, 10.0, 10.0]));
+        let inside = Aabb::from_rect([40.0, 40.0, 10.0, 10.0]);
+        assert_eq!(inside.moved_within(&bounds), inside);
+        let huge = Aabb::from_rect([-50.0, 20.0, 200.0, 10.0]);
+        assert_eq!(huge.moved_within(&bounds).min, Vec2D::new(0.0, 20.0));
+    }
+
+    #[test]
+    fn cell_aabb() {
+        let board = Board::from_length(300.0);
+        assert_eq!(board.cell_aabb(5), Aabb::from_rect([200.0, 100.0, 100.0, 100.0]));
+        assert_eq!(board.cell_aabb(5).to_rect(), board.cell_rect(5));
+        let sprite = Sprite::new(1.0, 2.0, 3.0, 4.0, colours::RED);
+        assert_eq!(sprite.aabb().to_rect(), sprite.get_rect());
+    }
+
+    #[test]
+    fn blink_phases() {
+        let mut sprite = Sprite::new(0.0, 0.0, 10.0, 10.0, BLACK);
+        let mut blink = Blink::new(1.0);
+        assert!(blink.is_visible());
+        let mut shown = Vec::new();
+        for _ in 0..8 {
+            blink.tick(0.25, &mut sprite);
+            shown.push(sprite.visible);
+        }
+        assert_eq!(shown, vec![true, false, false, true, true, false, false, true]);
+        assert!(Blink::new(0.0).is_visible());
+    }
+
+    #[test]
+    fn builder_visible() {
+        assert!(Sprite::builder().build().visible);
+        assert!(!Sprite::builder().visible(false).build().visible);
+    }
+
+    #[test]
+    fn board_code_round_trip() {
+        let mut board = Board::with_grid(400.0, 4);
+        board.add_wall(5);
+        board.block_cell(10);
+        for &i in &[0, 3, 7, 15] {
+            board.add_tile_at(i).unwrap();
+        }
+        let code = board.to_code();
+        assert_eq!(code, "X..X.#.X..-....X");
+        let copy = Board::from_code(&code, 400.0).unwrap();
+        assert_eq!(copy.grid_dim(), 4);
+        assert_eq!(copy.to_code(), code);
+        assert_eq!(BoardState::from(&copy), BoardState::from(&board));
+        assert!(copy.is_wall(5));
+        assert!(copy.is_blocked(10) && !copy.is_wall(10));
+        for code in &[".", "X", ".........", "XXXXXXXXX", "#-X.#-X.#"] {
+            assert_eq!(Board::from_code(code, 300.0).unwrap().to_code(), *code);
+        }
+    }
+
+    #[test]
+    fn rectangular_board_codes() {
+        let mut board = Board::with_rows_cols(500.0, 2, 5);
+        board.add_tile_at(1).unwrap();
+        board.add_wall(8);
+        let code = board.to_code();
+        assert_eq!(code, ".X.../...#.");
+        let copy = Board::from_code(&code, 500.0).unwrap();
+        assert_eq!((copy.rows(), copy.cols()), (2, 5));
+        assert_eq!(copy.to_code(), code);
+        assert_eq!(BoardState::from(&copy), BoardState::from(&board));
+        let tall = Board::from_code("X/./#", 300.0).unwrap();
+        assert_eq!((tall.rows(), tall.cols()), (3, 1));
+        assert_eq!(tall.to_code(), "X/./#");
+        assert_eq!(Board::from_code("X../....", 300.0), Err(ParseError::UnevenRows(1)));
+        assert_eq!(Board::from_code("X../.o.", 300.0), Err(ParseError::InvalidCell(5, 'o')));
+    }
+
+    #[test]
+    fn hex_board_codes() {
+        let mut board = Board::hex(1, 300.0);
+        board.add_tile_at(0).unwrap();
+        board.add_wall(3);
+        let code = board.to_code();
+        assert_eq!(code, "HX..#...");
+        let copy = Board::from_code(&code, 300.0).unwrap();
+        assert_eq!(copy.layout(), BoardLayout::Hex { radius: 1 });
+        assert_eq!(copy.to_code(), code);
+        assert_eq!(BoardState::from(&copy), BoardState::from(&board));
+        assert_eq!(Board::from_code("H.", 300.0).unwrap().layout(),
+                   BoardLayout::Hex { radius: 0 });
+        assert_eq!(Board::from_code("H.........", 300.0), Err(ParseError::NotHex(9)));
+        assert_eq!(Board::from_code("H", 300.0), Err(ParseError::NotHex(0)));
+        assert_eq!(Board::from_code("H..o....", 300.0), Err(ParseError::InvalidCell(3, 'o')));
+    }
+
+    #[test]
+    fn malformed_board_codes() {
+        assert_eq!(Board::from_code("X..X.", 300.0), Err(ParseError::NotSquare(5)));
+        assert_eq!(Board::from_code("X..o.....", 300.0), Err(ParseError::InvalidCell(3, 'o')));
+        assert_eq!(Board::from_code("", 300.0),
+                   Err(ParseError::Board(BoardError::InvalidGridDim(0))));
+        assert_eq!(Board::from_code("....", -1.0),
+                   Err(ParseError::Board(BoardError::InvalidLength(-1.0))));
+        assert_eq!(Board::from_code("X..é", 300.0), Err(ParseError::InvalidCell(3, 'é')));
+    }
+
+    #[test]
+    fn label_drifts_and_fades() {
+        let mut label = Label::new("MISS", Vec2D::new(10.0, 100.0), RED);
+        assert_eq!(label.faded_colour(), RED);
+        label.tick(0.5);
+        assert_eq!(label.pos, Vec2D::new(10.0, 100.0 - (LABEL_SPEED * 0.5)));
+        assert!(!label.is_expired());
+        assert!(label.faded_colour()[3] < 1.0);
+        label.tick(LABEL_DURATION);
+        assert!(label.is_expired());
+        assert_eq!(label.faded_colour()[3], 0.0);
+    }
+
+    #[test]
+    fn board_state_matches_occupancy() {
+        use std::collections::HashSet;
+
+        let mut board = Board::with_grid(400.0, 4);
+        let mut other = Board::with_grid(200.0, 4);
+        for &i in &[1, 6, 15] {
+            board.add_tile_at(i).unwrap();
+            other.add_tile_at(i).unwrap();
+        }
+        other.tiles.get_mut(6).unwrap().age = 5.0;
+        let state = BoardState::from(&board);
+        assert_eq!(state, BoardState::from(&other));
+        assert_eq!(state.bits(), 0b1000_0000_0100_0010);
+        assert_eq!(state.count(), 3);
+        let mut seen = HashSet::new();
+        seen.insert(state);
+        assert!(seen.contains(&BoardState::from(&other)));
+        board.remove_tile(6);
+        let whacked = BoardState::from(&board);
+        assert!(whacked != state);
+        assert!(!whacked.is_occupied(6) && whacked.is_occupied(15));
+        assert!(!seen.contains(&whacked));
+        assert_eq!(BoardState::from(&Board::from_length(300.0)), BoardState::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "a board with 25 cells is too big for a BoardState")]
+    fn board_state_too_many_cells() {
+        let _ = BoardState::from(&Board::with_grid(500.0, 5));
+    }
+
+    #[test]
+    fn recolour_every_tile() {
+        let mut board = Board::with_grid(400.0, 4);
+        for &i in &[0, 5, 6, 15] {
+            board.add_tile_at(i).unwrap();
+        }
+        board.tiles.get_mut(5).unwrap().sprite.colour[3] = 0.5;
+        board.recolour_tiles(colours::GREEN);
+        let coloured: Vec<usize> = board.iter_tiles()
+            .filter(|&(_, s)| s.colour[..3] == colours::GREEN[..3])
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(coloured, vec![0, 5, 6, 15]);
+        assert_eq!(board.tiles[5].unwrap().sprite.colour[3], 0.5);
+        assert!(board.tiles[1].is_none());
+
+        board.set_theme(Theme::high_contrast());
+        for (_, sprite) in board.iter_tiles() {
+            assert_eq!(sprite.border.map(|b| b.0), Some(Theme::high_contrast().tile_border));
+        }
+    }
+
+    #[test]
+    fn tile_shape_per_kind() {
+        assert_eq!(TileShape::for_kind(TileKind::Normal), TileShape::Plain);
+        assert_eq!(TileShape::for_kind(TileKind::Pattern), TileShape::InnerRect);
+        let mut board = Board::from_length(300.0);
+        board.add_tile_at(4).unwrap();
+        let tile = board.tiles[4].unwrap();
+        let mark = TileShape::InnerRect.mark(&tile.sprite).unwrap();
+        assert_eq!(mark.colour, board.theme().tile_border);
+        assert_eq!(mark.center(), tile.sprite.center());
+        assert!(mark.border.is_none());
+        let plain = Sprite::new(0.0, 0.0, 10.0, 20.0, RED);
+        assert_eq!(TileShape::InnerRect.mark(&plain).unwrap().colour, BLACK);
+    }
+
+    #[test]
+    fn tile_colours_vary_reproducibly() {
+        let mut board1 = Board::seeded(300.0, 5);
+        let mut board2 = Board::seeded(300.0, 5);
+        let varied = Theme { tile_variation: 0.1, ..Theme::default() };
+        board1.set_theme(varied);
+        board2.set_theme(varied);
+        for _ in 0..4 {
+            board1.add_tile();
+            board2.add_tile();
+        }
+        let colours1: Vec<Colour> = board1.iter_tiles().map(|(_, s)| s.colour).collect();
+        let colours2: Vec<Colour> = board2.iter_tiles().map(|(_, s)| s.colour).collect();
+        assert_eq!(colours1, colours2);
+        assert!(colours1.iter().skip(1).any(|&c| c != colours1[0]));
+        let theme = *board1.theme();
+        assert!(colours1.iter().all(|c| colours::approx_eq(*c, theme.tile, theme.tile_variation)));
+
+        board1.set_theme(Theme { tile_variation: 0.0, ..Theme::default() });
+        board1.add_tile();
+        assert!(board1.iter_tiles().all(|(_, s)| s.colour == RED));
+    }
+
+    #[test]
+    fn try_new_rejects_bad_sizes() {
+        use std::f64;
+
+        assert_eq!(Sprite::try_new(0.0, 0.0, 10.0, 20.0, RED),
+                   Ok(Sprite::new(0.0, 0.0, 10.0, 20.0, RED)));
+        assert!(Sprite::try_new(0.0, 0.0, 0.0, 0.0, RED).is_ok());
+        assert_eq!(Sprite::try_new(0.0, 0.0, -1.0, 20.0, RED),
+                   Err(SpriteError::InvalidWidth(-1.0)));
+        assert_eq!(Sprite::try_new(0.0, 0.0, 10.0, f64::INFINITY, RED),
+                   Err(SpriteError::InvalidHeight(f64::INFINITY)));
+        match Sprite::try_new(0.0, 0.0, f64::NAN, 20.0, RED) {
+            Err(SpriteError::InvalidWidth(w)) => assert!(w.is_nan()),
+            other => panic!("expected an invalid width, got {:?}", other),
+        }
+        assert_eq!(SpriteError::InvalidHeight(-2.5).to_string(),
+                   "sprite height must be non-negative and finite, got -2.5");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "invalid sprite size -5 x 10")]
+    fn new_panics_on_negative_size() {
+        Sprite::new(0.0, 0.0, -5.0, 10.0, RED);
+    }
+
+    #[test]
+    fn zero_size_never_overlaps() {
+        let tile = Sprite::new(0.0, 0.0, 100.0, 100.0, RED);
+        let point = Sprite::new(50.0, 50.0, 0.0, 0.0, RED);
+        let line = Sprite::new(50.0, 0.0, 0.0, 100.0, RED);
+        let dot = Sprite::circle(Vec2D::new(50.0, 50.0), 0.0, RED);
+        for empty in &[point, line, dot] {
+            assert!(empty.is_empty());
+            assert!(!tile.is_overlapping(empty));
+            assert!(!empty.is_overlapping(&tile));
+            assert!(!empty.is_overlapping(empty));
+            assert_eq!(tile.overlap_area(empty), 0.0);
+            assert_eq!(empty.overlap_fraction(&tile), 0.0);
+            assert!(!empty.contains_point(Vec2D::new(50.0, 50.0)));
+        }
+        assert!(!tile.is_empty());
+        assert!(tile.contains_point(Vec2D::new(50.0, 50.0)));
+    }
+
+    #[test]
+    fn degenerate_sizes_never_overlap() {
+        use std::f64;
+
+        let tile = Sprite::new(0.0, 0.0, 100.0, 100.0, RED);
+        let mut negative = tile;
+        negative.width = -200.0;
+        let mut nan = tile;
+        nan.height = f64::NAN;
+        for bad in &[negative, nan] {
+            assert!(bad.is_empty());
+            assert!(!tile.is_overlapping(bad));
+            assert!(!bad.is_overlapping(&tile));
+            assert_eq!(tile.overlap_area(bad), 0.0);
+            assert_eq!(bad.overlap_fraction(&tile), 0.0);
+            assert!(!bad.contains_point(Vec2D::new(50.0, 50.0)));
+            assert!(!bad.contains_point(Vec2D::new(-50.0, 50.0)));
+        }
+    }
+
+    #[test]
+    fn tile_ids_never_reused() {
+        use std::collections::HashSet;
+
+        let mut board = Board::from_length(300.0);
+        let mut other = Board::from_length(300.0);
+        let mut seen = HashSet::new();
+        for _ in 0..3 {
+            while board.add_tile().is_some() {
+                other.add_tile();
+            }
+            for (_, sprite) in board.iter_tiles().chain(other.iter_tiles()) {
+                assert!(sprite.id > 0);
+                assert!(seen.insert(sprite.id));
+            }
+            board.clear_board();
+            other.clear_board();
+        }
+        assert_eq!(seen.len(), 3 * 18);
+        assert_eq!(board.tile_id(0), None);
+        board.add_tile_at(0).unwrap();
+        assert!(!seen.contains(&board.tile_id(0).unwrap()));
+        assert_eq!(Sprite::builder().build().id, 0);
+    }
+
+    #[test]
+    fn rect_inset() {
+        let r = [10.0, 20.0, 100.0, 40.0];
+        assert_eq!(rect::inset(r, 5.0), [15.0, 25.0, 90.0, 30.0]);
+        assert_eq!(rect::inset(r, -5.0), [5.0, 15.0, 110.0, 50.0]);
+        assert_eq!(rect::inset(r, 0.0), r);
+        assert_eq!(rect::inset(r, 30.0), [40.0, 40.0, 40.0, 0.0]);
+        assert_eq!(rect::inset(r, 30.0), Aabb::from_rect(r).inset(30.0).to_rect());
+    }
+
+    #[test]
+    fn rect_union() {
+        let a = [0.0, 0.0, 10.0, 10.0];
+        let b = [20.0, -5.0, 5.0, 5.0];
+        assert_eq!(rect::union(a, b), [0.0, -5.0, 25.0, 15.0]);
+        assert_eq!(rect::union(a, b), rect::union(b, a));
+        assert_eq!(rect::union(a, [2.0, 2.0, 1.0, 1.0]), a);
+    }
+
+    #[test]
+    fn rect_intersection() {
+        let a = [0.0, 0.0, 10.0, 10.0];
+        assert_eq!(rect::intersection(a, [5.0, -5.0, 10.0, 10.0]), Some([5.0, 0.0, 5.0, 5.0]));
+        assert_eq!(rect::intersection(a, [2.0, 2.0, 1.0, 1.0]), Some([2.0, 2.0, 1.0, 1.0]));
+        assert_eq!(rect::intersection(a, a), Some(a));
+        assert_eq!(rect::intersection(a, [20.0, 20.0, 5.0, 5.0]), None);
+        assert_eq!(rect::intersection(a, [10.0, 0.0, 5.0, 5.0]), None);
+        assert_eq!(rect::intersection(a, [10.0, 10.0, 5.0, 5.0]), None);
+    }
+
+    #[test]
+    fn rect_center() {
+        assert_eq!(rect::center([10.0, 20.0, 100.0, 40.0]), Vec2D::new(60.0, 40.0));
+        assert_eq!(rect::center([0.0, 0.0, 0.0, 0.0]), Vec2D::empty());
+    }
+
+    #[test]
+    fn rect_fit_square() {
+        assert_eq!(rect::fit_square([0.0, 0.0, 800.0, 600.0]), [100.0, 0.0, 600.0, 600.0]);
+        assert_eq!(rect::fit_square([0.0, 0.0, 300.0, 500.0]), [0.0, 100.0, 300.0, 300.0]);
+        assert_eq!(rect::fit_square([10.0, 10.0, 50.0, 50.0]), [10.0, 10.0, 50.0, 50.0]);
+        assert_eq!(rect::fit_square([0.0, 0.0, -10.0, 20.0]), [-5.0, 10.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn rotation_defaults_to_zero() {
+        use std::f64::consts::PI;
+
+        assert_eq!(Sprite::new(0.0, 0.0, 10.0, 10.0, colours::RED).rotation, 0.0);
+        assert_eq!(Sprite::circle(Vec2D::new(5.0, 5.0), 5.0, colours::RED).rotation, 0.0);
+        let mut board = Board::from_length(300.0);
+        board.add_tile_at(4).unwrap();
+        assert_eq!(board.tiles[4].unwrap().sprite.rotation, 0.0);
+        let tile = board.tiles[4].unwrap().sprite;
+        let mut spun = tile;
+        spun.rotation = PI / 4.0;
+        let corner = Sprite::new(95.0, 95.0, 10.0, 10.0, colours::RED);
+        assert!(tile.is_overlapping(&corner));
+        assert!(spun.is_overlapping(&corner));
+    }
+
+    #[test]
+    fn is_occupied() {
+        let mut board = Board::from_length(300.0);
+        assert!((0..9).all(|i| !board.is_occupied(i)));
+        board.add_tile_at(2).unwrap();
+        board.add_tile_at(8).unwrap();
+        assert!(board.is_occupied(2));
+        assert!(board.is_occupied(8));
+        assert!(!board.is_occupied(3));
+        board.remove_tile(2);
+        assert!(!board.is_occupied(2));
+        assert!(!board.is_occupied(9));
+        assert!(!board.is_occupied(usize::max_value()));
+        board.block_cell(8);
+        assert!(!board.is_occupied(8));
+        let big = Board::with_grid(500.0, 5);
+        assert!(!big.is_occupied(24));
+        assert!(!big.is_occupied(25));
+    }
+
+    #[test]
+    fn vec2d_f32() {
+        let a: Vec2D<f32> = Vec2D::new(1.0, 2.0);
+        let b = Vec2D::new(4.0f32, 6.0);
+        assert_eq!(a + b, Vec2D::new(5.0, 8.0));
+        assert_eq!((b - a) * 2.0, Vec2D::new(6.0, 8.0));
+        assert_eq!(a.distance(b), 5.0);
+        assert_eq!(a.dot(b), 16.0);
+        assert_eq!(Vec2D::lerp(a, b, 0.5), Vec2D::new(2.5, 4.0));
+        assert_eq!(Vec2D::<f32>::zero().normalized(), Vec2D::zero());
+        assert_eq!(Vec2D::<f64>::zero(), Vec2D::empty());
+        assert_eq!(a.clamped(Vec2D::new(0.0, 3.0), Vec2D::new(0.5, 4.0)), Vec2D::new(0.5, 3.0));
+    }
+
+    #[test]
+    fn vec2d_conversions() {
+        let wide = Vec2D::new(0.1, -12345.678);
+        let narrow: Vec2D<f32> = wide.into();
+        assert!((narrow.x - 0.1f32).abs() < f32::EPSILON);
+        assert!((narrow.y - -12345.678f32).abs() < f32::EPSILON);
+        let back: Vec2D = narrow.into();
+        assert!((back.x - wide.x).abs() < 1e-6);
+        assert!((back.y - wide.y).abs() < 1e-3);
+        let exact = Vec2D::new(0.5f32, -256.25);
+        assert_eq!(Vec2D::<f32>::from(Vec2D::<f64>::from(exact)), exact);
+        assert_eq!(Vec2D::<f64>::from(exact), Vec2D::new(0.5, -256.25));
+    }
+
+    #[test]
+    fn snap_to_grid() {
+        let board = Board::from_length(300.0);
+        let cursor = |x: f64, y: f64| {
+            let mut sprite = Sprite::new(0.0, 0.0, 20.0, 20.0, colours::YELLOW);
+            sprite.set_center(Vec2D::new(x, y));
+            sprite
+        };
+        let cases = [((155.0, 140.0), 4, (150.0, 150.0)),
+                     ((10.0, 290.0), 6, (50.0, 250.0)),
+                     ((100.0, 150.0), 4, (150.0, 150.0)),
+                     ((99.9, 150.0), 3, (50.0, 150.0)),
+                     ((200.0, 200.0), 8, (250.0, 250.0)),
+                     ((-40.0, 120.0), 3, (50.0, 150.0)),
+                     ((400.0, -10.0), 2, (250.0, 50.0))];
+        for &((x, y), cell, (cx, cy)) in &cases {
+            let mut sprite = cursor(x, y);
+            assert_eq!(sprite.snap_to_grid(&board), cell);
+            assert_eq!(sprite.center(), Vec2D::new(cx, cy));
+            assert_eq!(sprite.width, 20.0);
+            assert_eq!(sprite.snap_to_grid(&board), cell);
+            assert_eq!(sprite.center(), Vec2D::new(cx, cy));
+        }
+    }
+
+    #[test]
+    fn snap_to_hex_grid() {
+        let board = Board::hex(1, 300.0);
+        let mut sprite = Sprite::new(0.0, 0.0, 10.0, 10.0, colours::YELLOW);
+        let near_top = board.cell_center(0) + Vec2D::new(3.0, -4.0);
+        sprite.set_center(near_top);
+        assert_eq!(sprite.snap_to_grid(&board), 0);
+        assert_eq!(sprite.center(), board.cell_center(0));
+        sprite.set_center(Vec2D::new(150.0, -500.0));
+        assert_eq!(sprite.snap_to_grid(&board), 0);
+        assert_eq!(sprite.center(), board.cell_center(0));
+    }
+
+    #[test]
+    fn rectangular_board_positions() {
+        let mut board = Board::with_rows_cols(500.0, 2, 5);
+        board.origin = Vec2D::new(10.0, 20.0);
+        assert_eq!((board.rows(), board.cols(), board.grid_dim()), (2, 5, 5));
+        assert_eq!(board.cell_size(), 100.0);
+        assert_eq!((board.width(), board.height()), (500.0, 200.0));
+        let xs: Vec<f64> = (0..10).map(|i| board.x_from_index(i)).collect();
+        let ys: Vec<f64> = (0..10).map(|i| board.y_from_index(i)).collect();
+        assert_eq!(xs, vec![10.0, 110.0, 210.0, 310.0, 410.0, 10.0, 110.0, 210.0, 310.0, 410.0]);
+        assert_eq!(ys, vec![20.0, 20.0, 20.0, 20.0, 20.0, 120.0, 120.0, 120.0, 120.0, 120.0]);
+        for i in 0..10 {
+            let center = board.cell_center(i);
+            assert_eq!(board.index_from_pos(center), Some(i));
+        }
+        assert_eq!(board.index_from_pos(Vec2D::new(450.0, 220.0)), None);
+        assert_eq!(board.index_from_pos(Vec2D::new(509.0, 219.0)), Some(9));
+        assert_eq!(board.index_from_pos(Vec2D::new(510.0, 100.0)), None);
+
+        let tall = Board::with_rows_cols(500.0, 5, 2);
+        assert_eq!((tall.width(), tall.height()), (200.0, 500.0));
+        assert_eq!(tall.x_from_index(3), 100.0);
+        assert_eq!(tall.y_from_index(3), 100.0);
+        assert_eq!(Board::with_grid(300.0, 3), Board::with_rows_cols(300.0, 3, 3));
+    }
+
+    #[test]
+    fn rectangular_board_capacity() {
+        let mut board = Board::with_rows_cols(500.0, 2, 5);
+        assert_eq!(board.tiles.len(), 10);
+        assert_eq!(board.free_positions().len(), 10);
+        assert_eq!(board.add_tile_at(10), Err(BoardError::OutOfRange(10)));
+        while board.add_tile().is_some() {}
+        assert!(board.is_full());
+        assert_eq!(board.iter_tiles().count(), 10);
+        for (i, sprite) in board.iter_tiles() {
+            assert_eq!(sprite.get_rect(), board.cell_rect(i));
+        }
+        assert_eq!(board.pattern_indices(Pattern::Row(1)), vec![5, 6, 7, 8, 9]);
+        assert_eq!(board.pattern_indices(Pattern::Column(3)), vec![3, 8]);
+        assert_eq!(board.pattern_indices(Pattern::Diagonal), vec![0, 6]);
+        assert_eq!(Board::try_with_rows_cols(500.0, 0, 5).err(),
+                   Some(BoardError::InvalidRowsCols(0, 5)));
+        assert_eq!(Board::try_with_rows_cols(500.0, 2, 0).err(),
+                   Some(BoardError::InvalidRowsCols(2, 0)));
+    }
+
+    #[test]
+    fn timer_finishes_at_boundary() {
+        let mut timer = Timer::new(0.5);
+        assert!(!timer.is_finished());
+        assert!(!timer.tick(0.25));
+        assert!(timer.tick(0.25));
+        assert!(timer.is_finished());
+        assert_eq!(timer.remaining(), 0.0);
+        assert_eq!(timer.fraction(), 1.0);
+    }
+
+    #[test]
+    fn timer_ticks_after_finishing() {
+        let mut timer = Timer::new(1.0);
+        assert!(timer.tick(1.5));
+        for _ in 0..3 {
+            assert!(!timer.tick(1.0));
+            assert!(timer.is_finished());
+            assert_eq!(timer.remaining(), 0.0);
+            assert_eq!(timer.fraction(), 1.0);
+        }
+        timer.reset();
+        assert_eq!(timer, Timer::new(1.0));
+        assert!(timer.tick(1.0));
+        timer.reset_with(2.0);
+        assert!(!timer.tick(1.0));
+        assert!(timer.tick(1.0));
+    }
+
+    #[test]
+    fn timer_progress() {
+        let mut timer = Timer::new(2.0);
+        assert_eq!(timer.fraction(), 0.0);
+        assert_eq!(timer.remaining(), 2.0);
+        timer.tick(0.5);
+        assert_eq!(timer.fraction(), 0.25);
+        assert_eq!(timer.remaining(), 1.5);
+        timer.tick(1.0);
+        assert_eq!(timer.fraction(), 0.75);
+        assert_eq!(timer.remaining(), 0.5);
+        let mut instant = Timer::new(0.0);
+        assert!(instant.is_finished());
+        assert_eq!(instant.fraction(), 1.0);
+        assert!(!instant.tick(0.1));
     }
 }
\ No newline at end of file
@@ -0,0 +1,285 @@
+//! Contains the key bindings used to play **Whack!**
+use piston::input::Key;
+
+/// Something the player can do by pressing a key, see `KeyMap`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Moves the cursor up a row, or up the difficulties in the menu.
+    MoveUp,
+    /// Moves the cursor down a row, or down the difficulties in the menu.
+    MoveDown,
+    /// Moves the cursor left a column.
+    MoveLeft,
+    /// Moves the cursor right a column.
+    MoveRight,
+    /// Whacks the tile under the cursor.
+    Whack,
+    /// Chooses the selected difficulty in the menu, starts play when ready and leaves the
+    /// game over screen.
+    Start,
+    /// Pauses the game. There is no pausing yet, so nothing is bound to this by default.
+    Pause,
+    /// Starts a new game straight away from the game over screen.
+    Restart,
+    /// Moves the cursor to the next board in a multi-board game.
+    SwitchBoard,
+    /// Takes back the last whack in practice mode.
+    Undo,
+    /// Switches to the next colour theme before play starts.
+    CycleTheme,
+    /// Switches between windowed and fullscreen.
+    ToggleFullscreen,
+    /// Moves the cursor straight to a cell of a 3x3 square board, numbered from 0 along the
+    /// top row, and whacks it.
+    SelectCell(usize),
+}
+
+impl Action {
+    /// Returns the change in row and column, in that order, of a move `Action`, or `None` for
+    /// any other `Action`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::input::Action;
+    ///
+    /// assert_eq!(Action::MoveLeft.offset(), Some((0, -1)));
+    /// assert_eq!(Action::Whack.offset(), None);
+    /// ```
+    pub fn offset(&self) -> Option<(isize, isize)> {
+        match *self {
+            Action::MoveUp => Some((-1, 0)),
+            Action::MoveDown => Some((1, 0)),
+            Action::MoveLeft => Some((0, -1)),
+            Action::MoveRight => Some((0, 1)),
+            _ => None,
+        }
+    }
+}
+
+/// Maps keys to the `Action`s they perform.
+///
+/// A key can be bound to several actions, which are told apart by the state of the game. By
+/// default:
+///
+/// * the arrow keys, W/A/S/D and H/J/K/L, as in Vim, move the cursor,
+/// * Space whacks and starts,
+/// * R restarts,
+/// * Tab switches boards,
+/// * U undoes,
+/// * T cycles the colour theme,
+/// * F toggles fullscreen,
+/// * the digits 1 to 9, on the number row and the numpad, select cells laid out like a numpad,
+///   with 7, 8 and 9 along the top row so that they match the board on screen.
+///
+/// Hex boards are always played with Q/W/E/A/S/D instead of the move actions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyMap {
+    bindings: Vec<(Key, Action)>,
+}
+
+impl KeyMap {
+    /// Returns a `KeyMap` with no keys bound.
+    pub fn new() -> KeyMap {
+        KeyMap { bindings: Vec::new() }
+    }
+
+    /// Binds `key` to perform `action`, alongside any other actions it is bound to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate piston;
+    /// extern crate whack;
+    ///
+    /// use piston::input::Key;
+    /// use whack::input::{Action, KeyMap};
+    ///
+    /// let mut keymap = KeyMap::new();
+    /// keymap.bind(Key::Return, Action::Whack);
+    /// keymap.bind(Key::Return, Action::Start);
+    /// assert_eq!(keymap.actions(Key::Return), vec![Action::Whack, Action::Start]);
+    /// assert!(keymap.actions(Key::Space).is_empty());
+    /// ```
+    pub fn bind(&mut self, key: Key, action: Action) {
+        if !self.is_bound(key, action) {
+            self.bindings.push((key, action));
+        }
+    }
+
+    /// Stops `key` from performing `action`.
+    pub fn unbind(&mut self, key: Key, action: Action) {
+        self.bindings.retain(|&binding| binding != (key, action));
+    }
+
+    /// Returns whether `key` performs `action`.
+    pub fn is_bound(&self, key: Key, action: Action) -> bool {
+        self.bindings.contains(&(key, action))
+    }
+
+    /// Returns every `Action` that `key` performs, in the order they were bound.
+    pub fn actions(&self, key: Key) -> Vec<Action> {
+        self.bindings.iter().filter(|&&(k, _)| k == key).map(|&(_, a)| a).collect()
+    }
+
+    /// Returns every key that performs `action`, in the order they were bound.
+    pub fn keys(&self, action: Action) -> Vec<Key> {
+        self.bindings.iter().filter(|&&(_, a)| a == action).map(|&(k, _)| k).collect()
+    }
+
+    /// Returns the name of the first key that performs `action` in capitals, for prompts, or
+    /// `None` if no key does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::input::{Action, KeyMap};
+    ///
+    /// let keymap = KeyMap::default();
+    /// assert_eq!(keymap.key_name(Action::Start), Some("SPACE".to_string()));
+    /// assert_eq!(keymap.key_name(Action::Pause), None);
+    /// ```
+    pub fn key_name(&self, action: Action) -> Option<String> {
+        self.keys(action).first().map(|key| format!("{:?}", key).to_uppercase())
+    }
+
+    /// Returns the cell of a board with `rows` rows and `cols` columns that `key` selects with
+    /// `Action::SelectCell`, or `None` if it doesn't select one.
+    ///
+    /// Cells are only selected on a 3x3 board.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate piston;
+    /// extern crate whack;
+    ///
+    /// use piston::input::Key;
+    /// use whack::input::KeyMap;
+    ///
+    /// let keymap = KeyMap::default();
+    /// assert_eq!(keymap.selected_cell(Key::NumPad7, 3, 3), Some(0));
+    /// assert_eq!(keymap.selected_cell(Key::D3, 3, 3), Some(8));
+    /// assert_eq!(keymap.selected_cell(Key::D3, 4, 4), None);
+    /// ```
+    pub fn selected_cell(&self, key: Key, rows: usize, cols: usize) -> Option<usize> {
+        if (rows, cols) != (3, 3) {
+            return None;
+        }
+        self.actions(key)
+            .iter()
+            .filter_map(|&action| match action {
+                Action::SelectCell(cell) if cell < rows * cols => Some(cell),
+                _ => None,
+            })
+            .next()
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> KeyMap {
+        let mut keymap = KeyMap::new();
+        let defaults = [(Key::Up, Action::MoveUp),
+                        (Key::Down, Action::MoveDown),
+                        (Key::Left, Action::MoveLeft),
+                        (Key::Right, Action::MoveRight),
+                        (Key::W, Action::MoveUp),
+                        (Key::S, Action::MoveDown),
+                        (Key::A, Action::MoveLeft),
+                        (Key::D, Action::MoveRight),
+                        (Key::H, Action::MoveLeft),
+                        (Key::J, Action::MoveDown),
+                        (Key::K, Action::MoveUp),
+                        (Key::L, Action::MoveRight),
+                        (Key::Space, Action::Whack),
+                        (Key::Space, Action::Start),
+                        (Key::R, Action::Restart),
+                        (Key::Tab, Action::SwitchBoard),
+                        (Key::U, Action::Undo),
+                        (Key::T, Action::CycleTheme),
+                        (Key::F, Action::ToggleFullscreen)];
+        for &(key, action) in &defaults {
+            keymap.bind(key, action);
+        }
+        let digits = [(Key::D1, Key::NumPad1),
+                      (Key::D2, Key::NumPad2),
+                      (Key::D3, Key::NumPad3),
+                      (Key::D4, Key::NumPad4),
+                      (Key::D5, Key::NumPad5),
+                      (Key::D6, Key::NumPad6),
+                      (Key::D7, Key::NumPad7),
+                      (Key::D8, Key::NumPad8),
+                      (Key::D9, Key::NumPad9)];
+        for (i, &(digit, numpad)) in digits.iter().enumerate() {
+            let cell = ((2 - (i / 3)) * 3) + (i % 3);
+            keymap.bind(digit, Action::SelectCell(cell));
+            keymap.bind(numpad, Action::SelectCell(cell));
+        }
+        keymap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bindings() {
+        let keymap = KeyMap::default();
+        assert_eq!(keymap.keys(Action::MoveUp), vec![Key::Up, Key::W, Key::K]);
+        assert_eq!(keymap.keys(Action::MoveRight), vec![Key::Right, Key::D, Key::L]);
+        assert_eq!(keymap.actions(Key::Space), vec![Action::Whack, Action::Start]);
+        assert_eq!(keymap.actions(Key::R), vec![Action::Restart]);
+        assert_eq!(keymap.actions(Key::F), vec![Action::ToggleFullscreen]);
+        assert!(keymap.keys(Action::Pause).is_empty());
+        assert!(keymap.actions(Key::Return).is_empty());
+    }
+
+    #[test]
+    fn digits_match_numpad_layout() {
+        let keymap = KeyMap::default();
+        let rows = [[Key::NumPad7, Key::NumPad8, Key::NumPad9],
+                    [Key::NumPad4, Key::NumPad5, Key::NumPad6],
+                    [Key::NumPad1, Key::NumPad2, Key::NumPad3]];
+        let number_row = [[Key::D7, Key::D8, Key::D9],
+                          [Key::D4, Key::D5, Key::D6],
+                          [Key::D1, Key::D2, Key::D3]];
+        for row in 0..3 {
+            for col in 0..3 {
+                let cell = Some((row * 3) + col);
+                assert_eq!(keymap.selected_cell(rows[row][col], 3, 3), cell);
+                assert_eq!(keymap.selected_cell(number_row[row][col], 3, 3), cell);
+            }
+        }
+        assert_eq!(keymap.selected_cell(Key::D0, 3, 3), None);
+        assert_eq!(keymap.selected_cell(Key::NumPad0, 3, 3), None);
+        assert_eq!(keymap.selected_cell(Key::A, 3, 3), None);
+        assert_eq!(keymap.selected_cell(Key::D5, 3, 4), None);
+        assert_eq!(keymap.selected_cell(Key::D5, 5, 5), None);
+    }
+
+    #[test]
+    fn rebinding_cells() {
+        let mut keymap = KeyMap::default();
+        keymap.unbind(Key::D7, Action::SelectCell(0));
+        keymap.bind(Key::Q, Action::SelectCell(0));
+        keymap.bind(Key::X, Action::SelectCell(9));
+        assert_eq!(keymap.selected_cell(Key::D7, 3, 3), None);
+        assert_eq!(keymap.selected_cell(Key::Q, 3, 3), Some(0));
+        assert_eq!(keymap.selected_cell(Key::X, 3, 3), None);
+        assert_eq!(keymap.keys(Action::SelectCell(8)), vec![Key::D3, Key::NumPad3]);
+    }
+
+    #[test]
+    fn rebinding() {
+        let mut keymap = KeyMap::default();
+        keymap.unbind(Key::Space, Action::Whack);
+        keymap.bind(Key::Return, Action::Whack);
+        keymap.bind(Key::Return, Action::Whack);
+        assert_eq!(keymap.actions(Key::Space), vec![Action::Start]);
+        assert_eq!(keymap.keys(Action::Whack), vec![Key::Return]);
+        assert!(keymap.is_bound(Key::Return, Action::Whack));
+        assert!(!keymap.is_bound(Key::Space, Action::Whack));
+        assert_eq!(keymap.key_name(Action::Whack), Some("RETURN".to_string()));
+    }
+}
@@ -2,57 +2,649 @@
 
 pub mod colours;
 pub mod gobs;
+pub mod input;
+pub mod render;
+pub mod scoring;
+pub mod textures;
 
 extern crate rand;
 extern crate piston;
 extern crate graphics;
 extern crate glutin_window;
 extern crate opengl_graphics;
+extern crate num_traits;
 
+use std::collections::HashSet;
 use std::error::Error;
+use std::fmt;
+use std::time::Instant;
 use glutin_window::GlutinWindow as Window;
-use opengl_graphics::{GlGraphics, OpenGL};
+use input::{Action, KeyMap};
+use opengl_graphics::{GlGraphics, GlyphCache, OpenGL};
 use piston::event_loop::*;
 use piston::input::*;
-use piston::window::WindowSettings;
+use piston::window::{AdvancedWindow, WindowSettings};
+use rand::Rng;
+use render::{GlRenderer, Renderer};
+use scoring::{HitContext, ScoringStrategy};
+use textures::TextureStore;
+
+/// The largest time step, in seconds, that a single update will advance the game by.
+///
+/// If the window loses focus or the OS stalls, the next update can carry a very large `dt`.
+/// Capping it stops a single stall from expiring every timer at once.
+pub const MAX_UPDATE_DT: f64 = 0.25;
+
+/// The slowest that `GameManager::set_time_scale` lets the game run.
+pub const MIN_TIME_SCALE: f64 = 0.1;
+
+/// The fastest that `GameManager::set_time_scale` lets the game run.
+pub const MAX_TIME_SCALE: f64 = 4.0;
+
+/// The number of whacks that can be undone in practice mode, see `GameManager::undo`.
+pub const MAX_UNDO_HISTORY: usize = 10;
+
+/// The number of frames that `GameManager::average_fps` is averaged over.
+pub const FRAME_TIME_SAMPLES: usize = 60;
+
+/// How long, in seconds, the cursor spins for after a successful whack.
+pub const CURSOR_SPIN_TIME: f64 = 0.3;
+
+/// Tiles blink once they have less than this many seconds left before they expire.
+pub const EXPIRY_WARNING_TIME: f64 = 1.0;
+
+/// The time, in seconds, for one flash of a tile that is about to expire, see `gobs::Blink`.
+pub const EXPIRY_BLINK_PERIOD: f64 = 0.2;
 
 /// Represents the state of the game.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum GameState {
+    Menu,
     Ready,
     Playing,
     Win,
     Lose,
 }
 
+impl fmt::Display for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match *self {
+            GameState::Menu => "Menu",
+            GameState::Ready => "Ready",
+            GameState::Playing => "Playing",
+            GameState::Win => "You Win!",
+            GameState::Lose => "Game Over",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// How quickly tiles are added, chosen from the menu at the start of the game.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Returns the `Difficulty` after this one in the menu, wrapping from the last to the first.
+    pub fn next(&self) -> Difficulty {
+        match *self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    /// Returns the `Difficulty` before this one in the menu, wrapping from the first to the last.
+    pub fn previous(&self) -> Difficulty {
+        match *self {
+            Difficulty::Easy => Difficulty::Hard,
+            Difficulty::Normal => Difficulty::Easy,
+            Difficulty::Hard => Difficulty::Normal,
+        }
+    }
+
+    /// Returns the `max_time` and `min_time` between spawns, in seconds, for this `Difficulty`.
+    pub fn tile_times(&self) -> (f64, f64) {
+        match *self {
+            Difficulty::Easy => (2.0, 0.4),
+            Difficulty::Normal => (1.0, 0.1),
+            Difficulty::Hard => (0.6, 0.05),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match *self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// What happens when the cursor is moved into the edge of a square `Board`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CursorEdgeBehaviour {
+    /// The cursor stops at the edge.
+    Clamp,
+    /// The cursor reappears in the cell on the opposite edge of the same row or column.
+    Wrap,
+}
+
+/// The rules for how a game of **Whack!** ends.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GameMode {
+    /// The game is lost once every board is full and no lives are left.
+    Classic,
+    /// Tiles keep spawning and can be whacked forever, the game is never lost.
+    Endless,
+}
+
+/// The controller buttons, by their button number, that play **Whack!** with a gamepad.
+///
+/// The defaults follow the SDL game controller layout, with the d-pad moving the cursor and
+/// the bottom face button (A on an Xbox pad) whacking.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ControllerButtonMap {
+    pub up: u8,
+    pub down: u8,
+    pub left: u8,
+    pub right: u8,
+    pub whack: u8,
+}
+
+impl Default for ControllerButtonMap {
+    fn default() -> ControllerButtonMap {
+        ControllerButtonMap {
+            up: 11,
+            down: 12,
+            left: 13,
+            right: 14,
+            whack: 0,
+        }
+    }
+}
+
+/// Returns the `Action`s that controller `button` performs under `map`, which are empty if the
+/// button isn't mapped.
+///
+/// Movement buttons perform the move actions and the whack button both whacks and starts, like
+/// Space by default, so a controller can also be used in the menus. Buttons perform their
+/// actions directly rather than standing in for keys, so they keep working when the keys are
+/// rebound.
+///
+/// # Examples
+///
+/// ```
+/// extern crate piston;
+/// extern crate whack;
+///
+/// use piston::input::ControllerButton;
+/// use whack::{map_controller_button, ControllerButtonMap};
+/// use whack::input::Action;
+///
+/// let map = ControllerButtonMap::default();
+/// let whack = ControllerButton::new(0, map.whack);
+/// assert_eq!(map_controller_button(whack, &map), vec![Action::Whack, Action::Start]);
+/// ```
+pub fn map_controller_button(button: ControllerButton, map: &ControllerButtonMap) -> Vec<Action> {
+    let b = button.button;
+    if b == map.up {
+        vec![Action::MoveUp]
+    } else if b == map.down {
+        vec![Action::MoveDown]
+    } else if b == map.left {
+        vec![Action::MoveLeft]
+    } else if b == map.right {
+        vec![Action::MoveRight]
+    } else if b == map.whack {
+        vec![Action::Whack, Action::Start]
+    } else {
+        Vec::new()
+    }
+}
+
+/// The durations of the last `FRAME_TIME_SAMPLES` frames, kept in a fixed size ring buffer so
+/// that recording a frame never allocates.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FrameTimes {
+    times: [f64; FRAME_TIME_SAMPLES],
+    /// The index that the next frame time is written to.
+    next: usize,
+    /// The number of frame times recorded, up to `FRAME_TIME_SAMPLES`.
+    len: usize,
+}
+
+impl FrameTimes {
+    /// Returns a `FrameTimes` with no frames recorded.
+    pub fn new() -> FrameTimes {
+        FrameTimes {
+            times: [0.0; FRAME_TIME_SAMPLES],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Records a frame that took `dt` seconds, replacing the oldest once the buffer is full.
+    ///
+    /// Durations that are not positive and finite are ignored.
+    pub fn record(&mut self, dt: f64) {
+        if !(dt.is_finite() && dt > 0.0) {
+            return;
+        }
+        self.times[self.next] = dt;
+        self.next = (self.next + 1) % FRAME_TIME_SAMPLES;
+        self.len = (self.len + 1).min(FRAME_TIME_SAMPLES);
+    }
+
+    /// Returns the mean duration in seconds of the recorded frames, or `None` if there are
+    /// none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::FrameTimes;
+    ///
+    /// let mut frame_times = FrameTimes::new();
+    /// assert_eq!(frame_times.average(), None);
+    /// frame_times.record(0.01);
+    /// frame_times.record(0.03);
+    /// assert_eq!(frame_times.average(), Some(0.02));
+    /// ```
+    pub fn average(&self) -> Option<f64> {
+        if self.len == 0 {
+            return None;
+        }
+        let total: f64 = self.times[..self.len].iter().sum();
+        Some(total / self.len as f64)
+    }
+}
+
+impl Default for FrameTimes {
+    fn default() -> FrameTimes {
+        FrameTimes::new()
+    }
+}
+
+/// Returns the time between spawns at `score` on the default spawn curve, which shortens it
+/// linearly from `max_time` at a score of 0 to `min_time` at a score of 100 and holds it there
+/// after.
+///
+/// # Examples
+///
+/// ```
+/// use whack::linear_spawn_interval;
+///
+/// assert_eq!(linear_spawn_interval(1.0, 0.2, 0), 1.0);
+/// assert_eq!(linear_spawn_interval(1.0, 0.2, 50), 0.6);
+/// assert_eq!(linear_spawn_interval(1.0, 0.2, 150), 0.2);
+/// ```
+pub fn linear_spawn_interval(max_time: f64, min_time: f64, score: u32) -> f64 {
+    if score < 100 {
+        max_time - ((max_time - min_time) * (score as f64 / 100.0))
+    } else {
+        min_time
+    }
+}
+
+/// Returns the default spawn curve as a closure, see `linear_spawn_interval`.
+///
+/// # Examples
+///
+/// ```
+/// use whack::linear_spawn_curve;
+///
+/// let curve = linear_spawn_curve(1.0, 0.2);
+/// assert_eq!(curve(50), 0.6);
+/// ```
+pub fn linear_spawn_curve(max_time: f64, min_time: f64) -> Box<Fn(u32) -> f64> {
+    Box::new(move |score| linear_spawn_interval(max_time, min_time, score))
+}
+
+/// An error raised when the game window cannot be created, holding the reason given by the
+/// windowing backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowError(pub String);
+
+impl fmt::Display for WindowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "could not create the game window: {}", self.0)
+    }
+}
+
+impl Error for WindowError {
+    fn description(&self) -> &str {
+        "could not create the game window"
+    }
+}
+
+/// The window title used unless another is given to `GameBuilder::title`.
+pub const DEFAULT_TITLE: &str = "WHACK!";
+
 /// Initialises an instance of **Whack!**
+///
+/// Returns a `WindowError` if the window cannot be created, for example when there is no
+/// display available.
 pub fn run() -> Result<(), Box<Error>> {
-    const WINDOW_XY: f64 = 300.0;
-    let window: Window = WindowSettings::new("WHACK!", [WINDOW_XY as u32, WINDOW_XY as u32])
-        .exit_on_esc(true)
-        .build()
-        .unwrap();
-    let mut game = GameManager::new(WINDOW_XY, 1.0, 0.1);
-    game.start(window)
+    GameBuilder::new().run()
+}
+
+/// Configures the window that an instance of **Whack!** is run in.
+///
+/// # Examples
+///
+/// ```
+/// use whack::GameBuilder;
+///
+/// let builder = GameBuilder::new().title("Whack-a-tile");
+/// assert_eq!(builder.get_title(), "Whack-a-tile");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameBuilder {
+    title: String,
+    window_size: f64,
+}
+
+impl GameBuilder {
+    /// Returns a `GameBuilder` for a 300x300 window titled `DEFAULT_TITLE`.
+    pub fn new() -> GameBuilder {
+        GameBuilder {
+            title: DEFAULT_TITLE.to_string(),
+            window_size: 300.0,
+        }
+    }
+
+    /// Sets the title of the window.
+    pub fn title<T: Into<String>>(mut self, title: T) -> GameBuilder {
+        self.title = title.into();
+        self
+    }
+
+    /// Returns the title of the window.
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Sets the width and height of the window.
+    pub fn window_size(mut self, window_size: f64) -> GameBuilder {
+        self.window_size = window_size;
+        self
+    }
+
+    /// Returns the width and height of the window.
+    pub fn get_window_size(&self) -> f64 {
+        self.window_size
+    }
+
+    /// Creates the window and runs the game in it until the window is closed.
+    ///
+    /// Returns a `WindowError` if the window cannot be created.
+    pub fn run(&self) -> Result<(), Box<Error>> {
+        let size = self.window_size as u32;
+        let window: Window = WindowSettings::new(self.title.clone(), [size, size])
+            .exit_on_esc(true)
+            .build()
+            .map_err(WindowError)?;
+        let mut game = GameManager::new(self.window_size, 1.0, 0.1);
+        game.start(window)
+    }
+}
+
+impl Default for GameBuilder {
+    fn default() -> GameBuilder {
+        GameBuilder::new()
+    }
+}
+
+/// Tracks the tiles spawned by a `Pattern` so a bonus can be awarded if they are all cleared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternClear {
+    /// The index in `GameManager::boards` of the `Board` the pattern is on.
+    pub board: usize,
+    pub indices: Vec<usize>,
+    pub whacked: Vec<usize>,
+    pub time_left: f64,
+}
+
+impl PatternClear {
+    /// Returns a new `PatternClear` that tracks the tiles at `indices` on the first `Board` for
+    /// `window` seconds.
+    pub fn new(indices: Vec<usize>, window: f64) -> PatternClear {
+        PatternClear {
+            board: 0,
+            indices: indices,
+            whacked: Vec::new(),
+            time_left: window,
+        }
+    }
+
+    /// Records a whack at index `i`, returns true if every tile in the pattern has been whacked.
+    pub fn record_whack(&mut self, i: usize) -> bool {
+        if self.indices.contains(&i) && !self.whacked.contains(&i) {
+            self.whacked.push(i);
+        }
+        self.is_cleared()
+    }
+
+    /// True if every tile in the pattern has been whacked.
+    pub fn is_cleared(&self) -> bool {
+        self.whacked.len() == self.indices.len()
+    }
+
+    /// True if the time window for clearing the pattern has elapsed.
+    pub fn is_expired(&self) -> bool {
+        self.time_left <= 0.0
+    }
+}
+
+/// Receives notifications of things happening in a game, see `GameManager::add_observer`.
+///
+/// Tiles are identified by their id, see `gobs::Sprite::id`, and their index on the `Board`
+/// they are on. Every method does nothing by default.
+pub trait GameEvents {
+    /// Called when a tile is added.
+    fn on_tile_spawned(&mut self, _id: u64, _index: usize) {}
+
+    /// Called when a tile is added at `index`, `elapsed` seconds into the game, for example
+    /// to build a heatmap of where tiles appear.
+    fn on_spawn(&mut self, _index: usize, _elapsed: f64) {}
+
+    /// Called when a tile is whacked and removed.
+    fn on_whack(&mut self, _id: u64, _index: usize) {}
+
+    /// Called when a tile is whacked, with its `kind` and the `points` scored for it, for
+    /// example to play a different sound for each kind of tile.
+    fn on_hit(&mut self, _kind: gobs::TileKind, _points: u32) {}
+}
+
+/// A whack that can be undone in practice mode, see `GameManager::undo`.
+#[derive(Debug, Clone, PartialEq)]
+struct WhackRecord {
+    /// The tiles that were hit, with the positions they were removed from.
+    tiles: Vec<(usize, gobs::Tile)>,
+    /// The points scored by the whack, including any pattern bonus.
+    points: u32,
+    /// The length of the score log before the whack.
+    log_len: usize,
+    /// The high score before the whack.
+    high_score: u32,
+    /// The number of whacks made before this one.
+    attempts: u32,
+    /// The number of whacks that had hit a tile before this one.
+    hits: u32,
+    /// The run of hits without a miss before the whack.
+    combo: u32,
+    /// The longest run of hits without a miss before the whack.
+    max_combo: u32,
+    /// The pattern that was being tracked before the whack.
+    pattern_clear: Option<PatternClear>,
+    /// The `elapsed` time when the whack was made.
+    elapsed: f64,
+}
+
+/// A record of points being scored, see `GameManager::score_log`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScoreEvent {
+    /// The time in seconds since play started that the points were scored.
+    pub elapsed: f64,
+    pub points: u32,
+    /// The index of the whacked tile on the `Board`.
+    pub tile_index: usize,
+}
+
+/// A summary of a game, see `GameManager::summary`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GameSummary {
+    pub score: u32,
+    /// The highest score reached since the `GameManager` was created.
+    pub high_score: u32,
+    /// The fraction of whacks that hit a tile, see `GameManager::accuracy`.
+    pub accuracy: f64,
+    /// The time in seconds spent playing.
+    pub elapsed: f64,
+    /// The longest run of whacks that hit a tile without a miss.
+    pub max_combo: u32,
+}
+
+impl fmt::Display for GameSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "Score: {} (best {}), accuracy: {:.0}%, time: {:.1}s, best combo: {}",
+               self.score,
+               self.high_score,
+               self.accuracy * 100.0,
+               self.elapsed,
+               self.max_combo)
+    }
 }
 
 /// The `GameManager` struct contains data and methods to run an instance of **Whack!**
 pub struct GameManager {
     pub gl: GlGraphics,
-    pub board: gobs::Board,
+    /// The images that sprites can be drawn with, see `gobs::Sprite::texture`.
+    pub textures: TextureStore,
+    /// The font that labels are drawn in, loaded from `render::FONT_PATH` by
+    /// `GameManager::start`. Labels are not drawn without one.
+    pub glyphs: Option<GlyphCache<'static>>,
+    /// The boards side by side across the window, from left to right, see `GameManager::multi`.
+    pub boards: Vec<gobs::Board>,
+    /// The index in `boards` of the `Board` that the cursor is on.
+    cursor_board: usize,
+    /// The cursor as it is drawn, centred on `cursor_cell` unless the cursor is free.
     pub cursor: gobs::Sprite,
+    /// The index of the `Board` cell the cursor is in, see `GameManager::cursor_cell`.
+    cursor_cell: usize,
+    /// Whether the cursor can be placed anywhere by moving its sprite, rather than always
+    /// being drawn in the centre of its cell.
+    ///
+    /// Whacks with a free cursor hit the tiles its sprite overlaps, as set by
+    /// `whack_threshold`, `whack_forgiveness` and `multi_whack`, instead of the tile in its cell.
+    pub free_cursor: bool,
     pub state: GameState,
     pub score: u32,
+    /// The highest score reached since the `GameManager` was created, which survives `reset`.
+    pub high_score: u32,
+    combo: u32,
+    max_combo: u32,
     pub max_time: f64,
     pub min_time: f64,
-    pub tile_timer: f64,
+    /// Counts down to the next spawn. This was a public `f64` before the spawn timer became a
+    /// `gobs::Timer`, and is now read and set with `GameManager::time_to_next_spawn` and
+    /// `GameManager::set_time_to_next_spawn`.
+    tile_timer: gobs::Timer,
+    pub spawns: u32,
+    pub pattern_frequency: u32,
+    pub pattern_window: f64,
+    pub pattern_bonus: u32,
+    pub pattern_clear: Option<PatternClear>,
+    pub grace_period: f64,
+    pub full_time: Option<f64>,
+    /// The time in seconds at the start of play during which full boards never cost a life.
+    pub warmup: f64,
+    /// The time left, in seconds, of the current warmup.
+    warmup_timer: gobs::Timer,
+    /// The number of lives the player has at the start of a game, applied when play starts.
+    pub starting_lives: u32,
+    lives: u32,
+    pub shuffle_interval: f64,
+    pub shuffle_timer: f64,
+    pub shuffle_warned: bool,
+    /// The time in seconds spent in the `Playing` state since the last reset.
+    pub elapsed: f64,
+    score_log: Vec<ScoreEvent>,
+    /// The number of times the player has tried to whack a tile since the last reset.
+    pub attempts: u32,
+    /// The number of whacks since the last reset that hit a tile.
+    pub hits: u32,
+    /// The fraction of the cursor, or of the tile if it is smaller, that must overlap a tile
+    /// for a whack with a `free_cursor` to hit it.
+    pub whack_threshold: f64,
+    /// How far a `free_cursor` is grown on every side when testing whether a whack hits a tile.
+    ///
+    /// This only affects whacking, the cursor is drawn at its normal size.
+    pub whack_forgiveness: f64,
+    /// Whether a whack with a `free_cursor` hits every tile under it instead of only the one it
+    /// overlaps most.
+    pub multi_whack: bool,
+    /// Maps the score to the time in seconds between spawns, replacing the
+    /// `linear_spawn_interval` from `max_time` to `min_time` if it is set.
+    pub spawn_curve: Option<Box<Fn(u32) -> f64>>,
+    /// Decides how many points each hit is worth, one each by default.
+    pub scoring: Box<ScoringStrategy>,
+    /// The controller buttons that move the cursor and whack, see `map_controller_button`.
+    pub controller_button_map: ControllerButtonMap,
+    /// The keys that control the game, see `GameManager::set_keymap`.
+    keymap: KeyMap,
+    /// Whether tiles are marked with their `gobs::TileShape` so that their kinds can be told
+    /// apart without relying on colour.
+    pub accessibility_mode: bool,
+    /// Whether a key or controller button must be released before it does anything again, so
+    /// that holding it down and letting the OS repeat it doesn't whack or move over and over.
+    pub require_key_release: bool,
+    /// What happens when the cursor is moved into the edge of the `Board`, see
+    /// `GameManager::set_cursor_edge_behaviour`.
+    cursor_edge: CursorEdgeBehaviour,
+    /// Whether whacks can be taken back with `GameManager::undo`, for practising and
+    /// debugging.
+    pub practice_mode: bool,
+    /// Whether full boards cost lives and end the game, `GameMode::Classic` by default.
+    pub mode: GameMode,
+    /// The most recent whacks that hit, oldest first, kept while in `practice_mode`.
+    undo_history: Vec<WhackRecord>,
+    /// The keys and controller buttons that are currently held down.
+    held_buttons: HashSet<Button>,
+    /// Notified of tiles spawning and being whacked.
+    observers: Vec<Box<GameEvents>>,
+    /// Score popups shown after whacks, see `GameManager::labels`.
+    labels: Vec<gobs::Label>,
+    /// The time left, in seconds, on the cursor's spin after a successful whack.
+    cursor_spin: f64,
+    /// The time step, in seconds of game time, of the last update while playing.
+    last_dt: f64,
+    /// How fast game time passes compared to real time, see `GameManager::set_time_scale`.
+    time_scale: f64,
+    /// The colours the game is drawn in, see `GameManager::set_theme`.
+    theme: colours::Theme,
+    /// How long the latest frames took to draw, see `GameManager::average_fps`.
+    frame_times: FrameTimes,
+    /// The index of the last theme chosen with `GameManager::cycle_theme`.
+    theme_preset: usize,
+    /// The `Difficulty` highlighted in the menu.
+    pub selected_difficulty: Difficulty,
 }
 
 impl PartialEq for GameManager {
     fn eq(&self, other: &GameManager) -> bool {
-        (self.board == other.board) && (self.cursor == other.cursor) &&
+        (self.boards == other.boards) && (self.cursor_board == other.cursor_board) &&
+        (self.cursor == other.cursor) && (self.cursor_cell == other.cursor_cell) &&
         (self.state == other.state) && (self.score == other.score) &&
-        (self.max_time == other.max_time) && (self.tile_timer == other.tile_timer)
+        (self.max_time == other.max_time) && (self.tile_timer == other.tile_timer) &&
+        (self.spawns == other.spawns) && (self.pattern_clear == other.pattern_clear)
     }
 }
 
@@ -74,43 +666,277 @@ impl GameManager {
     ///         .unwrap();
     /// whack::GameManager::new(WINDOW_XY, 3.0, 1.0);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_size` is not positive and finite, see `GameManager::try_new`.
     pub fn new(window_size: f64, max_time: f64, min_time: f64) -> GameManager {
-        let cursor_width = window_size / 16.0;
-        let cursor_height = window_size / 16.0;
-        GameManager {
+        GameManager::multi(window_size, 1, max_time, min_time)
+    }
+
+    /// Returns a new game manager struct, or an error if `window_size` is not positive and
+    /// finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use whack::GameManager;
+    /// use whack::gobs::BoardError;
+    ///
+    /// let game = GameManager::try_new(0.0, 3.0, 1.0);
+    /// assert_eq!(game.err(), Some(BoardError::InvalidLength(0.0)));
+    /// ```
+    pub fn try_new(window_size: f64,
+                   max_time: f64,
+                   min_time: f64)
+                   -> Result<GameManager, gobs::BoardError> {
+        GameManager::try_multi(window_size, 1, max_time, min_time)
+    }
+
+    /// Returns a new game manager struct with `n_boards` boards side by side across the window.
+    ///
+    /// Tiles spawn on a randomly chosen board and the cursor moves between boards with Tab.
+    /// The game is only lost once every board is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_boards` is `0` or `window_size` is not positive and finite, see
+    /// `GameManager::try_multi`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate whack;
+    /// extern crate piston;
+    /// extern crate glutin_window;
+    ///
+    /// const WINDOW_XY: f64 = 300.0;
+    /// let window: glutin_window::GlutinWindow =
+    ///     piston::window::WindowSettings::new("WHACK!", [WINDOW_XY as u32, WINDOW_XY as u32])
+    ///         .exit_on_esc(true)
+    ///         .build()
+    ///         .unwrap();
+    /// let game = whack::GameManager::multi(WINDOW_XY, 2, 3.0, 1.0);
+    /// assert_eq!(game.boards.len(), 2);
+    /// ```
+    pub fn multi(window_size: f64, n_boards: usize, max_time: f64, min_time: f64) -> GameManager {
+        GameManager::try_multi(window_size, n_boards, max_time, min_time)
+            .unwrap_or_else(|e| panic!("invalid window: {}", e))
+    }
+
+    /// Returns a new game manager struct with `n_boards` boards side by side across the
+    /// window, or an error if `window_size` is not positive and finite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_boards` is `0`.
+    pub fn try_multi(window_size: f64,
+                     n_boards: usize,
+                     max_time: f64,
+                     min_time: f64)
+                     -> Result<GameManager, gobs::BoardError> {
+        assert!(n_boards > 0, "a game needs at least one board");
+        let board_length = window_size / n_boards as f64;
+        let top = (window_size - board_length) / 2.0;
+        let mut boards = Vec::with_capacity(n_boards);
+        for i in 0..n_boards {
+            let mut board = gobs::Board::try_from_length(board_length)
+                .map_err(|_| gobs::BoardError::InvalidLength(window_size))?;
+            board.origin = gobs::Vec2D::new(i as f64 * board_length, top);
+            boards.push(board);
+        }
+        let theme = colours::Theme::default();
+        let cursor_size = board_length / 16.0;
+        let cursor_cell = centre_cell(&boards[0]);
+        let cursor = gobs::Sprite::builder()
+            .center(boards[0].cell_center(cursor_cell))
+            .size(cursor_size, cursor_size)
+            .colour(theme.cursor)
+            .border(theme.cursor, 2.0)
+            .filled(false)
+            .layer(gobs::CURSOR_LAYER)
+            .build();
+        Ok(GameManager {
             gl: GlGraphics::new(OpenGL::V3_2),
-            board: gobs::Board::from_length(window_size),
-            cursor: gobs::Sprite::new((window_size / 2.0) - (0.5 * cursor_width),
-                                      (window_size / 2.0) - (0.5 * cursor_height),
-                                      cursor_width,
-                                      cursor_height,
-                                      colours::YELLOW),
-            state: GameState::Ready,
+            textures: TextureStore::new(),
+            glyphs: None,
+            boards: boards,
+            cursor_board: 0,
+            cursor: cursor,
+            cursor_cell: cursor_cell,
+            free_cursor: false,
+            state: GameState::Menu,
             score: 0,
             max_time: max_time,
             min_time: min_time,
-            tile_timer: 0.0,
-        }
+            tile_timer: gobs::Timer::new(0.0),
+            spawns: 0,
+            pattern_frequency: 10,
+            pattern_window: 3.0,
+            pattern_bonus: 5,
+            pattern_clear: None,
+            grace_period: 1.0,
+            full_time: None,
+            warmup: 0.0,
+            warmup_timer: gobs::Timer::new(0.0),
+            starting_lives: 1,
+            lives: 1,
+            shuffle_interval: 30.0,
+            shuffle_timer: 30.0,
+            shuffle_warned: false,
+            elapsed: 0.0,
+            score_log: Vec::new(),
+            attempts: 0,
+            hits: 0,
+            whack_threshold: 0.0,
+            whack_forgiveness: 0.0,
+            multi_whack: false,
+            spawn_curve: None,
+            scoring: Box::new(scoring::Flat),
+            controller_button_map: ControllerButtonMap::default(),
+            keymap: KeyMap::default(),
+            require_key_release: false,
+            accessibility_mode: false,
+            cursor_edge: CursorEdgeBehaviour::Clamp,
+            practice_mode: false,
+            mode: GameMode::Classic,
+            undo_history: Vec::new(),
+            held_buttons: HashSet::new(),
+            high_score: 0,
+            combo: 0,
+            max_combo: 0,
+            observers: Vec::new(),
+            labels: Vec::new(),
+            cursor_spin: 0.0,
+            last_dt: 0.0,
+            time_scale: 1.0,
+            theme: theme,
+            frame_times: FrameTimes::new(),
+            theme_preset: 0,
+            selected_difficulty: Difficulty::Normal,
+        })
     }
 
-    /// Resets the state of the `GameManager`.
+    /// Resets the state of the `GameManager`, returning to the `Menu`.
     pub fn reset(&mut self) {
-        self.board.clear_board();
-        self.cursor.pos = gobs::Vec2D {
-            x: (self.board.length / 2.0) - (0.5 * self.cursor.width),
-            y: (self.board.length / 2.0) - (0.5 * self.cursor.height),
-        };
-        self.state = GameState::Ready;
+        for board in &mut self.boards {
+            board.clear_board();
+        }
+        self.cursor_cell = centre_cell(self.board());
+        self.centre_cursor();
+        self.state = GameState::Menu;
         self.score = 0;
-        self.tile_timer = 0.0;
+        self.tile_timer.reset_with(0.0);
+        self.spawns = 0;
+        self.pattern_clear = None;
+        self.full_time = None;
+        self.lives = self.starting_lives;
+        self.shuffle_timer = self.shuffle_interval;
+        self.shuffle_warned = false;
+        self.elapsed = 0.0;
+        self.score_log.clear();
+        self.attempts = 0;
+        self.hits = 0;
+        self.combo = 0;
+        self.max_combo = 0;
+        self.labels.clear();
+        self.cursor_spin = 0.0;
+        self.last_dt = 0.0;
+        self.undo_history.clear();
+    }
+
+    /// Returns the fraction of whacks that hit a tile, or `0.0` if there have been none.
+    pub fn accuracy(&self) -> f64 {
+        if self.attempts == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / self.attempts as f64
+    }
+
+    /// Returns every scoring event since the last reset, in the order they happened.
+    ///
+    /// Whacking a tile that completes a pattern records two events, one for the tile and one
+    /// for the pattern bonus.
+    pub fn score_log(&self) -> &[ScoreEvent] {
+        &self.score_log
+    }
+
+    /// Returns the number of whacks in a row that have hit a tile.
+    pub fn combo(&self) -> u32 {
+        self.combo
+    }
+
+    /// Returns a `GameSummary` of the game so far.
+    pub fn summary(&self) -> GameSummary {
+        GameSummary {
+            score: self.score,
+            high_score: self.high_score,
+            accuracy: self.accuracy(),
+            elapsed: self.elapsed,
+            max_combo: self.max_combo,
+        }
+    }
+
+    /// Adds `points` to the score and records them in the score log.
+    fn add_score(&mut self, points: u32, tile_index: usize) {
+        self.score += points;
+        self.high_score = self.high_score.max(self.score);
+        self.score_log.push(ScoreEvent {
+            elapsed: self.elapsed,
+            points: points,
+            tile_index: tile_index,
+        });
+    }
+
+    /// Resets the state of the `GameManager` and starts playing straight away.
+    pub fn restart(&mut self) {
+        self.reset();
+        self.start_playing();
+    }
+
+    /// Moves to the `Playing` state with `starting_lives` lives, starting the `warmup`.
+    fn start_playing(&mut self) {
+        self.state = GameState::Playing;
+        self.lives = self.starting_lives;
+        self.warmup_timer.reset_with(self.warmup);
+    }
+
+    /// Returns the time left, in seconds, before full boards can cost a life.
+    pub fn warmup_remaining(&self) -> f64 {
+        self.warmup_timer.remaining()
     }
 
     /// Initialises the event loop for the game instance.
+    ///
+    /// The mouse moves the cursor and left clicks whack, see `GameManager::mouse_clicked`.
+    /// Controller buttons perform actions with `controller_button_map`. The keys bound to
+    /// `Action::ToggleFullscreen` toggle between windowed and fullscreen. Glutin cannot change
+    /// an existing window, so a replacement is built from `WindowSettings` with `fullscreen`
+    /// toggled, the OpenGL backend, textures and font are recreated for the new context and the
+    /// game is laid out again with `resize`.
+    ///
+    /// Returns an error if the font at `render::FONT_PATH` cannot be loaded.
     pub fn start(&mut self, mut window: Window) -> Result<(), Box<Error>> {
-        println!("PRESS SPACE TO START!");
+        self.glyphs = Some(GlyphCache::new(render::FONT_PATH)?);
+        if let Some(prompt) = self.prompt() {
+            println!("{}", prompt);
+        }
+        println!("{}", self.selected_difficulty);
+        let windowed_size = piston::window::Window::size(&window);
+        let title = AdvancedWindow::get_title(&window);
+        let mut fullscreen = false;
         let mut events = Events::new(EventSettings::new());
+        let mut last_frame: Option<Instant> = None;
+        let mut mouse_pos: Option<gobs::Vec2D> = None;
         while let Some(e) = events.next(&mut window) {
             if let Some(r) = e.render_args() {
+                let now = Instant::now();
+                if let Some(last) = last_frame {
+                    let frame = now - last;
+                    self.frame_times
+                        .record(frame.as_secs() as f64 + (frame.subsec_nanos() as f64 * 1e-9));
+                }
+                last_frame = Some(now);
                 self.render(&r);
             }
 
@@ -118,180 +944,3321 @@ impl GameManager {
                 self.update(&u);
             }
 
+            if let Some([width, height]) = e.resize_args() {
+                self.resize(width as f64, height as f64);
+            }
+
             if let Some(Button::Keyboard(key)) = e.press_args() {
-                self.input(key);
+                if self.keymap.is_bound(key, Action::ToggleFullscreen) {
+                    fullscreen = !fullscreen;
+                    window = WindowSettings::new(title.clone(), windowed_size)
+                        .exit_on_esc(true)
+                        .fullscreen(fullscreen)
+                        .build()
+                        .map_err(WindowError)?;
+                    self.gl = GlGraphics::new(OpenGL::V3_2);
+                    self.textures.reload()?;
+                    self.glyphs = Some(GlyphCache::new(render::FONT_PATH)?);
+                    let size = piston::window::Window::size(&window);
+                    self.resize(size.width as f64, size.height as f64);
+                }
+                self.key_press(key);
+            }
+
+            if let Some(Button::Keyboard(key)) = e.release_args() {
+                self.key_release(key);
+            }
+
+            if let Some([x, y]) = e.mouse_cursor_args() {
+                let pos = gobs::Vec2D::new(x, y);
+                self.mouse_moved(pos);
+                mouse_pos = Some(pos);
+            }
+
+            if let Some(Button::Mouse(MouseButton::Left)) = e.press_args() {
+                if let Some(pos) = mouse_pos {
+                    self.mouse_clicked(pos);
+                }
+            }
+
+            if let Some(Button::Controller(button)) = e.press_args() {
+                self.controller_press(button);
+            }
+
+            if let Some(Button::Controller(button)) = e.release_args() {
+                self.controller_release(button);
             }
         }
 
         Ok(())
     }
 
+    /// Lays the game out again to fit a window of `width` by `height`.
+    ///
+    /// The boards stay side by side in the same order, as large as will fit and centred in the
+    /// window. Tiles and the cursor keep their positions relative to their `Board`.
+    ///
+    /// A window with no area, such as one that has been minimised, has nowhere to draw the
+    /// boards, so sizes that are not positive and finite are ignored and the layout is kept
+    /// until the window is given a real size.
+    pub fn resize(&mut self, width: f64, height: f64) {
+        let valid = |l: f64| l.is_finite() && l > 0.0;
+        if !valid(width) || !valid(height) {
+            return;
+        }
+        let n_boards = self.boards.len();
+        let board_length = (width / n_boards as f64).min(height);
+        let left = (width - (board_length * n_boards as f64)) / 2.0;
+        let top = (height - board_length) / 2.0;
+        let scale = board_length / self.board().length;
+        let cursor_offset = self.cursor.pos - self.board().origin;
+        for (i, board) in self.boards.iter_mut().enumerate() {
+            board.set_geometry(gobs::Vec2D::new(left + (i as f64 * board_length), top),
+                               board_length);
+        }
+        self.cursor.pos = self.board().origin + (cursor_offset * scale);
+        self.cursor.width *= scale;
+        self.cursor.height *= scale;
+        if self.free_cursor {
+            let b = self.cursor_board();
+            self.cursor_cell = self.cursor.snap_to_grid(&self.boards[b]);
+        } else {
+            self.centre_cursor();
+        }
+    }
+
     /// Called by the event loop when a `Render` event is recieved.
+    ///
+    /// Sprites are interpolated between the last two updates using `args.ext_dt`, the time
+    /// since the last update, scaled by the `time_scale`.
     fn render(&mut self, args: &RenderArgs) {
-        let sprites = self.get_sprites();
+        let alpha = if self.last_dt > 0.0 {
+            (args.ext_dt * self.time_scale) / self.last_dt
+        } else {
+            1.0
+        };
+        let frame = self.frame(alpha);
+        let textures = &self.textures;
+        let glyphs = self.glyphs.as_mut();
         self.gl.draw(args.viewport(), |c, gl| {
-            graphics::clear(colours::BLUE, gl);
-            for sprite in sprites {
-                graphics::rectangle(sprite.colour, sprite.get_rect(), c.transform, gl);
+            let mut renderer = GlRenderer::new(c, gl, textures);
+            if let Some(glyphs) = glyphs {
+                renderer = renderer.glyphs(glyphs);
             }
+            frame.draw(&mut renderer);
         });
     }
 
-    /// Called by the event loop when an `Update` event is recieved.
-    fn update(&mut self, args: &UpdateArgs) {
-        match self.state {
-            GameState::Playing => self.playing_update(args),
-            _ => (),
-        }
+    /// Draws the game with `renderer`.
+    pub fn draw(&mut self, renderer: &mut Renderer) {
+        self.frame(1.0).draw(renderer);
     }
 
-    /// Called by `update` when the `GameState` is `Playing`.
-    fn playing_update(&mut self, args: &UpdateArgs) {
-        self.tile_timer -= args.dt;
-        if self.tile_timer < 0.0 {
-            if self.score < 100 {
-                let score_delta = (self.max_time - self.min_time) * (self.score as f64 / 100.0);
-                self.tile_timer = self.max_time - score_delta;
-            } else {
-                self.tile_timer = self.min_time;
-            }
-            println!("{}", self.tile_timer);
-            self.board.add_tile();
-        }
-        if self.board.is_full() {
-            self.state = GameState::Lose;
-            println!("You lose!");
+    /// Collects everything that needs drawing for the current frame.
+    fn frame(&mut self, alpha: f64) -> Frame {
+        let next_spawn = match self.state {
+            GameState::Playing => self.board_mut().next_spawn(),
+            _ => None,
+        };
+        let mut labels = self.labels.clone();
+        if self.state == GameState::Lose {
+            labels.push(self.summary_label());
+        }
+        Frame {
+            highlight: self.cursor_cell().map(|i| self.board().cell_rect(i)),
+            preview: next_spawn.map(|i| self.board().cell_rect(i)),
+            sprites: self.render_sprites(alpha),
+            labels: labels,
+            theme: self.theme,
         }
     }
 
-    /// Called by the event loop when an `Input` event is recieved.
-    fn input(&mut self, key: piston::input::Key) {
-        match self.state {
-            GameState::Ready => self.ready_key_press(key),
-            GameState::Playing => self.playing_key_press(key),
-            GameState::Lose => self.lose_key_press(key),
-            _ => (),
-        }
+    /// Returns a `Label` showing the `summary`, drawn over the middle of the `Board` the
+    /// cursor is on while the game is lost.
+    fn summary_label(&self) -> gobs::Label {
+        let board = self.board();
+        let pos = gobs::Vec2D::new(board.origin.x, board_centre(board).y);
+        let text = format!("You lose! {}", self.summary());
+        let mut label = gobs::Label::new(text, pos, self.theme.hud);
+        label.velocity = gobs::Vec2D::new(0.0, 0.0);
+        label
     }
 
-    /// Called by `input` when the `GameState` is `Ready`.
-    fn ready_key_press(&mut self, key: piston::input::Key) {
-        if key == Key::Space {
-            self.state = GameState::Playing;
-        }
+    /// Returns the `Board` that the cursor is on.
+    pub fn board(&self) -> &gobs::Board {
+        &self.boards[self.cursor_board()]
     }
 
-    /// Called by `input` when the `GameState` is `Playing`.
-    fn playing_key_press(&mut self, key: piston::input::Key) {
-        self.handle_movement(key);
-        self.whack(key);
+    /// Returns the `Board` that the cursor is on, mutably.
+    pub fn board_mut(&mut self) -> &mut gobs::Board {
+        let b = self.cursor_board();
+        &mut self.boards[b]
+    }
+
+    /// Returns the index in `boards` of the `Board` that the cursor is on.
+    ///
+    /// If boards have been removed from the end of `boards`, leaving nothing at the cursor's
+    /// index, the cursor is on the last `Board`.
+    pub fn cursor_board(&self) -> usize {
+        self.cursor_board.min(self.boards.len() - 1)
     }
 
-    /// Called by `input` when the `GameState` is `Lose`.
-    fn lose_key_press(&mut self, key: piston::input::Key) {
-        if key == Key::Space {
-            self.reset();
-            self.state = GameState::Ready;
+    /// Returns the index of the `Board` cell the cursor is in.
+    ///
+    /// A `free_cursor` is in the cell containing its centre, or `None` if it is off the `Board`.
+    pub fn cursor_cell(&self) -> Option<usize> {
+        if self.free_cursor {
+            self.board().index_from_pos(self.cursor.center())
+        } else {
+            Some(self.grid_cell())
         }
     }
 
-    /// Handles movement input when the
-    fn handle_movement(&mut self, key: piston::input::Key) {
-        const MOVEMENT_KEYS: [piston::input::Key; 4] = [Key::Up, Key::Down, Key::Left, Key::Right];
-        if MOVEMENT_KEYS.contains(&key) {
-            let move_dist: f64 = self.board.length / 3.0;
-            let move_vec = match key {
-                Key::Up => {
-                    gobs::Vec2D {
-                        x: 0.0,
-                        y: -move_dist,
-                    }
-                }
-                Key::Down => {
-                    gobs::Vec2D {
-                        x: 0.0,
-                        y: move_dist,
-                    }
-                }
-                Key::Right => {
-                    gobs::Vec2D {
-                        x: move_dist,
-                        y: 0.0,
-                    }
-                }
-                Key::Left => {
-                    gobs::Vec2D {
-                        x: -move_dist,
-                        y: 0.0,
-                    }
-                }
-                _ => gobs::Vec2D { x: 0.0, y: 0.0 },
-            };
-            self.cursor.pos.add(move_vec);
+    /// Returns `cursor_cell`, or the centre cell if the `Board` has been replaced by one that
+    /// is too small to have it.
+    fn grid_cell(&self) -> usize {
+        if self.cursor_cell < self.board().tiles.len() {
+            self.cursor_cell
+        } else {
+            centre_cell(self.board())
         }
     }
 
-    /// Checks if user has whacked a valid tile.
-    fn whack(&mut self, key: piston::input::Key) {
-        if key == Key::Space {
-            let overlapping: Vec<usize> = self.board
-                .tiles
-                .iter()
-                .map(|x| x.map_or(false, |y| y.is_overlapping(&self.cursor)))
-                .enumerate()
-                .filter(|x| x.1)
-                .map(|x| x.0)
-                .collect();
-            if overlapping.len() > 0 {
-                assert_eq!(overlapping.len(), 1);
-                self.board.tiles[overlapping[0]].take();
-                self.score += 1;
-                println!("{:?}", self.score);
-            } else {
-                self.board.add_tile();
-            }
+    /// Moves the cursor to the centre of cell `i` of the `Board`, if there is one.
+    pub fn set_cursor_cell(&mut self, i: usize) {
+        if i < self.board().tiles.len() {
+            self.cursor_cell = i;
+            self.centre_cursor();
         }
     }
 
-    fn get_sprites(&self) -> Vec<gobs::Sprite> {
-        // Could add tags to sprites and filter them later on
-        // Add field for layer to sprite
-        let mut sprites: Vec<gobs::Sprite> = self.board
-            .tiles
-            .iter()
-            .filter(|x| x.is_some())
-            .map(|x| x.unwrap())
-            .collect();
-        sprites.push(self.cursor);
-        sprites
+    /// Returns the index of the cell that movement starts from, the cell nearest the centre of
+    /// a `free_cursor`.
+    fn movement_cell(&self) -> usize {
+        if self.free_cursor {
+            let mut cursor = self.cursor;
+            cursor.snap_to_grid(self.board())
+        } else {
+            self.grid_cell()
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    extern crate piston;
-    extern crate glutin_window;
+    /// Puts the cursor sprite exactly in the centre of `cursor_cell`, moving it to the centre
+    /// of the `Board` first if the `Board` no longer has that cell.
+    fn centre_cursor(&mut self) {
+        self.cursor_cell = self.grid_cell();
+        let center = self.board().cell_center(self.cursor_cell);
+        self.cursor.set_center(center);
+    }
+
+    /// Sets whether the cursor stops at the edges of the `Board` or wraps around to the
+    /// opposite edge when moved into them.
+    ///
+    /// Only square boards wrap, the cursor always stops at the edges of hex boards.
+    pub fn set_cursor_edge_behaviour(&mut self, behaviour: CursorEdgeBehaviour) {
+        self.cursor_edge = behaviour;
+    }
+
+    /// Returns what happens when the cursor is moved into the edge of the `Board`.
+    pub fn cursor_edge_behaviour(&self) -> CursorEdgeBehaviour {
+        self.cursor_edge
+    }
+
+    /// Sets the keys that control the game, replacing all of the current bindings.
+    pub fn set_keymap(&mut self, keymap: KeyMap) {
+        self.keymap = keymap;
+    }
+
+    /// Returns the keys that control the game, `KeyMap::default()` unless set otherwise.
+    pub fn keymap(&self) -> &KeyMap {
+        &self.keymap
+    }
+
+    /// Returns the number of frames drawn per second, averaged over the last
+    /// `FRAME_TIME_SAMPLES` frames, or `0.0` before any have been timed.
+    pub fn average_fps(&self) -> f64 {
+        self.frame_times.average().map_or(0.0, |dt| 1.0 / dt)
+    }
+
+    /// Sets how fast game time passes compared to real time, clamped to between
+    /// `MIN_TIME_SCALE` and `MAX_TIME_SCALE`.
+    ///
+    /// A `time_scale` of `0.5` runs every timer and animation at half speed. Values that are
+    /// not a number leave the `time_scale` unchanged.
+    pub fn set_time_scale(&mut self, time_scale: f64) {
+        if !time_scale.is_nan() {
+            self.time_scale = time_scale.max(MIN_TIME_SCALE).min(MAX_TIME_SCALE);
+        }
+    }
+
+    /// Returns how fast game time passes compared to real time.
+    pub fn time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    /// Sets the colours the game is drawn in.
+    ///
+    /// Everything changes colour straight away, including the tiles already on the boards.
+    pub fn set_theme(&mut self, theme: colours::Theme) {
+        self.theme = theme;
+        for board in &mut self.boards {
+            board.set_theme(theme);
+        }
+        self.cursor.colour = theme.cursor;
+        if let Some((_, thickness)) = self.cursor.border {
+            self.cursor.border = Some((theme.cursor, thickness));
+        }
+    }
+
+    /// Returns the colours the game is drawn in.
+    pub fn theme(&self) -> &colours::Theme {
+        &self.theme
+    }
+
+    /// Sets the colour the window is cleared to, keeping the rest of the theme.
+    pub fn set_background(&mut self, background: colours::Colour) {
+        self.theme.background = background;
+    }
+
+    /// Returns the colour the window is cleared to, `colours::BLUE` by default.
+    pub fn background(&self) -> colours::Colour {
+        self.theme.background
+    }
+
+    /// Switches to the next of the `colours::Theme::presets`, wrapping from the last to the
+    /// first.
+    pub fn cycle_theme(&mut self) {
+        let presets = colours::Theme::presets();
+        self.theme_preset = (self.theme_preset + 1) % presets.len();
+        self.set_theme(presets[self.theme_preset]);
+    }
+
+    /// Called by the event loop when an `Update` event is recieved.
+    fn update(&mut self, args: &UpdateArgs) {
+        match self.state {
+            GameState::Playing => self.playing_update(args),
+            _ => (),
+        }
+    }
+
+    /// Called by `update` when the `GameState` is `Playing`.
+    ///
+    /// The time step is capped at `MAX_UPDATE_DT` and then multiplied by the `time_scale`.
+    fn playing_update(&mut self, args: &UpdateArgs) {
+        let dt = args.dt.min(MAX_UPDATE_DT) * self.time_scale;
+        self.last_dt = dt;
+        self.elapsed += dt;
+        let cursor_cell = self.cursor_cell();
+        self.board_mut().set_cursor_cell(cursor_cell);
+        self.tile_timer.tick(dt);
+        for board in &mut self.boards {
+            board.tick(dt);
+        }
+        if !self.free_cursor {
+            self.centre_cursor();
+        } else if self.cursor.is_moving() {
+            self.cursor.step(dt);
+        }
+        self.cursor_spin = (self.cursor_spin - dt).max(0.0);
+        self.warmup_timer.tick(dt);
+        self.update_pattern_clear(dt);
+        self.update_blinks(dt);
+        self.update_labels(dt);
+        self.update_shuffle(dt);
+        if self.time_to_next_spawn() < 0.0 {
+            let interval = self.spawn_interval();
+            self.tile_timer.reset_with(interval);
+            self.spawn_tiles();
+        }
+        if self.mode == GameMode::Classic {
+            self.check_full_board(dt);
+        }
+    }
+
+    /// Costs a life if every `Board` has been full for at least `grace_period` seconds.
+    ///
+    /// Nothing happens during the `warmup`, the grace period only starts once it is over.
+    ///
+    /// While lives remain the boards are cleared and play continues, otherwise the game is
+    /// lost. The tile timer is zeroed on losing so that a restart begins from a clean state.
+    fn check_full_board(&mut self, dt: f64) {
+        if !self.warmup_timer.is_finished() || !self.all_boards_full() {
+            self.full_time = None;
+            return;
+        }
+        let full_time = self.full_time.map_or(0.0, |t| t + dt);
+        if full_time < self.grace_period {
+            self.full_time = Some(full_time);
+            return;
+        }
+        self.full_time = None;
+        self.lives = self.lives.saturating_sub(1);
+        if self.lives > 0 {
+            for board in &mut self.boards {
+                board.clear_board();
+            }
+            self.pattern_clear = None;
+            println!("Lives left: {}", self.lives);
+        } else {
+            self.tile_timer.reset_with(0.0);
+            self.state = GameState::Lose;
+        }
+    }
+
+    /// Returns the number of lives the player has left in this game.
+    pub fn lives_remaining(&self) -> u32 {
+        self.lives
+    }
+
+    /// True if every `Board` in the game is full.
+    pub fn all_boards_full(&self) -> bool {
+        self.boards.iter().all(|b| b.is_full())
+    }
+
+    /// Adds tiles to randomly chosen boards that are not full, every `pattern_frequency` spawns
+    /// a whole `Pattern` is added to one of them instead.
+    ///
+    /// A `pattern_frequency` of `0` disables patterns.
+    fn spawn_tiles(&mut self) {
+        self.spawns += 1;
+        if (self.pattern_frequency > 0) && (self.spawns % self.pattern_frequency == 0) {
+            let b = match self.random_spawn_board() {
+                Some(b) => b,
+                None => return,
+            };
+            let board = &mut self.boards[b];
+            let pattern = board.random_pattern();
+            let placed = board.spawn_pattern(pattern);
+            let spawned: Vec<(u64, usize)> = placed.iter()
+                .filter_map(|&i| board.tile_id(i).map(|id| (id, i)))
+                .collect();
+            self.notify_spawned(&spawned);
+            if !placed.is_empty() {
+                let mut clear = PatternClear::new(placed, self.pattern_window);
+                clear.board = b;
+                self.pattern_clear = Some(clear);
+            }
+        } else {
+            let mut spawned = Vec::new();
+            for _ in 0..self.spawn_count() {
+                let board = match self.random_spawn_board() {
+                    Some(b) => &mut self.boards[b],
+                    None => break,
+                };
+                let added = board.add_tile();
+                spawned.extend(added.and_then(|i| board.tile_id(i).map(|id| (id, i))));
+            }
+            self.notify_spawned(&spawned);
+        }
+    }
+
+    /// Registers `observer` to be told about events in the game.
+    pub fn add_observer(&mut self, observer: Box<GameEvents>) {
+        self.observers.push(observer);
+    }
+
+    /// Tells the observers about each tile in `spawned`, given as `(id, index)`.
+    fn notify_spawned(&mut self, spawned: &[(u64, usize)]) {
+        for observer in &mut self.observers {
+            for &(id, i) in spawned {
+                observer.on_tile_spawned(id, i);
+                observer.on_spawn(i, self.elapsed);
+            }
+        }
+    }
+
+    /// Returns the time in seconds until the next spawn at the current score, see
+    /// `spawn_curve`.
+    pub fn spawn_interval(&self) -> f64 {
+        match self.spawn_curve {
+            Some(ref curve) => curve(self.score),
+            None => linear_spawn_interval(self.max_time, self.min_time, self.score),
+        }
+    }
+
+    /// Returns the index in `boards` of a randomly chosen `Board` that is not full, or `None`
+    /// if they all are.
+    fn random_spawn_board(&self) -> Option<usize> {
+        let candidates: Vec<usize> =
+            (0..self.boards.len()).filter(|&b| !self.boards[b].is_full()).collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        Some(candidates[rand::thread_rng().gen_range(0, candidates.len())])
+    }
+
+    /// Moves the cursor to the same position on the next `Board`, wrapping around from the
+    /// last to the first.
+    ///
+    /// Any pattern being tracked for a clear bonus is kept, so it can still be cleared after
+    /// coming back to its board.
+    fn switch_board(&mut self) {
+        if self.boards.len() < 2 {
+            return;
+        }
+        let offset = self.cursor.pos - self.board().origin;
+        self.board_mut().set_cursor_cell(None);
+        self.cursor_board = (self.cursor_board() + 1) % self.boards.len();
+        self.cursor.pos = self.board().origin + offset;
+        self.cursor_cell = self.cursor.snap_to_grid(&self.boards[self.cursor_board]);
+        self.undo_history.clear();
+    }
+
+    /// Returns the time in seconds until the next tiles are spawned.
+    ///
+    /// Tiles are spawned once this drops below zero, so a negative value means that a spawn
+    /// is pending and will happen on the next update.
+    pub fn time_to_next_spawn(&self) -> f64 {
+        self.tile_timer.duration - self.tile_timer.elapsed
+    }
+
+    /// Sets the time in seconds until the next tiles are spawned, after which the time between
+    /// spawns follows the `spawn_curve` again.
+    pub fn set_time_to_next_spawn(&mut self, seconds: f64) {
+        self.tile_timer.reset_with(seconds);
+    }
+
+    /// Returns the number of tiles added each time the tile timer expires.
+    ///
+    /// A single tile is added until the score reaches 50, two until it reaches 100 and three
+    /// after that.
+    pub fn spawn_count(&self) -> usize {
+        if self.score < 50 {
+            1
+        } else if self.score < 100 {
+            2
+        } else {
+            3
+        }
+    }
+
+    /// Shuffles the tiles on every `Board` each `shuffle_interval` seconds, warning the player
+    /// one second beforehand.
+    ///
+    /// A `shuffle_interval` of `0.0` or less disables shuffling.
+    fn update_shuffle(&mut self, dt: f64) {
+        if self.shuffle_interval <= 0.0 {
+            return;
+        }
+        self.shuffle_timer -= dt;
+        if (self.shuffle_timer <= 1.0) && !self.shuffle_warned {
+            println!("Shuffle incoming!");
+            self.shuffle_warned = true;
+        }
+        if self.shuffle_timer <= 0.0 {
+            for board in &mut self.boards {
+                board.shuffle();
+            }
+            self.shuffle_timer += self.shuffle_interval;
+            self.shuffle_warned = false;
+        }
+    }
+
+    /// Counts down the time left to clear the active pattern, discarding it once expired.
+    fn update_pattern_clear(&mut self, dt: f64) {
+        let expired = match self.pattern_clear {
+            Some(ref mut clear) => {
+                clear.time_left -= dt;
+                clear.is_expired()
+            }
+            None => false,
+        };
+        if expired {
+            self.pattern_clear = None;
+        }
+    }
+
+    /// Blinks the tiles that are about to expire, showing any others that were blinking.
+    ///
+    /// Only the tiles of the active pattern expire: once the pattern has fewer than
+    /// `EXPIRY_WARNING_TIME` seconds left, its tiles that are yet to be whacked blink.
+    fn update_blinks(&mut self, dt: f64) {
+        let expiring: Vec<(usize, usize)> = match self.pattern_clear {
+            Some(ref clear) if clear.time_left < EXPIRY_WARNING_TIME => {
+                clear.indices
+                    .iter()
+                    .filter(|i| !clear.whacked.contains(i))
+                    .map(|&i| (clear.board, i))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        for (b, board) in self.boards.iter_mut().enumerate() {
+            for i in 0..board.tiles.len() {
+                if let Some(tile) = board.tiles.get_mut(i) {
+                    if expiring.contains(&(b, i)) {
+                        tile.blink
+                            .get_or_insert(gobs::Blink::new(EXPIRY_BLINK_PERIOD))
+                            .tick(dt, &mut tile.sprite);
+                    } else if tile.blink.is_some() {
+                        tile.blink = None;
+                        tile.sprite.visible = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves the labels on by `dt` seconds, discarding any that have expired.
+    fn update_labels(&mut self, dt: f64) {
+        for label in &mut self.labels {
+            label.tick(dt);
+        }
+        self.labels.retain(|l| !l.is_expired());
+    }
+
+    /// Returns the score popups currently shown, oldest first.
+    pub fn labels(&self) -> &[gobs::Label] {
+        &self.labels
+    }
+
+    /// Records a whacked tile on the cursor's `Board` against the active pattern and awards the
+    /// bonus if it is cleared.
+    fn record_pattern_whack(&mut self, i: usize) {
+        let board = self.cursor_board();
+        let cleared = match self.pattern_clear {
+            Some(ref mut clear) if clear.board == board => clear.record_whack(i),
+            _ => false,
+        };
+        if cleared {
+            let bonus = self.pattern_bonus;
+            self.add_score(bonus, i);
+            self.pattern_clear = None;
+        }
+    }
+
+    /// Called by the event loop when a key is pressed, including repeats while it is held.
+    ///
+    /// If `require_key_release` is set, presses of a key that is already held are ignored.
+    fn key_press(&mut self, key: piston::input::Key) {
+        if self.press_button(Button::Keyboard(key)) {
+            self.input(key);
+        }
+    }
+
+    /// Called by the event loop when a key is released.
+    fn key_release(&mut self, key: piston::input::Key) {
+        self.held_buttons.remove(&Button::Keyboard(key));
+    }
+
+    /// Called by the event loop when a controller button is pressed, including repeats while
+    /// it is held, which are ignored in the same way as for keys.
+    fn controller_press(&mut self, button: ControllerButton) {
+        if self.press_button(Button::Controller(button)) {
+            let actions = map_controller_button(button, &self.controller_button_map);
+            self.perform_all(actions);
+        }
+    }
+
+    /// Called by the event loop when a controller button is released.
+    fn controller_release(&mut self, button: ControllerButton) {
+        self.held_buttons.remove(&Button::Controller(button));
+    }
+
+    /// Marks `button` as held down, returning whether the press should be acted on.
+    ///
+    /// A press of a button that is already held is a repeat, which is ignored if
+    /// `require_key_release` is set.
+    fn press_button(&mut self, button: Button) -> bool {
+        let repeat = !self.held_buttons.insert(button);
+        !(repeat && self.require_key_release)
+    }
+
+    /// Called by the event loop when an `Input` event is recieved.
+    ///
+    /// While `Playing`, keys go to `playing_key_press`, otherwise `key` performs the actions it
+    /// is bound to in the `keymap`.
+    fn input(&mut self, key: piston::input::Key) {
+        if self.state == GameState::Playing {
+            self.playing_key_press(key);
+        } else {
+            let actions = self.keymap.actions(key);
+            self.perform_all(actions);
+        }
+    }
+
+    /// Performs each of `actions` in turn, stopping early if one changes the `GameState` so
+    /// that, for example, starting from the menu doesn't also count as input when `Ready`.
+    fn perform_all(&mut self, actions: Vec<Action>) {
+        let state = self.state;
+        for action in actions {
+            if self.state != state {
+                break;
+            }
+            self.perform(action);
+        }
+    }
+
+    /// Performs `action` in the current `GameState`, whatever key or controller button it
+    /// came from.
+    ///
+    /// While `Playing`, the move actions only move the cursor on square boards.
+    fn perform(&mut self, action: Action) {
+        match self.state {
+            GameState::Menu => self.menu_action(action),
+            GameState::Ready => self.ready_action(action),
+            GameState::Playing => match action.offset() {
+                Some(offset) => {
+                    if self.board().layout() == gobs::BoardLayout::Square {
+                        self.move_cursor(offset);
+                    }
+                }
+                None => self.playing_action(action),
+            },
+            GameState::Lose => self.lose_action(action),
+            _ => (),
+        }
+    }
+
+    /// Returns the prompt for the current `GameState`, naming the keys that are bound in the
+    /// `keymap`, or `None` if there isn't one.
+    fn prompt(&self) -> Option<String> {
+        let name = |action| self.keymap.key_name(action).unwrap_or_else(|| "(NO KEY)".into());
+        match self.state {
+            GameState::Menu => Some(format!("CHOOSE A DIFFICULTY WITH {} AND {}, THEN PRESS {}!",
+                                            name(Action::MoveUp),
+                                            name(Action::MoveDown),
+                                            name(Action::Start))),
+            GameState::Ready => Some(format!("PRESS {} TO START, OR {} TO CHANGE THE COLOURS!",
+                                             name(Action::Start),
+                                             name(Action::CycleTheme))),
+            _ => None,
+        }
+    }
+
+    /// Called by `perform` when the `GameState` is `Menu`.
+    ///
+    /// `MoveUp` and `MoveDown` cycle through the difficulties while `Start` applies the
+    /// selected one and moves to the `Ready` state.
+    fn menu_action(&mut self, action: Action) {
+        match action {
+            Action::Start => {
+                let difficulty = self.selected_difficulty;
+                self.apply_difficulty(difficulty);
+                self.state = GameState::Ready;
+                if let Some(prompt) = self.prompt() {
+                    println!("{}", prompt);
+                }
+                return;
+            }
+            Action::MoveUp => self.selected_difficulty = self.selected_difficulty.previous(),
+            Action::MoveDown => self.selected_difficulty = self.selected_difficulty.next(),
+            _ => return,
+        }
+        println!("{}", self.selected_difficulty);
+    }
+
+    /// Sets the time between spawns to match `difficulty`.
+    pub fn apply_difficulty(&mut self, difficulty: Difficulty) {
+        let (max_time, min_time) = difficulty.tile_times();
+        self.max_time = max_time;
+        self.min_time = min_time;
+    }
+
+    /// Called by `perform` when the `GameState` is `Ready`.
+    ///
+    /// `Start` starts playing while `CycleTheme` cycles through the colour themes.
+    fn ready_action(&mut self, action: Action) {
+        match action {
+            Action::Start => self.start_playing(),
+            Action::CycleTheme => self.cycle_theme(),
+            _ => (),
+        }
+    }
+
+    /// Called by `input` when the `GameState` is `Playing`.
+    ///
+    /// On a 3x3 square `Board`, the keys bound to `Action::SelectCell`, the digits 1 to 9 by
+    /// default, move the cursor straight to a cell and whack it, see `KeyMap::selected_cell`.
+    /// Otherwise the cursor is moved with `handle_movement` and the rest of the actions bound to
+    /// `key` are performed.
+    fn playing_key_press(&mut self, key: piston::input::Key) {
+        if self.board().layout() == gobs::BoardLayout::Square {
+            let (rows, cols) = (self.board().rows(), self.board().cols());
+            if let Some(i) = self.keymap.selected_cell(key, rows, cols) {
+                if !self.board().is_wall(i) {
+                    self.set_cursor_cell(i);
+                    self.whack_cursor();
+                }
+                return;
+            }
+        }
+        self.handle_movement(key);
+        for action in self.keymap.actions(key) {
+            self.playing_action(action);
+        }
+    }
+
+    /// Called by `perform` and `playing_key_press` when the `GameState` is `Playing`.
+    ///
+    /// Handles every action but the moves, which are left to the caller.
+    fn playing_action(&mut self, action: Action) {
+        match action {
+            Action::Whack => self.whack_cursor(),
+            Action::SwitchBoard => self.switch_board(),
+            Action::Undo => {
+                self.undo();
+            }
+            _ => (),
+        }
+    }
+
+    /// Called by `perform` when the `GameState` is `Lose`.
+    ///
+    /// `Start` returns to the `Ready` state while `Restart` restarts immediately.
+    fn lose_action(&mut self, action: Action) {
+        match action {
+            Action::Start => {
+                self.reset();
+                self.state = GameState::Ready;
+            }
+            Action::Restart => self.restart(),
+            _ => (),
+        }
+    }
+
+    /// Moves the cursor to the cell under the mouse at `pos` while `Playing`.
+    ///
+    /// Nothing happens if `pos` is off the `Board` the cursor is on or over a wall.
+    pub fn mouse_moved(&mut self, pos: gobs::Vec2D) {
+        if self.state != GameState::Playing {
+            return;
+        }
+        if let Some(i) = self.board().index_from_pos(pos) {
+            if !self.board().is_wall(i) {
+                self.set_cursor_cell(i);
+            }
+        }
+    }
+
+    /// Moves the cursor to the cell under the mouse at `pos` and whacks it while `Playing`.
+    ///
+    /// Clicks off the `Board` the cursor is on or on a wall are ignored. A click on an empty
+    /// cell misses, just like a whack from the keyboard.
+    pub fn mouse_clicked(&mut self, pos: gobs::Vec2D) {
+        if self.state != GameState::Playing {
+            return;
+        }
+        match self.board().index_from_pos(pos) {
+            Some(i) if !self.board().is_wall(i) => {
+                self.set_cursor_cell(i);
+                self.whack_cursor();
+            }
+            _ => (),
+        }
+    }
+
+    /// Handles movement input when the `GameState` is `Playing`.
+    ///
+    /// Moves that would take the cursor into a wall cell are cancelled.
+    fn handle_movement(&mut self, key: piston::input::Key) {
+        match self.board().layout() {
+            gobs::BoardLayout::Square => self.square_movement(key),
+            gobs::BoardLayout::Hex { .. } => self.hex_movement(key),
+        }
+    }
+
+    /// Moves the cursor one column or row with the keys bound to the move actions, see
+    /// `GameManager::move_cursor`.
+    fn square_movement(&mut self, key: piston::input::Key) {
+        let offset = self.keymap.actions(key).iter().filter_map(|a| a.offset()).next();
+        if let Some(offset) = offset {
+            self.move_cursor(offset);
+        }
+    }
+
+    /// Moves the cursor on a square `Board` by `(d_row, d_col)` rows and columns, as given by
+    /// `Action::offset`. The cursor is kept inside the `Board` as set by the
+    /// `CursorEdgeBehaviour`.
+    fn move_cursor(&mut self, (d_row, d_col): (isize, isize)) {
+        let (rows, cols) = (self.board().rows() as isize, self.board().cols() as isize);
+        let i = self.movement_cell() as isize;
+        let (row, col) = ((i / cols) + d_row, (i % cols) + d_col);
+        let (row, col) = match self.cursor_edge {
+            CursorEdgeBehaviour::Clamp => (row.max(0).min(rows - 1), col.max(0).min(cols - 1)),
+            CursorEdgeBehaviour::Wrap => ((row + rows) % rows, (col + cols) % cols),
+        };
+        let destination = ((row * cols) + col) as usize;
+        if !self.board().is_wall(destination) {
+            self.set_cursor_cell(destination);
+        }
+    }
+
+    /// Moves the cursor to the centre of a neighbouring hex cell with Q/W/E/A/S/D, the cursor
+    /// never leaves the `Board`.
+    fn hex_movement(&mut self, key: piston::input::Key) {
+        let direction = match key {
+            Key::Q => gobs::HexDirection::NorthWest,
+            Key::W => gobs::HexDirection::North,
+            Key::E => gobs::HexDirection::NorthEast,
+            Key::A => gobs::HexDirection::SouthWest,
+            Key::S => gobs::HexDirection::South,
+            Key::D => gobs::HexDirection::SouthEast,
+            _ => return,
+        };
+        let destination = self.board().hex_neighbour(self.movement_cell(), direction);
+        if let Some(i) = destination {
+            if self.board().is_wall(i) {
+                return;
+            }
+            self.set_cursor_cell(i);
+        }
+    }
+
+    /// Checks if user has whacked a valid tile.
+    ///
+    /// The tile in the cursor's cell is hit, if there is one. With a `free_cursor`, a tile is only
+    /// hit if it overlaps the cursor, taking the cursor's `Shape` into account, and their overlap
+    /// fraction is greater than `whack_threshold`. If the cursor is over several tiles, the one it
+    /// overlaps most is hit, or the one with the lowest index if it straddles them equally, unless
+    /// `multi_whack` is set, in which case they are all hit and scored. The cursor is inflated by
+    /// `whack_forgiveness` for these tests.
+    ///
+    /// Each tile hit loses one of its `gobs::Tile::hits_required`, and is only removed and
+    /// scored once it has none left.
+    fn whack_cursor(&mut self) {
+        let mut record = WhackRecord {
+            tiles: Vec::new(),
+            points: self.score,
+            log_len: self.score_log.len(),
+            high_score: self.high_score,
+            attempts: self.attempts,
+            hits: self.hits,
+            combo: self.combo,
+            max_combo: self.max_combo,
+            pattern_clear: self.pattern_clear.clone(),
+            elapsed: self.elapsed,
+        };
+        self.attempts += 1;
+        let targets: Vec<usize> = if self.free_cursor && self.multi_whack {
+            self.whack_targets().into_iter().map(|(i, _)| i).collect()
+        } else {
+            self.would_hit().into_iter().collect()
+        };
+        if targets.is_empty() {
+            self.combo = 0;
+            let warning = self.theme.warning;
+            self.labels.push(gobs::Label::new("MISS", self.cursor.center(), warning));
+            let board = self.board_mut();
+            let spawned: Vec<(u64, usize)> = board.add_tile()
+                .and_then(|i| board.tile_id(i).map(|id| (id, i)))
+                .into_iter()
+                .collect();
+            self.notify_spawned(&spawned);
+            return;
+        }
+        self.hits += 1;
+        self.combo += 1;
+        self.max_combo = self.max_combo.max(self.combo);
+        self.cursor_spin = CURSOR_SPIN_TIME;
+        for i in targets {
+            if let Some(&tile) = self.board().tiles.get(i) {
+                record.tiles.push((i, tile));
+            }
+            if !self.board_mut().tiles.get_mut(i).map_or(true, |tile| tile.hit()) {
+                continue;
+            }
+            if let Some(tile) = self.board_mut().remove_tile(i) {
+                for observer in &mut self.observers {
+                    observer.on_whack(tile.sprite.id, i);
+                }
+                let ctx = HitContext {
+                    elapsed: self.elapsed,
+                    combo: self.combo,
+                    kind: tile.kind,
+                };
+                let points = self.scoring.points_for_hit(&ctx);
+                for observer in &mut self.observers {
+                    observer.on_hit(tile.kind, points);
+                }
+                self.add_score(points, i);
+                self.labels.push(gobs::Label::new(format!("+{}", points),
+                                                  tile.sprite.center(),
+                                                  self.theme.hud));
+            }
+            self.record_pattern_whack(i);
+        }
+        if self.practice_mode {
+            record.points = self.score - record.points;
+            if self.undo_history.len() == MAX_UNDO_HISTORY {
+                self.undo_history.remove(0);
+            }
+            self.undo_history.push(record);
+        }
+        println!("{:?}", self.score);
+    }
+
+    /// Returns the index of the tile that a whack would hit now, or `None` if it would miss,
+    /// without changing anything.
+    ///
+    /// This is the tile in the cursor's cell, or the tile a `free_cursor` overlaps most, see
+    /// `GameManager::whack_cursor`. With `multi_whack` set, a whack with a `free_cursor` would
+    /// also hit any other tiles under it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate whack;
+    /// extern crate piston;
+    /// extern crate glutin_window;
+    ///
+    /// const WINDOW_XY: f64 = 300.0;
+    /// let window: glutin_window::GlutinWindow =
+    ///     piston::window::WindowSettings::new("WHACK!", [WINDOW_XY as u32, WINDOW_XY as u32])
+    ///         .exit_on_esc(true)
+    ///         .build()
+    ///         .unwrap();
+    /// let mut game = whack::GameManager::new(WINDOW_XY, 3.0, 1.0);
+    /// assert_eq!(game.would_hit(), None);
+    /// game.board_mut().add_tile_at(4).unwrap();
+    /// assert_eq!(game.would_hit(), Some(4));
+    /// ```
+    pub fn would_hit(&self) -> Option<usize> {
+        if !self.free_cursor {
+            let i = self.grid_cell();
+            return if self.board().is_occupied(i) { Some(i) } else { None };
+        }
+        let mut best: Option<(usize, f64)> = None;
+        for t in self.whack_targets() {
+            if best.map_or(true, |b| t.1 > b.1) {
+                best = Some(t);
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// Returns the index of every tile under the cursor, inflated by `whack_forgiveness`, that
+    /// overlaps it by more than `whack_threshold`, along with their overlap fractions.
+    fn whack_targets(&self) -> Vec<(usize, f64)> {
+        let mut targets = Vec::new();
+        let cursor = self.cursor.inflated(self.whack_forgiveness);
+        for (i, sprite) in self.board().iter_tiles() {
+            if !sprite.is_overlapping(&cursor) {
+                continue;
+            }
+            let fraction = sprite.overlap_fraction(&cursor);
+            if fraction > self.whack_threshold {
+                targets.push((i, fraction));
+            }
+        }
+        targets
+    }
+
+    /// Takes back the last whack that hit, putting the tiles it hit back on the `Board` as they
+    /// were and taking away the points it scored. Returns `false` if there was nothing to undo.
+    ///
+    /// The high score, accuracy, combo and progress towards clearing a pattern go back to what
+    /// they were before the whack, although a pattern's time to be cleared keeps running.
+    ///
+    /// Whacks can only be undone in `practice_mode`, up to `MAX_UNDO_HISTORY` of them, and
+    /// not once the cursor has moved to another `Board`. A tile whose position has been
+    /// filled again since it was whacked is not put back.
+    pub fn undo(&mut self) -> bool {
+        if !self.practice_mode {
+            return false;
+        }
+        let record = match self.undo_history.pop() {
+            Some(record) => record,
+            None => return false,
+        };
+        for (i, tile) in record.tiles {
+            match self.board_mut().tiles.get_mut(i) {
+                Some(current) if current.sprite.id == tile.sprite.id => *current = tile,
+                Some(_) => (),
+                None => {
+                    self.board_mut().tiles.insert(i, tile).unwrap();
+                    self.board_mut().stop_fading(tile.sprite.id);
+                }
+            }
+        }
+        self.score = self.score.saturating_sub(record.points);
+        self.score_log.truncate(record.log_len);
+        self.high_score = record.high_score;
+        self.attempts = record.attempts;
+        self.hits = record.hits;
+        self.combo = record.combo;
+        self.max_combo = record.max_combo;
+        let since = self.elapsed - record.elapsed;
+        self.pattern_clear = record.pattern_clear
+            .map(|mut clear| {
+                clear.time_left -= since;
+                clear
+            })
+            .filter(|clear| !clear.is_expired());
+        true
+    }
+
+    /// Returns every sprite to be drawn, sorted by layer from the bottom up, as they looked
+    /// `alpha` of the way from the previous update to the latest one.
+    ///
+    /// Animations only advance in updates, by the time that has passed, so they look the same
+    /// at any frame rate. An `alpha` of `1.0` shows the latest update. Sprites on the same
+    /// layer keep the order they were collected in: walls, tiles, fading tiles and then the
+    /// cursor, which is rotated if it is spinning after a whack. Sprites that aren't `visible`
+    /// are left out. In `accessibility_mode` each tile is followed by its `gobs::TileShape`
+    /// mark.
+    pub fn render_sprites(&self, alpha: f64) -> Vec<gobs::Sprite> {
+        let rewind = self.last_dt * (1.0 - alpha.max(0.0).min(1.0));
+        let boards = &self.boards;
+        let mut sprites: Vec<gobs::Sprite> =
+            boards.iter().flat_map(|b| b.wall_sprites()).collect();
+        for (_, tile) in boards.iter().flat_map(|b| b.tiles.iter_occupied()) {
+            let sprite = tile.sprite_at(rewind);
+            sprites.push(sprite);
+            if self.accessibility_mode {
+                sprites.extend(gobs::TileShape::for_kind(tile.kind).mark(&sprite));
+            }
+        }
+        sprites.extend(boards.iter().flat_map(|b| b.fading_tiles()));
+        let mut cursor = self.cursor.translated(-(self.cursor.velocity * rewind));
+        let spin = self.cursor_spin + rewind;
+        if (self.cursor_spin > 0.0) && (spin < CURSOR_SPIN_TIME) {
+            let turned = 1.0 - (spin / CURSOR_SPIN_TIME);
+            cursor.rotation += 2.0 * std::f64::consts::PI * turned;
+        }
+        sprites.push(cursor);
+        sprites.retain(|s| s.visible);
+        sprites.sort_by_key(|s| s.layer);
+        sprites
+    }
+
+    /// Returns every sprite to be drawn as of the latest update, see
+    /// `GameManager::render_sprites`.
+    pub fn get_sprites(&self) -> Vec<gobs::Sprite> {
+        self.render_sprites(1.0)
+    }
+}
+
+/// The shapes making up a single frame of the game.
+struct Frame {
+    /// The cell the cursor is in.
+    highlight: Option<[f64; 4]>,
+    /// The cell where the next tile will be added.
+    preview: Option<[f64; 4]>,
+    sprites: Vec<gobs::Sprite>,
+    labels: Vec<gobs::Label>,
+    theme: colours::Theme,
+}
+
+impl Frame {
+    /// Draws the `Frame` with `renderer`, from back to front.
+    fn draw(&self, renderer: &mut Renderer) {
+        renderer.clear(self.theme.background);
+        if let Some(rect) = self.highlight {
+            renderer.rect(self.theme.highlight, rect);
+        }
+        if let Some(rect) = self.preview {
+            renderer.border(self.theme.preview, rect, 1.0);
+        }
+        for sprite in &self.sprites {
+            renderer.sprite(sprite);
+        }
+        for label in &self.labels {
+            renderer.label(label);
+        }
+    }
+}
+
+/// Returns the screen position of the centre of a `Board`.
+fn board_centre(board: &gobs::Board) -> gobs::Vec2D {
+    gobs::Vec2D::new(board.origin.x + (board.width() / 2.0),
+                     board.origin.y + (board.height() / 2.0))
+}
+
+/// Returns the index of the cell containing the centre of `board`, where the cursor starts.
+fn centre_cell(board: &gobs::Board) -> usize {
+    board.index_from_pos(board_centre(board)).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate piston;
+    extern crate glutin_window;
+
+    use super::*;
+
+    /// A `Renderer` that records what it is asked to draw.
+    #[derive(Debug, Default)]
+    struct RecordingRenderer {
+        clears: Vec<colours::Colour>,
+        rects: Vec<(colours::Colour, [f64; 4])>,
+        ellipses: Vec<(colours::Colour, [f64; 4])>,
+        labels: Vec<(colours::Colour, String)>,
+    }
+
+    impl Renderer for RecordingRenderer {
+        fn clear(&mut self, c: colours::Colour) {
+            self.clears.push(c);
+        }
+
+        fn rect(&mut self, c: colours::Colour, rect: [f64; 4]) {
+            self.rects.push((c, rect));
+        }
+
+        fn ellipse(&mut self, c: colours::Colour, rect: [f64; 4]) {
+            self.ellipses.push((c, rect));
+        }
+
+        fn label(&mut self, label: &gobs::Label) {
+            self.labels.push((label.faded_colour(), label.text.clone()));
+        }
+    }
+
+    #[test]
+    fn draw_rotated_sprites() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(0).unwrap();
+        game.boards[0].add_tile_at(4).unwrap();
+        let mut plain = RecordingRenderer::default();
+        game.draw(&mut plain);
+        for &(i, angle) in &[(0, 1.0), (4, -std::f64::consts::PI)] {
+            game.boards[0].tiles.get_mut(i).unwrap().sprite.rotation = angle;
+        }
+        game.cursor.rotation = 0.5;
+        let mut rotated = RecordingRenderer::default();
+        game.draw(&mut rotated);
+        assert_eq!(rotated.rects, plain.rects);
+    }
+
+    fn make_manager() -> GameManager {
+        const WINDOW_XY: f64 = 300.0;
+        let window: glutin_window::GlutinWindow =
+            piston::window::WindowSettings::new(DEFAULT_TITLE,
+                                                [WINDOW_XY as u32, WINDOW_XY as u32])
+                .exit_on_esc(true)
+                .build()
+                .unwrap();
+        GameManager::new(WINDOW_XY, 3.0, 1.0)
+    }
+
+    #[test]
+    fn copy_game_state() {
+        use std::collections::HashSet;
+
+        let state = GameState::Playing;
+        let copied = state;
+        assert_eq!(state, copied);
+        let mut seen = HashSet::new();
+        seen.insert(state);
+        seen.insert(copied);
+        seen.insert(GameState::Lose);
+        assert_eq!(seen.len(), 2);
+        assert!(seen.contains(&GameState::Playing));
+        assert!(!seen.contains(&GameState::Ready));
+    }
+
+    // Window creation fails for real when there is no display, e.g. running `cargo run` with
+    // `DISPLAY` unset on Linux, which `main` reports as an application error.
+    #[test]
+    fn window_error_into_box_error() {
+        fn build() -> Result<(), String> {
+            Err("no display".to_string())
+        }
+        fn run_fallible() -> Result<(), Box<Error>> {
+            build().map_err(WindowError)?;
+            Ok(())
+        }
+        let e = run_fallible().unwrap_err();
+        assert_eq!(e.to_string(), "could not create the game window: no display");
+    }
+
+    #[test]
+    fn builder_title() {
+        let builder = GameBuilder::new();
+        assert_eq!(builder.get_title(), DEFAULT_TITLE);
+        assert_eq!(builder.get_window_size(), 300.0);
+        let builder = builder.title(String::from("Tile Smasher")).window_size(600.0);
+        assert_eq!(builder.get_title(), "Tile Smasher");
+        assert_eq!(builder.get_window_size(), 600.0);
+    }
+
+    #[test]
+    fn display_game_state() {
+        assert_eq!(GameState::Ready.to_string(), "Ready");
+        assert_eq!(GameState::Playing.to_string(), "Playing");
+        assert_eq!(GameState::Win.to_string(), "You Win!");
+        assert_eq!(GameState::Lose.to_string(), "Game Over");
+    }
+
+    #[test]
+    fn get_sprites() {
+        let mut game = make_manager();
+        let sprites = game.get_sprites();
+        assert_eq!(sprites.len(), 1);
+        game.boards[0].add_tile();
+        let sprites = game.get_sprites();
+        assert_eq!(sprites.len(), 2);
+    }
+
+    #[test]
+    fn get_sprites_sorted_by_layer() {
+        let mut game = make_manager();
+        for &i in &[0, 1, 2, 3] {
+            game.boards[0].add_tile_at(i).unwrap();
+        }
+        game.boards[0].tiles.get_mut(0).unwrap().sprite.layer = 20;
+        game.boards[0].tiles.get_mut(2).unwrap().sprite.layer = -1;
+        let layers: Vec<i8> = game.get_sprites().iter().map(|s| s.layer).collect();
+        assert_eq!(layers, vec![-1, 0, 0, gobs::CURSOR_LAYER, 20]);
+        let sprites = game.get_sprites();
+        assert_eq!(sprites[1].pos, gobs::Vec2D::new(100.0, 0.0));
+        assert_eq!(sprites[2].pos, gobs::Vec2D::new(0.0, 100.0));
+        assert_eq!(sprites[3], game.cursor);
+        assert_eq!(sprites[4].pos, gobs::Vec2D::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn accessibility_mode_marks_pattern_tiles() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(0).unwrap();
+        game.boards[0].spawn_pattern(gobs::Pattern::Row(2));
+        assert_eq!(game.render_sprites(1.0).len(), 5);
+        let mut plain = RecordingRenderer::default();
+        game.draw(&mut plain);
+
+        game.accessibility_mode = true;
+        let sprites = game.render_sprites(1.0);
+        assert_eq!(sprites.len(), 8);
+        let mark = sprites[2];
+        assert_eq!(mark.colour, game.theme().tile_border);
+        assert_eq!(mark.center(), game.boards[0].cell_center(6));
+        assert!(mark.width < game.boards[0].cell_size());
+        let mut marked = RecordingRenderer::default();
+        game.draw(&mut marked);
+        assert_eq!(marked.rects.len(), plain.rects.len() + 3);
+    }
+
+    #[test]
+    fn cursor_drawn_above_tiles() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(4).unwrap();
+        game.whack_cursor();
+        game.boards[0].add_tile_at(4).unwrap();
+        assert_eq!(game.get_sprites().len(), 3);
+        assert_eq!(game.get_sprites().last(), Some(&game.cursor));
+    }
+
+    #[test]
+    fn render_smoke() {
+        let mut game = make_manager();
+        let args = RenderArgs {
+            ext_dt: 0.016,
+            width: 300,
+            height: 300,
+            draw_width: 300,
+            draw_height: 300,
+        };
+        game.render(&args);
+        game.state = GameState::Playing;
+        game.boards[0].add_tile();
+        game.render(&args);
+        game.cursor.pos.x = -100.0;
+        game.render(&args);
+    }
+
+    #[test]
+    fn resize_layout() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(2).unwrap();
+        game.handle_movement(Key::Right);
+        game.resize(800.0, 600.0);
+        assert_eq!(game.boards[0].origin, gobs::Vec2D::new(100.0, 0.0));
+        assert_eq!(game.boards[0].length, 600.0);
+        assert_eq!(game.boards[0].tiles[2].unwrap().sprite.get_rect(),
+                   game.boards[0].cell_rect(2));
+        assert_eq!(game.cursor_cell(), Some(5));
+        assert_eq!(game.cursor.width, 600.0 / 16.0);
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(5));
+    }
+
+    #[test]
+    fn invalid_window_sizes() {
+        use std::f64;
+
+        for &size in &[0.0, -300.0, f64::INFINITY] {
+            assert_eq!(GameManager::try_new(size, 1.0, 0.1).err(),
+                       Some(gobs::BoardError::InvalidLength(size)));
+        }
+        assert!(GameManager::try_new(f64::NAN, 1.0, 0.1).is_err());
+        assert_eq!(GameManager::try_multi(-30.0, 3, 1.0, 0.1).err(),
+                   Some(gobs::BoardError::InvalidLength(-30.0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid window")]
+    fn new_with_zero_window_panics() {
+        GameManager::new(0.0, 1.0, 0.1);
+    }
+
+    #[test]
+    fn resize_snaps_cursor() {
+        let mut game = make_manager();
+        game.free_cursor = true;
+        game.cursor.pos.x += 40.0;
+        game.cursor.pos.y -= 20.0;
+        game.resize(600.0, 600.0);
+        assert_eq!(game.cursor_cell(), Some(4));
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(4));
+    }
+
+    #[test]
+    fn movement_snaps_cursor() {
+        let mut game = make_manager();
+        game.boards[0] = gobs::Board::with_grid(game.boards[0].length, 4);
+        game.set_cursor_cell(centre_cell(&game.boards[0]));
+        game.handle_movement(Key::Left);
+        assert_eq!(game.cursor_cell(), Some(9));
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(9));
+        game.handle_movement(Key::Up);
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(5));
+    }
+
+    #[test]
+    fn resize_to_nothing() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(2).unwrap();
+        let layout = |game: &GameManager| {
+            let board = &game.boards[0];
+            (board.origin, board.length, board.tiles[2].unwrap(), game.cursor)
+        };
+        let before = layout(&game);
+        for &(width, height) in &[(0.0, 0.0), (-100.0, 300.0), (300.0, 0.0), (1.0 / 0.0, 5.0)] {
+            game.resize(width, height);
+            assert_eq!(layout(&game), before);
+        }
+        game.resize(600.0, 600.0);
+        for i in 0..9 {
+            assert!(game.boards[0].x_from_index(i).is_finite());
+            assert!(game.boards[0].y_from_index(i).is_finite());
+        }
+        assert_eq!(game.boards[0].x_from_index(2), 400.0);
+        assert_eq!(game.boards[0].tiles[2].unwrap().sprite.get_rect(),
+                   game.boards[0].cell_rect(2));
+        assert_eq!(game.cursor.width, 600.0 / 16.0);
+    }
+
+    #[test]
+    fn resize_multi_board_layout() {
+        let mut game = make_multi_manager(2);
+        game.switch_board();
+        game.resize(400.0, 100.0);
+        assert_eq!(game.cursor_board(), 1);
+        assert_eq!(game.boards[0].origin, gobs::Vec2D::new(100.0, 0.0));
+        assert_eq!(game.boards[1].origin, gobs::Vec2D::new(200.0, 0.0));
+        assert_eq!(game.cursor_cell(), Some(4));
+    }
+
+    #[test]
+    fn draw_known_board() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(0).unwrap();
+        game.boards[0].add_tile_at(8).unwrap();
+        let mut renderer = RecordingRenderer::default();
+        game.draw(&mut renderer);
+        assert_eq!(renderer.clears, vec![colours::BLUE]);
+        assert_eq!(renderer.rects.len(), 15);
+        assert_eq!(renderer.rects[0].1, [100.0, 100.0, 100.0, 100.0]);
+        assert_eq!(renderer.rects[1], (colours::RED, [0.0, 0.0, 100.0, 100.0]));
+        assert_eq!(renderer.rects[6], (colours::RED, [200.0, 200.0, 100.0, 100.0]));
+        let tile_border = colours::darken(colours::RED, 0.5);
+        assert!(renderer.rects[2..6].iter().all(|r| r.0 == tile_border));
+        let cursor = game.cursor.inflated(-1.0).get_rect();
+        let cursor_edges: Vec<(colours::Colour, [f64; 4])> =
+            render::border_rects(cursor, 1.0).iter().map(|&r| (colours::YELLOW, r)).collect();
+        assert_eq!(renderer.rects[11..].to_vec(), cursor_edges);
+    }
+
+    #[test]
+    fn hollow_sprites() {
+        let mut renderer = RecordingRenderer::default();
+        let mut sprite = gobs::Sprite::builder()
+            .size(10.0, 10.0)
+            .colour(colours::RED)
+            .border(colours::BLACK, 2.0)
+            .build();
+        renderer.sprite(&sprite);
+        assert_eq!(renderer.rects.len(), 5);
+        sprite.filled = false;
+        renderer.sprite(&sprite);
+        assert_eq!(renderer.rects.len(), 9);
+        assert!(renderer.rects[5..].iter().all(|r| r.0 == colours::BLACK));
+        assert_eq!(renderer.rects[5].1, [0.0, 0.0, 10.0, 2.0]);
+        sprite.border = None;
+        renderer.sprite(&sprite);
+        assert_eq!(renderer.rects.len(), 9);
+    }
+
+    #[test]
+    fn draw_circular_cursor() {
+        let mut game = make_manager();
+        game.cursor = gobs::Sprite::circle(gobs::Vec2D::new(150.0, 150.0), 10.0, colours::YELLOW);
+        let mut renderer = RecordingRenderer::default();
+        game.draw(&mut renderer);
+        assert_eq!(renderer.rects.len(), 1);
+        assert_eq!(renderer.ellipses, vec![(colours::YELLOW, [140.0, 140.0, 20.0, 20.0])]);
+    }
+
+    #[test]
+    fn draw_spawn_preview() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.free_cursor = true;
+        game.cursor.pos.x = -100.0;
+        let mut renderer = RecordingRenderer::default();
+        game.draw(&mut renderer);
+        let i = game.boards[0].next_spawn().unwrap();
+        let rect = game.boards[0].cell_rect(i);
+        assert_eq!(renderer.rects.len(), 8);
+        assert_eq!(renderer.rects[0].1, [rect[0] - 1.0, rect[1] - 1.0, rect[2] + 2.0, 2.0]);
+        assert_eq!(renderer.rects[3].1,
+                   [rect[0] + rect[2] - 1.0, rect[1] + 1.0, 2.0, rect[3] - 2.0]);
+    }
+
+    #[test]
+    fn cursor_cell() {
+        let mut game = make_manager();
+        assert_eq!(game.cursor_cell(), Some(4));
+        game.set_cursor_cell(7);
+        assert_eq!(game.cursor_cell(), Some(7));
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(7));
+        game.set_cursor_cell(9);
+        assert_eq!(game.cursor_cell(), Some(7));
+        game.cursor.pos = gobs::Vec2D::new(-50.0, 150.0);
+        assert_eq!(game.cursor_cell(), Some(7));
+    }
+
+    #[test]
+    fn cursor_whacks_its_cell() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(4).unwrap();
+        game.boards[0].add_tile_at(5).unwrap();
+        game.multi_whack = true;
+        game.whack_forgiveness = 100.0;
+        game.cursor.pos.x += 60.0;
+        assert_eq!(game.would_hit(), Some(4));
+        game.whack_cursor();
+        assert!(!game.boards[0].is_occupied(4));
+        assert!(game.boards[0].is_occupied(5));
+        game.whack_cursor();
+        assert_eq!(game.hits, 1);
+        game.handle_movement(Key::Right);
+        game.whack_cursor();
+        assert!(!game.boards[0].is_occupied(5));
+        assert_eq!(game.hits, 2);
+    }
+
+    #[test]
+    fn cursor_centred_every_update() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.handle_movement(Key::Up);
+        game.cursor.pos += gobs::Vec2D::new(35.0, -12.5);
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.cursor_cell(), Some(1));
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(1));
+    }
+
+    #[test]
+    fn reset_centres_cursor_cell() {
+        let mut game = make_manager();
+        game.handle_movement(Key::Down);
+        game.handle_movement(Key::Right);
+        assert_eq!(game.cursor_cell(), Some(8));
+        game.reset();
+        assert_eq!(game.cursor_cell(), Some(4));
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(4));
+    }
+
+    #[test]
+    fn replaced_board_recentres_cursor() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.set_cursor_cell(8);
+        game.boards[0] = gobs::Board::hex(1, 300.0);
+        assert_eq!(game.cursor_cell(), Some(3));
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(3));
+        let mut game = make_multi_manager(2);
+        game.state = GameState::Playing;
+        game.switch_board();
+        game.boards.truncate(1);
+        assert_eq!(game.cursor_board(), 0);
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(4));
+    }
+
+    #[test]
+    fn free_cursor_cell() {
+        let mut game = make_manager();
+        game.free_cursor = true;
+        assert_eq!(game.cursor_cell(), Some(4));
+        game.cursor.pos = gobs::Vec2D::new(10.0, 10.0);
+        assert_eq!(game.cursor_cell(), Some(0));
+        game.cursor.pos = gobs::Vec2D::new(260.0, 110.0);
+        assert_eq!(game.cursor_cell(), Some(5));
+        game.cursor.pos = gobs::Vec2D::new(-50.0, 150.0);
+        assert_eq!(game.cursor_cell(), None);
+        game.cursor.pos = gobs::Vec2D::new(150.0, 350.0);
+        assert_eq!(game.cursor_cell(), None);
+    }
+
+    #[test]
+    fn average_fps() {
+        let mut game = make_manager();
+        assert_eq!(game.average_fps(), 0.0);
+        game.frame_times.record(0.02);
+        assert_eq!(game.average_fps(), 50.0);
+        for &dt in &[0.01, 0.03, 0.0, -1.0, f64::NAN] {
+            game.frame_times.record(dt);
+        }
+        assert!((game.average_fps() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn frame_times_keep_latest() {
+        let mut frame_times = FrameTimes::new();
+        for _ in 0..FRAME_TIME_SAMPLES {
+            frame_times.record(0.1);
+        }
+        assert!((frame_times.average().unwrap() - 0.1).abs() < 1e-12);
+        for i in 0..FRAME_TIME_SAMPLES {
+            frame_times.record(0.025);
+            let (old, new) = ((FRAME_TIME_SAMPLES - i - 1) as f64, (i + 1) as f64);
+            let expected = ((old * 0.1) + (new * 0.025)) / FRAME_TIME_SAMPLES as f64;
+            assert!((frame_times.average().unwrap() - expected).abs() < 1e-12);
+        }
+        assert!((1.0 / frame_times.average().unwrap() - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn huge_dt_is_capped() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.pattern_frequency = 0;
+        game.tile_timer.reset_with(0.5);
+        game.update(&UpdateArgs { dt: 1000.0 });
+        assert_eq!(game.boards[0].free_positions().len(), 9);
+        assert_eq!(game.tile_timer.remaining(), 0.25);
+        for _ in 0..3 {
+            let free_before = game.boards[0].free_positions().len();
+            game.update(&UpdateArgs { dt: 1000.0 });
+            assert!(free_before - game.boards[0].free_positions().len() <= 1);
+        }
+    }
+
+    #[test]
+    fn spawn_count_grows_with_score() {
+        let mut game = make_manager();
+        game.pattern_frequency = 0;
+        assert_eq!(game.spawn_count(), 1);
+        game.spawn_tiles();
+        assert_eq!(game.boards[0].free_positions().len(), 8);
+        game.score = 50;
+        assert_eq!(game.spawn_count(), 2);
+        game.spawn_tiles();
+        assert_eq!(game.boards[0].free_positions().len(), 6);
+        game.score = 150;
+        assert_eq!(game.spawn_count(), 3);
+        game.spawn_tiles();
+        assert_eq!(game.boards[0].free_positions().len(), 3);
+        game.spawn_tiles();
+        assert!(game.boards[0].is_full());
+        game.spawn_tiles();
+        assert!(game.boards[0].is_full());
+    }
+
+    fn fill_board(game: &mut GameManager) {
+        for _ in 0..9 {
+            game.boards[0].add_tile();
+        }
+    }
+
+    #[test]
+    fn grace_period_loss() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.grace_period = 0.5;
+        fill_board(&mut game);
+        game.update(&UpdateArgs { dt: 0.1 });
+        game.update(&UpdateArgs { dt: 0.2 });
+        assert_eq!(game.state, GameState::Playing);
+        game.update(&UpdateArgs { dt: 0.2 });
+        assert_eq!(game.state, GameState::Playing);
+        game.update(&UpdateArgs { dt: 0.2 });
+        assert_eq!(game.state, GameState::Lose);
+    }
+
+    #[test]
+    fn endless_mode_never_lost() {
+        let mut game = make_manager();
+        game.mode = GameMode::Endless;
+        game.restart();
+        game.grace_period = 0.0;
+        game.tile_timer.reset_with(100.0);
+        fill_board(&mut game);
+        for _ in 0..40 {
+            game.update(&UpdateArgs { dt: 0.25 });
+        }
+        assert_eq!(game.state, GameState::Playing);
+        assert_eq!(game.lives_remaining(), 1);
+        assert_eq!(game.full_time, None);
+        whack_index(&mut game, 4);
+        assert!(!game.boards[0].is_occupied(4));
+        game.tile_timer.reset_with(0.0);
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert!(game.all_boards_full());
+        game.mode = GameMode::Classic;
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.state, GameState::Lose);
+    }
+
+    #[test]
+    fn warmup_prevents_loss() {
+        let mut game = make_manager();
+        game.warmup = 1.0;
+        game.grace_period = 0.0;
+        game.state = GameState::Ready;
+        game.input(Key::Space);
+        assert_eq!(game.warmup_remaining(), 1.0);
+        game.tile_timer.reset_with(100.0);
+        fill_board(&mut game);
+        for _ in 0..3 {
+            game.update(&UpdateArgs { dt: 0.25 });
+            assert_eq!(game.state, GameState::Playing);
+        }
+        assert_eq!(game.warmup_remaining(), 0.25);
+        game.update(&UpdateArgs { dt: 0.25 });
+        assert_eq!(game.state, GameState::Lose);
+
+        game.restart();
+        assert_eq!(game.warmup_remaining(), 1.0);
+    }
+
+    #[test]
+    fn grace_period_whack_avoids_loss() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.grace_period = 0.5;
+        fill_board(&mut game);
+        game.update(&UpdateArgs { dt: 0.1 });
+        game.update(&UpdateArgs { dt: 0.25 });
+        whack_index(&mut game, 4);
+        game.update(&UpdateArgs { dt: 0.25 });
+        assert_eq!(game.state, GameState::Playing);
+        assert!(game.full_time.is_none());
+        game.boards[0].add_tile();
+        game.update(&UpdateArgs { dt: 0.25 });
+        game.update(&UpdateArgs { dt: 0.2 });
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn full_board_costs_a_life() {
+        let mut game = make_manager();
+        game.starting_lives = 3;
+        game.restart();
+        assert_eq!(game.lives_remaining(), 3);
+        game.tile_timer.reset_with(100.0);
+        game.grace_period = 0.0;
+        for lives in (1..3).rev() {
+            fill_board(&mut game);
+            game.update(&UpdateArgs { dt: 0.1 });
+            assert_eq!(game.state, GameState::Playing);
+            assert_eq!(game.lives_remaining(), lives);
+            assert_eq!(game.boards[0].free_positions().len(), 9);
+        }
+        fill_board(&mut game);
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.state, GameState::Lose);
+        assert_eq!(game.lives_remaining(), 0);
+        game.restart();
+        assert_eq!(game.lives_remaining(), 3);
+    }
+
+    #[test]
+    fn first_game_has_starting_lives() {
+        let mut game = make_manager();
+        game.starting_lives = 3;
+        game.input(Key::Space);
+        game.input(Key::Space);
+        assert_eq!(game.state, GameState::Playing);
+        assert_eq!(game.lives_remaining(), 3);
+    }
+
+    #[test]
+    fn zero_grace_period_loses_immediately() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.grace_period = 0.0;
+        fill_board(&mut game);
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.state, GameState::Lose);
+    }
+
+    fn make_multi_manager(n_boards: usize) -> GameManager {
+        const WINDOW_XY: f64 = 300.0;
+        let window: glutin_window::GlutinWindow =
+            piston::window::WindowSettings::new(DEFAULT_TITLE,
+                                                [WINDOW_XY as u32, WINDOW_XY as u32])
+                .exit_on_esc(true)
+                .build()
+                .unwrap();
+        GameManager::multi(WINDOW_XY, n_boards, 3.0, 1.0)
+    }
+
+    #[test]
+    fn multi_board_layout() {
+        let game = make_multi_manager(3);
+        assert_eq!(game.boards.len(), 3);
+        assert_eq!(game.boards[0].length, 100.0);
+        assert_eq!(game.boards[0].origin, gobs::Vec2D::new(0.0, 100.0));
+        assert_eq!(game.boards[2].origin, gobs::Vec2D::new(200.0, 100.0));
+        assert_eq!(game.cursor_cell(), Some(4));
+    }
+
+    #[test]
+    fn multi_board_spawn_distribution() {
+        let mut game = make_multi_manager(3);
+        game.pattern_frequency = 0;
+        let mut counts = [0; 3];
+        for _ in 0..300 {
+            game.spawn_tiles();
+            for (count, board) in counts.iter_mut().zip(&game.boards) {
+                *count += 9 - board.free_positions().len();
+            }
+            game.reset();
+        }
+        assert_eq!(counts.iter().sum::<usize>(), 300);
+        assert!(counts.iter().all(|&c| c > 50));
+        for _ in 0..26 {
+            game.spawn_tiles();
+        }
+        assert!(!game.all_boards_full());
+        game.spawn_tiles();
+        assert!(game.all_boards_full());
+    }
+
+    #[test]
+    fn multi_board_whack_searches_cursor_board() {
+        let mut game = make_multi_manager(2);
+        game.boards[1].add_tile_at(4).unwrap();
+        game.whack_cursor();
+        assert_eq!(game.score, 0);
+        assert!(game.boards[1].tiles[4].is_some());
+        game.boards[0].clear_board();
+        game.playing_key_press(Key::Tab);
+        assert_eq!(game.cursor_board(), 1);
+        assert_eq!(game.cursor_cell(), Some(4));
+        assert_eq!(game.board().origin.x, 150.0);
+        game.whack_cursor();
+        assert_eq!(game.score, 1);
+        assert!(game.boards[1].tiles[4].is_none());
+        game.playing_key_press(Key::Tab);
+        assert_eq!(game.cursor_board(), 0);
+    }
+
+    #[test]
+    fn multi_board_loss_requires_all_full() {
+        let mut game = make_multi_manager(2);
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.grace_period = 0.0;
+        fill_board(&mut game);
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.state, GameState::Playing);
+        for _ in 0..9 {
+            game.boards[1].add_tile();
+        }
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.state, GameState::Lose);
+    }
+
+    #[test]
+    fn multi_board_patterns() {
+        let mut game = make_multi_manager(3);
+        game.pattern_frequency = 1;
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            game.spawn_tiles();
+            let b = game.pattern_clear.as_ref().unwrap().board;
+            assert!(game.boards[b].free_positions().len() < 9);
+            seen[b] = true;
+            game.reset();
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn pattern_bonus_on_other_board() {
+        let mut game = make_multi_manager(2);
+        let placed = game.boards[1].spawn_pattern(gobs::Pattern::Row(1));
+        let mut clear = PatternClear::new(placed, 3.0);
+        clear.board = 1;
+        game.pattern_clear = Some(clear);
+        game.boards[0].add_tile_at(3).unwrap();
+        whack_index(&mut game, 3);
+        assert!(game.pattern_clear.as_ref().unwrap().whacked.is_empty());
+        game.switch_board();
+        for &i in &[3, 4, 5] {
+            whack_index(&mut game, i);
+        }
+        assert_eq!(game.score, 4 + game.pattern_bonus);
+        assert!(game.pattern_clear.is_none());
+    }
+
+    #[test]
+    fn time_to_next_spawn() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        assert_eq!(game.time_to_next_spawn(), 0.0);
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.time_to_next_spawn(), 3.0);
+        game.update(&UpdateArgs { dt: 0.25 });
+        game.update(&UpdateArgs { dt: 0.25 });
+        assert_eq!(game.time_to_next_spawn(), 2.5);
+        game.set_time_to_next_spawn(-1.0);
+        assert_eq!(game.time_to_next_spawn(), -1.0);
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert_eq!(game.time_to_next_spawn(), 3.0);
+    }
+
+    #[test]
+    fn spawn_waits_for_timer_to_pass_zero() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.set_time_to_next_spawn(0.25);
+        game.update(&UpdateArgs { dt: 0.25 });
+        assert_eq!(game.time_to_next_spawn(), 0.0);
+        assert_eq!(game.spawns, 0);
+        game.update(&UpdateArgs { dt: 0.125 });
+        assert_eq!(game.spawns, 1);
+    }
+
+    #[test]
+    fn shuffle_timer() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.shuffle_timer = 1.2;
+        game.boards[0].add_tile();
+        game.boards[0].add_tile();
+        let cursor = game.cursor;
+        let before = game.boards[0].free_positions();
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert!(!game.shuffle_warned);
+        game.update(&UpdateArgs { dt: 0.2 });
+        assert!(game.shuffle_warned);
+        assert_eq!(game.boards[0].free_positions(), before);
+        for _ in 0..4 {
+            game.update(&UpdateArgs { dt: 0.25 });
+        }
+        assert!(!game.shuffle_warned);
+        assert_eq!(game.boards[0].free_positions().len(), 7);
+        assert!(game.boards[0].free_positions() != before);
+        assert_eq!(game.cursor, cursor);
+        assert!(game.shuffle_timer > 29.0);
+    }
+
+    fn whack_index(game: &mut GameManager, i: usize) {
+        game.set_cursor_cell(i);
+        game.whack_cursor();
+    }
+
+    #[test]
+    fn pattern_spawn_frequency() {
+        let mut game = make_manager();
+        game.pattern_frequency = 3;
+        game.spawn_tiles();
+        game.spawn_tiles();
+        assert_eq!(game.boards[0].free_positions().len(), 7);
+        assert!(game.pattern_clear.is_none());
+        game.spawn_tiles();
+        assert!(game.pattern_clear.is_some());
+        game.pattern_frequency = 0;
+        for _ in 0..3 {
+            game.spawn_tiles();
+        }
+        assert!(game.pattern_clear.is_some());
+    }
+
+    #[test]
+    fn pattern_bonus_within_window() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        let placed = game.boards[0].spawn_pattern(gobs::Pattern::Column(0));
+        game.pattern_clear = Some(PatternClear::new(placed, 3.0));
+        whack_index(&mut game, 0);
+        whack_index(&mut game, 3);
+        game.update(&UpdateArgs { dt: 1.0 });
+        assert_eq!(game.score, 2);
+        whack_index(&mut game, 6);
+        assert_eq!(game.score, 3 + game.pattern_bonus);
+        assert!(game.pattern_clear.is_none());
+    }
+
+    #[test]
+    fn pattern_bonus_expires() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        let placed = game.boards[0].spawn_pattern(gobs::Pattern::Row(2));
+        game.pattern_clear = Some(PatternClear::new(placed, 3.0));
+        whack_index(&mut game, 6);
+        whack_index(&mut game, 7);
+        for _ in 0..15 {
+            game.update(&UpdateArgs { dt: 0.25 });
+        }
+        assert!(game.pattern_clear.is_none());
+        whack_index(&mut game, 8);
+        assert_eq!(game.score, 3);
+    }
+
+    #[test]
+    fn expiring_pattern_tiles_blink() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(0).unwrap();
+        let placed = game.boards[0].spawn_pattern(gobs::Pattern::Row(2));
+        game.pattern_clear = Some(PatternClear::new(placed, 1.4));
+        whack_index(&mut game, 6);
+        game.update(&UpdateArgs { dt: 0.25 });
+        assert!(game.boards[0].tiles[7].unwrap().blink.is_none());
+        game.update(&UpdateArgs { dt: 0.25 });
+        let tile = game.boards[0].tiles[7].unwrap();
+        assert_eq!(tile.blink.map(|b| b.t), Some(0.25));
+        assert!(tile.sprite.visible);
+        assert!(game.boards[0].tiles[0].unwrap().blink.is_none());
+        let shown = game.render_sprites(1.0).len();
+        game.update(&UpdateArgs { dt: 0.1 });
+        assert!(!game.boards[0].tiles[7].unwrap().sprite.visible);
+        assert!(!game.boards[0].tiles[8].unwrap().sprite.visible);
+        assert!(game.boards[0].tiles[0].unwrap().sprite.visible);
+        assert_eq!(game.render_sprites(1.0).len(), shown - 2);
+        for _ in 0..4 {
+            game.update(&UpdateArgs { dt: 0.25 });
+        }
+        assert!(game.pattern_clear.is_none());
+        let tile = game.boards[0].tiles[7].unwrap();
+        assert!(tile.blink.is_none());
+        assert!(tile.sprite.visible);
+    }
+
+    #[test]
+    fn whack_spawns_labels() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(2).unwrap();
+        whack_index(&mut game, 2);
+        game.whack_cursor();
+        let labels: Vec<&str> = game.labels().iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(labels, vec!["+1", "MISS"]);
+        assert_eq!(game.labels()[0].pos, game.boards[0].cell_center(2));
+        assert_eq!(game.labels()[0].colour, colours::WHITE);
+        assert_eq!(game.labels()[1].colour, colours::RED);
+    }
+
+    #[test]
+    fn labels_tick_and_expire() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.whack_cursor();
+        let start = game.labels()[0].pos;
+        game.update(&UpdateArgs { dt: 0.25 });
+        let label = game.labels()[0].clone();
+        assert!(label.pos.y < start.y);
+        assert_eq!(label.ttl, gobs::LABEL_DURATION - 0.25);
+        let mut renderer = RecordingRenderer::default();
+        game.draw(&mut renderer);
+        assert_eq!(renderer.labels, vec![(label.faded_colour(), "MISS".to_string())]);
+        game.whack_cursor();
+        assert_eq!(game.labels().len(), 2);
+        game.update(&UpdateArgs { dt: 0.25 });
+        game.update(&UpdateArgs { dt: 0.25 });
+        assert_eq!(game.labels().len(), 1);
+        game.reset();
+        assert!(game.labels().is_empty());
+    }
+
+    #[test]
+    fn time_scale_slows_spawns() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(0.9);
+        for _ in 0..4 {
+            game.update(&UpdateArgs { dt: 0.25 });
+        }
+        assert_eq!(game.spawns, 1);
+
+        let mut slow = make_manager();
+        slow.set_time_scale(0.5);
+        slow.state = GameState::Playing;
+        slow.tile_timer.reset_with(0.9);
+        for _ in 0..4 {
+            slow.update(&UpdateArgs { dt: 0.25 });
+        }
+        assert_eq!(slow.spawns, 0);
+        assert_eq!(slow.elapsed, 0.5);
+        for _ in 0..4 {
+            slow.update(&UpdateArgs { dt: 0.25 });
+        }
+        assert_eq!(slow.spawns, 1);
+    }
+
+    #[test]
+    fn time_scale_clamped() {
+        let mut game = make_manager();
+        assert_eq!(game.time_scale(), 1.0);
+        game.set_time_scale(0.0);
+        assert_eq!(game.time_scale(), MIN_TIME_SCALE);
+        game.set_time_scale(-2.0);
+        assert_eq!(game.time_scale(), MIN_TIME_SCALE);
+        game.set_time_scale(100.0);
+        assert_eq!(game.time_scale(), MAX_TIME_SCALE);
+        game.set_time_scale(std::f64::NAN);
+        assert_eq!(game.time_scale(), MAX_TIME_SCALE);
+        game.set_time_scale(0.5);
+        assert_eq!(game.time_scale(), 0.5);
+    }
+
+    #[test]
+    fn custom_theme() {
+        let theme = colours::Theme {
+            background: colours::BLACK,
+            tile: colours::GREEN,
+            tile_border: colours::CYAN,
+            wall: colours::WHITE,
+            cursor: colours::MAGENTA,
+            ..colours::Theme::default()
+        };
+        let mut game = make_multi_manager(2);
+        game.set_theme(theme);
+        assert_eq!(game.theme(), &theme);
+        game.boards[0].add_tile_at(0).unwrap();
+        game.boards[1].add_tile_at(4).unwrap();
+        game.boards[0].add_wall(8);
+        let tile = game.boards[0].tiles[0].unwrap().sprite;
+        assert_eq!(tile.colour, colours::GREEN);
+        assert_eq!(tile.border.map(|b| b.0), Some(colours::CYAN));
+        assert_eq!(game.boards[1].tiles[4].unwrap().sprite.colour, colours::GREEN);
+        assert_eq!(game.cursor.colour, colours::MAGENTA);
+        assert_eq!(game.cursor.border.map(|b| b.0), Some(colours::MAGENTA));
+        assert_eq!(game.boards[0].wall_sprites()[0].colour, colours::WHITE);
+        let mut renderer = RecordingRenderer::default();
+        game.draw(&mut renderer);
+        assert_eq!(renderer.clears, vec![colours::BLACK]);
+    }
+
+    #[test]
+    fn custom_background() {
+        let mut game = make_manager();
+        assert_eq!(game.background(), colours::BLUE);
+        game.set_background(colours::GREEN);
+        assert_eq!(game.background(), colours::GREEN);
+        assert_eq!(game.theme().tile, colours::Theme::default().tile);
+        let mut renderer = RecordingRenderer::default();
+        game.draw(&mut renderer);
+        assert_eq!(renderer.clears, vec![colours::GREEN]);
+    }
+
+    #[test]
+    fn cycle_theme_wraps() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(3).unwrap();
+        game.state = GameState::Ready;
+        let presets = colours::Theme::presets();
+        for i in 1..(presets.len() + 1) {
+            game.input(Key::T);
+            let theme = presets[i % presets.len()];
+            assert_eq!(game.theme(), &theme);
+            assert_eq!(game.boards[0].tiles[3].unwrap().sprite.colour, theme.tile);
+            assert_eq!(game.cursor.colour, theme.cursor);
+        }
+        assert_eq!(game.theme(), &colours::Theme::default());
+        assert_eq!(game.state, GameState::Ready);
+    }
+
+    #[test]
+    fn default_spawn_curve() {
+        let mut game = make_manager();
+        assert_eq!(game.spawn_interval(), 3.0);
+        game.score = 50;
+        assert_eq!(game.spawn_interval(), 2.0);
+        game.score = 500;
+        assert_eq!(game.spawn_interval(), 1.0);
+        game.apply_difficulty(Difficulty::Hard);
+        assert_eq!(game.spawn_interval(), 0.05);
+    }
+
+    #[test]
+    fn constant_spawn_curve() {
+        let mut game = make_manager();
+        game.spawn_curve = Some(Box::new(|_| 0.7));
+        game.state = GameState::Playing;
+        for &score in &[0, 10, 99, 1000] {
+            game.score = score;
+            assert_eq!(game.spawn_interval(), 0.7);
+            game.tile_timer.reset_with(0.01);
+            game.update(&UpdateArgs { dt: 0.02 });
+            assert_eq!(game.tile_timer.remaining(), 0.7);
+        }
+    }
+
+    /// A `GameEvents` observer that records the events it is sent.
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        spawned: Vec<(u64, usize)>,
+        whacked: Vec<(u64, usize)>,
+        hits: Vec<(gobs::TileKind, u32)>,
+        spawn_times: Vec<(usize, f64)>,
+    }
+
+    impl GameEvents for std::rc::Rc<std::cell::RefCell<RecordingObserver>> {
+        fn on_tile_spawned(&mut self, id: u64, index: usize) {
+            self.borrow_mut().spawned.push((id, index));
+        }
+
+        fn on_whack(&mut self, id: u64, index: usize) {
+            self.borrow_mut().whacked.push((id, index));
+        }
+
+        fn on_hit(&mut self, kind: gobs::TileKind, points: u32) {
+            self.borrow_mut().hits.push((kind, points));
+        }
+
+        fn on_spawn(&mut self, index: usize, elapsed: f64) {
+            self.borrow_mut().spawn_times.push((index, elapsed));
+        }
+    }
+
+    #[test]
+    fn observers_see_spawns() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let play = |seed: usize| {
+            let events = Rc::new(RefCell::new(RecordingObserver::default()));
+            let mut game = make_manager();
+            game.boards[0] = gobs::Board::seeded(game.boards[0].length, seed);
+            game.add_observer(Box::new(events.clone()));
+            game.restart();
+            for frame in 0..60 {
+                game.update(&UpdateArgs { dt: 0.1 });
+                if frame % 7 == 0 {
+                    game.whack_cursor();
+                }
+            }
+            let spawn_times = events.borrow().spawn_times.clone();
+            spawn_times
+        };
+        let spawn_times = play(42);
+        assert!(spawn_times.len() > 5);
+        assert!(spawn_times.iter().all(|&(i, _)| i < 9));
+        assert!(spawn_times.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(spawn_times.iter().all(|&(_, t)| t > 0.0 && t <= 6.0 + 1e-9));
+        assert_eq!(play(42), spawn_times);
+    }
+
+    #[test]
+    fn observers_see_hit_kinds() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let events = Rc::new(RefCell::new(RecordingObserver::default()));
+        let mut game = make_manager();
+        game.add_observer(Box::new(events.clone()));
+        game.scoring = Box::new(scoring::ComboBonus { every: 2 });
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].spawn_pattern(gobs::Pattern::Row(0));
+        game.boards[0].add_tile_at(4).unwrap();
+        whack_index(&mut game, 1);
+        whack_index(&mut game, 4);
+        game.whack_cursor();
+        assert_eq!(events.borrow().hits,
+                   vec![(gobs::TileKind::Pattern, 1), (gobs::TileKind::Normal, 2)]);
+        assert_eq!(events.borrow().whacked.len(), 2);
+    }
+
+    #[test]
+    fn observers_see_tile_ids() {
+        use std::cell::RefCell;
+        use std::collections::HashSet;
+        use std::rc::Rc;
+
+        let events = Rc::new(RefCell::new(RecordingObserver::default()));
+        let mut game = make_manager();
+        game.add_observer(Box::new(events.clone()));
+        game.pattern_frequency = 0;
+        game.restart();
+        game.tile_timer.reset_with(0.0);
+        game.update(&UpdateArgs { dt: 0.1 });
+        let (id, i) = events.borrow().spawned[0];
+        assert_eq!(game.boards[0].tile_id(i), Some(id));
+        assert!(game.render_sprites(1.0).iter().any(|s| s.id == id));
+        assert_eq!(game.cursor.id, 0);
+        whack_index(&mut game, i);
+        assert_eq!(events.borrow().whacked, vec![(id, i)]);
+        game.whack_cursor();
+        assert_eq!(events.borrow().spawned.len(), 2);
+
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        fill_board(&mut game);
+        let ids: HashSet<u64> = game.boards[0].iter_tiles().map(|(_, s)| s.id).collect();
+        assert_eq!(ids.len(), 9);
+        assert!(events.borrow().spawned.iter().all(|&(id, _)| !ids.contains(&id)));
+        whack_index(&mut game, 4);
+        let last = *events.borrow().whacked.last().unwrap();
+        assert_eq!(last.1, 4);
+        assert!(ids.contains(&last.0));
+        assert!(game.boards[0].iter_tiles().all(|(_, s)| s.id != last.0));
+    }
+
+    #[test]
+    fn score_log() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(0).unwrap();
+        game.boards[0].add_tile_at(8).unwrap();
+        game.update(&UpdateArgs { dt: 0.1 });
+        whack_index(&mut game, 0);
+        game.update(&UpdateArgs { dt: 0.2 });
+        whack_index(&mut game, 8);
+        let log = game.score_log();
+        assert_eq!(log.len(), 2);
+        assert_eq!((log[0].points, log[0].tile_index), (1, 0));
+        assert_eq!((log[1].points, log[1].tile_index), (1, 8));
+        assert!(log[0].elapsed < log[1].elapsed);
+        game.reset();
+        assert!(game.score_log().is_empty());
+        assert_eq!(game.elapsed, 0.0);
+    }
+
+    #[test]
+    fn accuracy_without_attempts() {
+        let game = make_manager();
+        assert_eq!(game.accuracy(), 0.0);
+    }
+
+    #[test]
+    fn accuracy_all_hits() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(0).unwrap();
+        game.boards[0].add_tile_at(4).unwrap();
+        whack_index(&mut game, 0);
+        whack_index(&mut game, 4);
+        assert_eq!((game.hits, game.attempts), (2, 2));
+        assert_eq!(game.accuracy(), 1.0);
+    }
+
+    #[test]
+    fn accuracy_mixed() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(0).unwrap();
+        for _ in 0..4 {
+            whack_index(&mut game, 0);
+            game.boards[0].clear_board();
+        }
+        assert_eq!((game.hits, game.attempts), (1, 4));
+        assert_eq!(game.accuracy(), 0.25);
+        game.reset();
+        assert_eq!(game.accuracy(), 0.0);
+    }
 
-    use super::*;
+    #[test]
+    fn whack_threshold() {
+        let mut game = make_manager();
+        game.free_cursor = true;
+        game.boards[0].add_tile_at(4).unwrap();
+        let size = game.cursor.width;
+        game.cursor.pos = gobs::Vec2D::new(200.0 - (size / 2.0), 200.0 - (size / 2.0));
+        game.whack_threshold = 0.3;
+        game.whack_cursor();
+        assert!(game.boards[0].tiles[4].is_some());
+        assert_eq!(game.hits, 0);
+        game.boards[0].clear_board();
+        game.boards[0].add_tile_at(4).unwrap();
+        game.whack_threshold = 0.2;
+        game.whack_cursor();
+        assert!(game.boards[0].tiles[4].is_none());
+        assert_eq!(game.hits, 1);
+    }
 
-    fn make_manager() -> GameManager {
-        const WINDOW_XY: f64 = 300.0;
-        let window: glutin_window::GlutinWindow =
-            piston::window::WindowSettings::new("WHACK!", [WINDOW_XY as u32, WINDOW_XY as u32])
-                .exit_on_esc(true)
-                .build()
-                .unwrap();
-        GameManager::new(WINDOW_XY, 3.0, 1.0)
+    #[test]
+    fn cursor_spins_after_hit() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(10.0);
+        let cursor_rotation = |game: &GameManager| {
+            game.get_sprites().iter().find(|s| s.layer == gobs::CURSOR_LAYER).unwrap().rotation
+        };
+        assert_eq!(cursor_rotation(&game), 0.0);
+        game.boards[0].add_tile_at(4).unwrap();
+        game.whack_cursor();
+        assert_eq!(game.hits, 1);
+        game.update(&UpdateArgs { dt: CURSOR_SPIN_TIME / 2.0 });
+        assert!(cursor_rotation(&game) > 0.0);
+        assert_eq!(game.cursor.rotation, 0.0);
+        game.update(&UpdateArgs { dt: CURSOR_SPIN_TIME / 2.0 });
+        assert_eq!(cursor_rotation(&game), 0.0);
     }
 
     #[test]
-    fn get_sprites() {
+    fn game_summary() {
+        let mut game = make_manager();
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        for &i in &[0, 4, 8] {
+            game.boards[0].add_tile_at(i).unwrap();
+            whack_index(&mut game, i);
+        }
+        game.update(&UpdateArgs { dt: 0.2 });
+        game.boards[0].clear_board();
+        whack_index(&mut game, 4);
+        game.boards[0].clear_board();
+        game.boards[0].add_tile_at(2).unwrap();
+        whack_index(&mut game, 2);
+        assert_eq!(game.combo(), 1);
+        let summary = game.summary();
+        assert_eq!(summary,
+                   GameSummary {
+                       score: game.score,
+                       high_score: game.high_score,
+                       accuracy: 0.8,
+                       elapsed: 0.2,
+                       max_combo: 3,
+                   });
+        assert_eq!(summary.score, 4);
+        assert_eq!(summary.high_score, 4);
+        game.restart();
+        assert_eq!(game.summary().score, 0);
+        assert_eq!(game.summary().max_combo, 0);
+        assert_eq!(game.summary().high_score, 4);
+    }
+
+    #[test]
+    fn lose_screen_shows_summary() {
+        let mut game = make_manager();
+        let mut playing = RecordingRenderer::default();
+        game.draw(&mut playing);
+        assert!(playing.labels.is_empty());
+        game.state = GameState::Lose;
+        let mut lost = RecordingRenderer::default();
+        game.draw(&mut lost);
+        let text = format!("You lose! {}", game.summary());
+        assert_eq!(lost.labels, vec![(game.theme.hud, text)]);
+    }
+
+    #[test]
+    fn animation_independent_of_frame_rate() {
+        let mut slow = make_manager();
+        let mut fast = make_manager();
+        for game in [&mut slow, &mut fast].iter_mut() {
+            game.state = GameState::Playing;
+            game.tile_timer.reset_with(100.0);
+            game.boards[0].add_tile_at(4).unwrap();
+            game.whack_cursor();
+        }
+        slow.update(&UpdateArgs { dt: 0.1 });
+        for _ in 0..4 {
+            fast.update(&UpdateArgs { dt: 0.025 });
+        }
+        let (a, b) = (slow.render_sprites(1.0)[0], fast.render_sprites(1.0)[0]);
+        assert!((a.colour[3] - b.colour[3]).abs() < 1e-6);
+        assert!(a.colour[3] > 0.0 && a.colour[3] < 1.0);
+    }
+
+    #[test]
+    fn render_sprites_interpolate() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.free_cursor = true;
+        game.cursor.velocity = gobs::Vec2D::new(100.0, 0.0);
+        let start = game.cursor.pos;
+        game.update(&UpdateArgs { dt: 0.1 });
+        let cursor_at = |game: &GameManager, alpha: f64| game.render_sprites(alpha)[0].pos;
+        assert_eq!(cursor_at(&game, 1.0), game.cursor.pos);
+        assert!((cursor_at(&game, 0.0) - start).length() < 1e-9);
+        let halfway = start + gobs::Vec2D::new(5.0, 0.0);
+        assert!((cursor_at(&game, 0.5) - halfway).length() < 1e-9);
+    }
+
+    #[test]
+    fn time_bonus_scoring() {
+        let mut game = make_manager();
+        game.scoring = Box::new(scoring::TimeBonus {
+            max_bonus: 8,
+            duration: 10.0,
+        });
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(0).unwrap();
+        whack_index(&mut game, 0);
+        for _ in 0..20 {
+            game.update(&UpdateArgs { dt: 0.25 });
+        }
+        game.boards[0].add_tile_at(8).unwrap();
+        whack_index(&mut game, 8);
+        let points: Vec<u32> = game.score_log().iter().map(|e| e.points).collect();
+        assert!(points[0] > points[1]);
+        assert_eq!(points, vec![9, 5]);
+        assert_eq!(game.score, 14);
+    }
+
+    #[test]
+    fn default_controller_buttons() {
+        let map = ControllerButtonMap::default();
+        let mapped: Vec<Vec<Action>> = (0..16)
+            .map(|b| map_controller_button(ControllerButton::new(0, b), &map))
+            .collect();
+        assert_eq!(mapped[11], vec![Action::MoveUp]);
+        assert_eq!(mapped[12], vec![Action::MoveDown]);
+        assert_eq!(mapped[13], vec![Action::MoveLeft]);
+        assert_eq!(mapped[14], vec![Action::MoveRight]);
+        assert_eq!(mapped[0], vec![Action::Whack, Action::Start]);
+        assert_eq!(mapped.iter().filter(|a| !a.is_empty()).count(), 5);
+    }
+
+    #[test]
+    fn custom_controller_buttons() {
+        let map = ControllerButtonMap {
+            up: 3,
+            down: 0,
+            left: 2,
+            right: 1,
+            whack: 5,
+        };
+        let press = |b| map_controller_button(ControllerButton::new(1, b), &map);
+        assert_eq!(press(3), vec![Action::MoveUp]);
+        assert_eq!(press(5), vec![Action::Whack, Action::Start]);
+        assert!(press(11).is_empty());
+    }
+
+    #[test]
+    fn controller_ignores_rebound_keys() {
+        let mut game = make_manager();
+        let mut keymap = KeyMap::default();
+        keymap.unbind(Key::Space, Action::Whack);
+        keymap.unbind(Key::Space, Action::Start);
+        keymap.unbind(Key::Right, Action::MoveRight);
+        game.set_keymap(keymap);
+        let map = game.controller_button_map;
+        let press = |game: &mut GameManager, b| game.controller_press(ControllerButton::new(0, b));
+        press(&mut game, map.whack);
+        assert_eq!(game.state, GameState::Ready);
+        press(&mut game, map.whack);
+        assert_eq!(game.state, GameState::Playing);
+        game.tile_timer.reset_with(100.0);
+        press(&mut game, map.right);
+        assert_eq!(game.cursor_cell(), Some(5));
+        game.boards[0].add_tile_at(5).unwrap();
+        game.update(&UpdateArgs { dt: 0.2 });
+        press(&mut game, map.whack);
+        assert_eq!(game.score, 1);
+        assert!(!game.boards[0].is_occupied(5));
+    }
+
+    #[test]
+    fn prompts_name_bound_keys() {
+        let mut game = make_manager();
+        assert_eq!(game.prompt(),
+                   Some("CHOOSE A DIFFICULTY WITH UP AND DOWN, THEN PRESS SPACE!".to_string()));
+        let mut keymap = KeyMap::default();
+        keymap.unbind(Key::Space, Action::Start);
+        keymap.bind(Key::Return, Action::Start);
+        keymap.unbind(Key::T, Action::CycleTheme);
+        game.set_keymap(keymap);
+        game.input(Key::Return);
+        assert_eq!(game.state, GameState::Ready);
+        assert_eq!(game.prompt(),
+                   Some("PRESS RETURN TO START, OR (NO KEY) TO CHANGE THE COLOURS!".to_string()));
+        game.input(Key::Return);
+        assert_eq!(game.prompt(), None);
+    }
+
+    #[test]
+    fn multi_whack() {
+        let mut game = make_manager();
+        game.free_cursor = true;
+        game.boards[0].add_tile_at(4).unwrap();
+        game.boards[0].add_tile_at(5).unwrap();
+        game.cursor.pos = gobs::Vec2D::new(200.0 - (game.cursor.width / 4.0), 150.0);
+        game.multi_whack = true;
+        game.whack_cursor();
+        assert!(game.boards[0].tiles[4].is_none());
+        assert!(game.boards[0].tiles[5].is_none());
+        assert_eq!(game.score, 2);
+        assert_eq!(game.score_log().len(), 2);
+        assert_eq!(game.hits, 1);
+    }
+
+    #[test]
+    fn straddling_whack_is_deterministic() {
+        for _ in 0..10 {
+            let mut game = make_manager();
+            game.free_cursor = true;
+            game.boards[0].add_tile_at(4).unwrap();
+            game.boards[0].add_tile_at(5).unwrap();
+            game.cursor.set_center(gobs::Vec2D::new(200.0, 150.0));
+            game.whack_cursor();
+            assert!(game.boards[0].tiles[4].is_none());
+            assert!(game.boards[0].tiles[5].is_some());
+            assert_eq!(game.score_log()[0].tile_index, 4);
+        }
+        let mut game = make_manager();
+        game.free_cursor = true;
+        game.boards[0].add_tile_at(5).unwrap();
+        game.cursor.pos = gobs::Vec2D::new(200.0 - game.cursor.width, 150.0);
+        game.whack_cursor();
+        assert_eq!(game.hits, 0);
+        assert!(game.boards[0].tiles[5].is_some());
+    }
+
+    #[test]
+    fn whack_forgiveness() {
+        let mut game = make_manager();
+        game.free_cursor = true;
+        game.boards[0].add_tile_at(4).unwrap();
+        let size = game.cursor.width;
+        game.cursor.pos = gobs::Vec2D::new(100.0 - size - 2.0, 150.0);
+        let drawn = game.cursor;
+        game.whack_cursor();
+        assert_eq!(game.hits, 0);
+        game.boards[0].clear_board();
+        game.boards[0].add_tile_at(4).unwrap();
+        game.whack_forgiveness = 5.0;
+        game.whack_cursor();
+        assert_eq!(game.hits, 1);
+        assert!(game.boards[0].tiles[4].is_none());
+        assert_eq!(game.cursor, drawn);
+    }
+
+    #[test]
+    fn circular_cursor_misses_corner() {
+        let mut game = make_manager();
+        game.free_cursor = true;
+        game.boards[0].add_tile_at(0).unwrap();
+        game.cursor = gobs::Sprite::circle(gobs::Vec2D::new(107.0, 107.0), 9.0, colours::YELLOW);
+        game.whack_cursor();
+        assert_eq!(game.hits, 0);
+        assert!(game.boards[0].tiles[0].is_some());
+        game.boards[0].clear_board();
+        game.boards[0].add_tile_at(0).unwrap();
+        game.cursor = gobs::Sprite::circle(gobs::Vec2D::new(105.0, 105.0), 9.0, colours::YELLOW);
+        game.whack_cursor();
+        assert_eq!(game.hits, 1);
+        assert!(game.boards[0].tiles[0].is_none());
+    }
+
+    #[test]
+    fn whack_picks_largest_overlap() {
+        let mut game = make_manager();
+        game.free_cursor = true;
+        game.boards[0].add_tile_at(4).unwrap();
+        game.boards[0].add_tile_at(5).unwrap();
+        game.cursor.pos = gobs::Vec2D::new(200.0 - (game.cursor.width / 4.0), 150.0);
+        game.whack_cursor();
+        assert!(game.boards[0].tiles[4].is_some());
+        assert!(game.boards[0].tiles[5].is_none());
+        game.cursor.pos.x = 200.0 - (game.cursor.width * 0.75);
+        game.whack_cursor();
+        assert!(game.boards[0].tiles[4].is_none());
+        assert_eq!(game.hits, 2);
+    }
+
+    #[test]
+    fn tile_timer_reset_on_loss() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.grace_period = 0.0;
+        game.tile_timer.reset_with(0.05);
+        fill_board(&mut game);
+        game.update(&UpdateArgs { dt: 0.25 });
+        assert_eq!(game.state, GameState::Lose);
+        assert_eq!(game.tile_timer.remaining(), 0.0);
+        game.update(&UpdateArgs { dt: 0.25 });
+        assert_eq!(game.tile_timer.remaining(), 0.0);
+        game.restart();
+        assert_eq!(game.tile_timer.remaining(), 0.0);
+    }
+
+    #[test]
+    fn held_whack_needs_release() {
+        let mut game = make_manager();
+        game.require_key_release = true;
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(4).unwrap();
+        game.key_press(Key::Space);
+        game.boards[0].add_tile_at(4).unwrap();
+        for _ in 0..5 {
+            game.key_press(Key::Space);
+        }
+        assert_eq!(game.score, 1);
+        assert_eq!(game.attempts, 1);
+        game.key_release(Key::Space);
+        game.key_press(Key::Space);
+        assert_eq!(game.score, 2);
+    }
+
+    #[test]
+    fn held_controller_whack_needs_release() {
+        let mut game = make_manager();
+        game.require_key_release = true;
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        let whack = ControllerButton::new(0, game.controller_button_map.whack);
+        game.boards[0].add_tile_at(4).unwrap();
+        game.controller_press(whack);
+        game.boards[0].add_tile_at(4).unwrap();
+        for _ in 0..5 {
+            game.controller_press(whack);
+        }
+        assert_eq!(game.score, 1);
+        assert_eq!(game.attempts, 1);
+        game.key_press(Key::Space);
+        assert_eq!(game.score, 2);
+        game.controller_release(whack);
+        game.boards[0].add_tile_at(4).unwrap();
+        game.controller_press(whack);
+        assert_eq!(game.score, 3);
+    }
+
+    #[test]
+    fn repeated_presses_without_release() {
+        let mut game = make_manager();
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        for _ in 0..3 {
+            game.boards[0].add_tile_at(4).unwrap();
+            game.key_press(Key::Space);
+        }
+        assert_eq!(game.score, 3);
+    }
+
+    #[test]
+    fn starts_in_menu() {
+        let game = make_manager();
+        assert_eq!(game.state, GameState::Menu);
+        assert_eq!(game.selected_difficulty, Difficulty::Normal);
+    }
+
+    #[test]
+    fn menu_navigation() {
+        let mut game = make_manager();
+        game.input(Key::Down);
+        assert_eq!(game.selected_difficulty, Difficulty::Hard);
+        game.input(Key::Down);
+        assert_eq!(game.selected_difficulty, Difficulty::Easy);
+        game.input(Key::Up);
+        assert_eq!(game.selected_difficulty, Difficulty::Hard);
+        game.input(Key::Up);
+        game.input(Key::Up);
+        assert_eq!(game.selected_difficulty, Difficulty::Easy);
+        game.input(Key::Left);
+        assert_eq!(game.selected_difficulty, Difficulty::Easy);
+        assert_eq!(game.state, GameState::Menu);
+    }
+
+    #[test]
+    fn menu_applies_difficulty() {
+        let mut game = make_manager();
+        game.input(Key::Down);
+        game.input(Key::Space);
+        assert_eq!(game.state, GameState::Ready);
+        assert_eq!((game.max_time, game.min_time), Difficulty::Hard.tile_times());
+        game.input(Key::Space);
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn display_difficulty() {
+        assert_eq!(Difficulty::Easy.to_string(), "Easy");
+        assert_eq!(Difficulty::Normal.to_string(), "Normal");
+        assert_eq!(Difficulty::Hard.to_string(), "Hard");
+    }
+
+    #[test]
+    fn restart_game() {
+        let mut game = make_manager();
+        game.boards[0].add_tile();
+        game.state = GameState::Lose;
+        game.score = 20;
+        game.restart();
+        assert_eq!(game.state, GameState::Playing);
+        assert_eq!(game.score, 0);
+        assert_eq!(game.boards[0].free_positions().len(), 9);
+        game.state = GameState::Lose;
+        game.input(Key::R);
+        assert_eq!(game.state, GameState::Playing);
+        game.state = GameState::Lose;
+        game.input(Key::Space);
+        assert_eq!(game.state, GameState::Ready);
+    }
+
+    #[test]
+    fn hammered_movement_stays_on_board() {
+        let keys = [Key::Left, Key::Up, Key::Right, Key::Down, Key::Left];
+        for &(rows, cols) in &[(1, 1), (3, 3), (4, 4), (5, 5), (2, 5), (5, 2)] {
+            let mut game = make_manager();
+            let origin = gobs::Vec2D::new(20.0, 30.0);
+            game.boards[0] = gobs::Board::with_rows_cols(game.boards[0].length, rows, cols);
+            game.boards[0].origin = origin;
+            game.set_cursor_cell(centre_cell(&game.boards[0]));
+            for &key in &keys {
+                for _ in 0..25 {
+                    game.handle_movement(key);
+                    let cell = game.cursor_cell().expect("cursor left the board");
+                    assert_eq!(game.cursor.center(), game.boards[0].cell_center(cell));
+                    let rect = game.cursor.get_rect();
+                    assert!((rect[0] >= origin.x) && (rect[1] >= origin.y));
+                    assert!(rect[0] + rect[2] <= origin.x + game.boards[0].width());
+                    assert!(rect[1] + rect[3] <= origin.y + game.boards[0].height());
+                }
+            }
+            assert_eq!(game.cursor_cell(), Some((rows - 1) * cols));
+        }
+    }
+
+    #[test]
+    fn hammered_movement_stops_at_walls() {
+        let mut game = make_manager();
+        game.boards[0].add_wall(3);
+        game.boards[0].add_wall(1);
+        game.handle_movement(Key::Down);
+        for &key in &[Key::Left, Key::Up] {
+            for _ in 0..10 {
+                game.handle_movement(key);
+                let cell = game.cursor_cell().unwrap();
+                assert!(!game.boards[0].is_wall(cell));
+            }
+        }
+        assert_eq!(game.cursor_cell(), Some(6));
+        for &key in &[Key::Right, Key::Up] {
+            for _ in 0..10 {
+                game.handle_movement(key);
+            }
+        }
+        assert_eq!(game.cursor_cell(), Some(2));
+    }
+
+    #[test]
+    fn cursor_edge_behaviour_defaults_to_clamp() {
+        let mut game = make_manager();
+        assert_eq!(game.cursor_edge_behaviour(), CursorEdgeBehaviour::Clamp);
+        game.set_cursor_edge_behaviour(CursorEdgeBehaviour::Wrap);
+        assert_eq!(game.cursor_edge_behaviour(), CursorEdgeBehaviour::Wrap);
+    }
+
+    #[test]
+    fn edge_behaviour_on_every_edge() {
+        // From each edge cell of a 3x4 board, the cell after one move off the edge.
+        let cases = [(1, Key::Up, 1, 9),
+                     (9, Key::Down, 9, 1),
+                     (4, Key::Left, 4, 7),
+                     (7, Key::Right, 7, 4),
+                     (0, Key::Up, 0, 8),
+                     (0, Key::Left, 0, 3),
+                     (3, Key::Up, 3, 11),
+                     (3, Key::Right, 3, 0),
+                     (8, Key::Down, 8, 0),
+                     (8, Key::Left, 8, 11),
+                     (11, Key::Down, 11, 3),
+                     (11, Key::Right, 11, 8)];
+        for &(start, key, clamped, wrapped) in &cases {
+            for &(behaviour, expected) in &[(CursorEdgeBehaviour::Clamp, clamped),
+                                            (CursorEdgeBehaviour::Wrap, wrapped)] {
+                let mut game = make_manager();
+                game.boards[0] = gobs::Board::with_rows_cols(game.boards[0].length, 3, 4);
+                game.boards[0].origin = gobs::Vec2D::new(20.0, 30.0);
+                game.set_cursor_edge_behaviour(behaviour);
+                game.set_cursor_cell(start);
+                game.handle_movement(key);
+                assert_eq!(game.cursor_cell(), Some(expected), "{:?} {:?}", key, behaviour);
+                assert_eq!(game.cursor.center(), game.boards[0].cell_center(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn wrapped_cursor_stays_centred() {
+        let mut game = make_manager();
+        game.boards[0] = gobs::Board::with_rows_cols(100.0 / 3.0, 3, 7);
+        game.boards[0].origin = gobs::Vec2D::new(0.1, 0.7);
+        game.set_cursor_edge_behaviour(CursorEdgeBehaviour::Wrap);
+        game.set_cursor_cell(0);
+        for &key in &[Key::Right, Key::Down, Key::Left, Key::Up] {
+            for _ in 0..50 {
+                game.handle_movement(key);
+                let cell = game.cursor_cell().expect("cursor left the board");
+                assert_eq!(game.cursor.center(), game.boards[0].cell_center(cell));
+            }
+        }
+        assert_eq!(game.cursor_cell(), Some(0));
+    }
+
+    #[test]
+    fn wrapping_stops_at_walls() {
+        let mut game = make_manager();
+        game.set_cursor_edge_behaviour(CursorEdgeBehaviour::Wrap);
+        game.boards[0].add_wall(5);
+        game.set_cursor_cell(3);
+        game.handle_movement(Key::Left);
+        assert_eq!(game.cursor_cell(), Some(3));
+        game.handle_movement(Key::Up);
+        game.handle_movement(Key::Up);
+        assert_eq!(game.cursor_cell(), Some(6));
+    }
+
+    #[test]
+    fn would_hit_finds_tile_under_cursor() {
+        let mut game = make_manager();
+        assert_eq!(game.would_hit(), None);
+        game.boards[0].add_tile_at(3).unwrap();
+        assert_eq!(game.would_hit(), None);
+        game.boards[0].add_tile_at(4).unwrap();
+        assert_eq!(game.would_hit(), Some(4));
+        game.handle_movement(Key::Left);
+        assert_eq!(game.would_hit(), Some(3));
+        game.free_cursor = true;
+        game.cursor.pos.x += game.boards[0].cell_size() * 0.4;
+        assert_eq!(game.would_hit(), Some(3));
+        game.cursor.pos.x += game.boards[0].cell_size() * 0.2;
+        assert_eq!(game.would_hit(), Some(4));
+        game.whack_threshold = 1.0;
+        assert_eq!(game.would_hit(), None);
+    }
+
+    #[test]
+    fn would_hit_matches_whack() {
+        let mut game = make_manager();
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].spawn_pattern(gobs::Pattern::Row(1));
+        let tiles = game.boards[0].tiles.clone();
+        let score = game.score;
+        for _ in 0..3 {
+            assert_eq!(game.would_hit(), Some(4));
+        }
+        assert_eq!(game.boards[0].tiles, tiles);
+        assert_eq!(game.score, score);
+        assert_eq!(game.attempts, 0);
+        game.whack_cursor();
+        assert!(!game.boards[0].is_occupied(4));
+        assert_eq!(game.would_hit(), None);
+    }
+
+    #[test]
+    fn undo_restores_board_and_score() {
+        let mut game = make_manager();
+        game.practice_mode = true;
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(4).unwrap();
+        game.boards[0].add_tile_at(0).unwrap();
+        game.update(&UpdateArgs { dt: 0.2 });
+        let tiles = game.boards[0].tiles.clone();
+        game.score = 7;
+        game.whack_cursor();
+        assert_eq!(game.score, 8);
+        assert!(!game.boards[0].is_occupied(4));
+        assert!(game.undo());
+        assert_eq!(game.boards[0].tiles, tiles);
+        assert_eq!(game.score, 7);
+        assert!(game.score_log().is_empty());
+        assert!(!game.undo());
+    }
+
+    #[test]
+    fn tiles_needing_several_hits() {
+        let mut game = make_manager();
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(4).unwrap();
+        game.boards[0].tiles.get_mut(4).unwrap().hits_required = 3;
+        for hits_left in &[2, 1] {
+            game.whack_cursor();
+            assert_eq!(game.boards[0].tiles[4].map(|t| t.hits_required), Some(*hits_left));
+            assert_eq!(game.score, 0);
+        }
+        game.whack_cursor();
+        assert!(!game.boards[0].is_occupied(4));
+        assert_eq!(game.score, 1);
+        assert_eq!(game.hits, 3);
+        assert_eq!(game.boards[0].free_positions().len(), 9);
+    }
+
+    #[test]
+    fn tiles_needing_more_hits_are_darker() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(4).unwrap();
+        game.boards[0].tiles.get_mut(4).unwrap().age = 1.0;
+        let colour = game.boards[0].tiles[4].unwrap().sprite.colour;
+        let drawn = |game: &GameManager| game.render_sprites(1.0)[0].colour;
+        assert_eq!(drawn(&game), colour);
+        game.boards[0].tiles.get_mut(4).unwrap().hits_required = 2;
+        assert_eq!(drawn(&game), colours::darken(colour, gobs::EXTRA_HIT_DARKEN));
+        game.whack_cursor();
+        assert_eq!(drawn(&game), colour);
+    }
+
+    #[test]
+    fn undo_restores_partly_hit_tile() {
+        let mut game = make_manager();
+        game.practice_mode = true;
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(4).unwrap();
+        game.boards[0].tiles.get_mut(4).unwrap().hits_required = 2;
+        let tiles = game.boards[0].tiles.clone();
+        game.whack_cursor();
+        assert!(game.undo());
+        assert_eq!(game.boards[0].tiles, tiles);
+        game.whack_cursor();
+        game.whack_cursor();
+        assert!(game.undo());
+        assert_eq!(game.boards[0].tiles[4].map(|t| t.hits_required), Some(1));
+        assert_eq!(game.score, 0);
+    }
+
+    #[test]
+    fn undo_restores_combo_and_pattern() {
+        let mut game = make_manager();
+        game.practice_mode = true;
+        game.scoring = Box::new(scoring::ComboBonus { every: 1 });
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        let placed = game.boards[0].spawn_pattern(gobs::Pattern::Row(1));
+        game.pattern_clear = Some(PatternClear::new(placed, 3.0));
+        whack_index(&mut game, 3);
+        whack_index(&mut game, 4);
+        let (score, summary) = (game.score, game.summary());
+        for _ in 0..3 {
+            whack_index(&mut game, 5);
+            assert_eq!(game.score, score + 4 + game.pattern_bonus);
+            assert!(game.pattern_clear.is_none());
+            assert_eq!(game.boards[0].fading_tiles().len(), 3);
+            assert!(game.undo());
+            assert_eq!((game.score, game.summary()), (score, summary));
+            assert_eq!((game.combo(), game.attempts, game.hits), (2, 2, 2));
+            assert_eq!(game.pattern_clear.as_ref().map(|c| c.whacked.len()), Some(2));
+            assert_eq!(game.boards[0].fading_tiles().len(), 2);
+        }
+    }
+
+    #[test]
+    fn undo_needs_practice_mode() {
+        let mut game = make_manager();
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(4).unwrap();
+        game.whack_cursor();
+        assert!(!game.undo());
+        assert!(!game.boards[0].is_occupied(4));
+        assert_eq!(game.score, 1);
+    }
+
+    #[test]
+    fn undo_history_is_limited() {
+        let mut game = make_manager();
+        game.practice_mode = true;
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        for _ in 0..(MAX_UNDO_HISTORY + 2) {
+            game.boards[0].add_tile_at(4).unwrap();
+            game.whack_cursor();
+        }
+        let mut undone = 0;
+        while game.undo() {
+            game.boards[0].remove_tile(4);
+            undone += 1;
+        }
+        assert_eq!(undone, MAX_UNDO_HISTORY);
+        assert_eq!(game.score, 2);
+    }
+
+    #[test]
+    fn wasd_matches_arrow_keys() {
+        let pairs = [(Key::W, Key::Up), (Key::A, Key::Left), (Key::S, Key::Down),
+                     (Key::D, Key::Right)];
+        for &(letter, arrow) in &pairs {
+            let (mut by_letter, mut by_arrow) = (make_manager(), make_manager());
+            let start = by_letter.cursor.center();
+            by_letter.handle_movement(letter);
+            by_arrow.handle_movement(arrow);
+            assert!(by_letter.cursor_cell() != Some(4));
+            assert_eq!(by_letter.cursor_cell(), by_arrow.cursor_cell());
+            assert_eq!(by_letter.cursor.center() - start, by_arrow.cursor.center() - start);
+        }
+        let mut game = make_manager();
+        for &key in &[Key::W, Key::Right, Key::S, Key::Left, Key::A, Key::Down, Key::D] {
+            game.handle_movement(key);
+        }
+        assert_eq!(game.cursor_cell(), Some(7));
+    }
+
+    #[test]
+    fn hjkl_movement() {
+        let moves = [(Key::H, 3), (Key::J, 7), (Key::K, 1), (Key::L, 5)];
+        for &(key, cell) in &moves {
+            let mut game = make_manager();
+            game.handle_movement(key);
+            assert_eq!(game.cursor_cell(), Some(cell));
+            assert_eq!(game.cursor.center(), game.boards[0].cell_center(cell));
+        }
+        let mut game = make_manager();
+        for &key in &[Key::B, Key::G, Key::I, Key::M, Key::N, Key::O, Key::X, Key::Z] {
+            game.handle_movement(key);
+            assert_eq!(game.cursor_cell(), Some(4));
+        }
+    }
+
+    #[test]
+    fn mouse_moves_cursor() {
+        let mut game = make_manager();
+        game.mouse_moved(gobs::Vec2D::new(20.0, 20.0));
+        assert_eq!(game.cursor_cell(), Some(4));
+        game.restart();
+        let cases = [((20.0, 250.0), Some(6)),
+                     ((100.0, 100.0), Some(4)),
+                     ((99.99, 200.0), Some(6)),
+                     ((299.0, 0.0), Some(2)),
+                     ((300.0, 150.0), None),
+                     ((-1.0, 150.0), None),
+                     ((150.0, 310.0), None)];
+        for &((x, y), cell) in &cases {
+            game.set_cursor_cell(1);
+            game.mouse_moved(gobs::Vec2D::new(x, y));
+            assert_eq!(game.cursor_cell(), Some(cell.unwrap_or(1)), "({}, {})", x, y);
+            assert_eq!(game.cursor.center(), game.boards[0].cell_center(cell.unwrap_or(1)));
+        }
+        game.boards[0].add_wall(0);
+        game.mouse_moved(gobs::Vec2D::new(50.0, 50.0));
+        assert_eq!(game.cursor_cell(), Some(1));
+    }
+
+    #[test]
+    fn mouse_clicks_whack() {
+        let mut game = make_manager();
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(3).unwrap();
+        game.boards[0].add_tile_at(4).unwrap();
+        game.mouse_clicked(gobs::Vec2D::new(50.0, 150.0));
+        assert_eq!(game.cursor_cell(), Some(3));
+        assert!(!game.boards[0].is_occupied(3));
+        game.mouse_clicked(gobs::Vec2D::new(100.0, 100.0));
+        assert!(!game.boards[0].is_occupied(4));
+        assert_eq!((game.attempts, game.hits), (2, 2));
+        for &(x, y) in &[(300.0, 100.0), (-5.0, -5.0), (1000.0, 1000.0)] {
+            game.mouse_clicked(gobs::Vec2D::new(x, y));
+        }
+        assert_eq!(game.attempts, 2);
+        assert_eq!(game.cursor_cell(), Some(4));
+        game.mouse_clicked(gobs::Vec2D::new(250.0, 250.0));
+        assert_eq!(game.cursor_cell(), Some(8));
+        assert_eq!((game.attempts, game.hits, game.combo()), (3, 2, 0));
+        assert_eq!(game.boards[0].free_positions().len(), 8);
+    }
+
+    #[test]
+    fn digits_whack_cells() {
+        let digits = [Key::D7, Key::D8, Key::D9, Key::D4, Key::D5, Key::D6, Key::D1, Key::D2,
+                      Key::D3];
+        let mut game = make_manager();
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        fill_board(&mut game);
+        for (i, &digit) in digits.iter().enumerate() {
+            game.input(digit);
+            assert_eq!(game.cursor_cell(), Some(i));
+            assert!(!game.boards[0].is_occupied(i));
+            assert_eq!(game.boards[0].free_positions().len(), i + 1);
+        }
+        assert_eq!(game.hits, 9);
+        assert_eq!(game.score, 9);
+        game.boards[0].add_tile_at(8).unwrap();
+        game.input(Key::NumPad3);
+        assert_eq!(game.score, 10);
+    }
+
+    #[test]
+    fn digit_on_empty_cell_misses() {
+        let mut game = make_manager();
+        game.restart();
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(0).unwrap();
+        game.input(Key::NumPad7);
+        assert_eq!(game.combo(), 1);
+        game.input(Key::NumPad5);
+        assert_eq!(game.cursor_cell(), Some(4));
+        assert_eq!((game.attempts, game.hits, game.combo()), (2, 1, 0));
+        assert_eq!(game.labels().last().map(|l| l.text.as_str()), Some("MISS"));
+        assert_eq!(game.boards[0].free_positions().len(), 8);
+    }
+
+    #[test]
+    fn digits_need_three_by_three_board() {
+        let mut game = make_manager();
+        game.boards[0] = gobs::Board::with_grid(game.boards[0].length, 4);
+        game.set_cursor_cell(centre_cell(&game.boards[0]));
+        game.restart();
+        game.boards[0].add_tile_at(0).unwrap();
+        game.input(Key::D7);
+        assert_eq!(game.cursor_cell(), Some(10));
+        assert!(game.boards[0].is_occupied(0));
+        assert_eq!(game.attempts, 0);
+    }
+
+    #[test]
+    fn rebound_whack_key() {
         let mut game = make_manager();
+        let mut keymap = KeyMap::default();
+        keymap.unbind(Key::Space, Action::Whack);
+        keymap.bind(Key::Return, Action::Whack);
+        game.set_keymap(keymap);
+        assert_eq!(game.keymap().keys(Action::Whack), vec![Key::Return]);
+        game.input(Key::Space);
+        game.input(Key::Space);
+        assert_eq!(game.state, GameState::Playing);
+        game.tile_timer.reset_with(100.0);
+        game.boards[0].add_tile_at(4).unwrap();
+        game.input(Key::Space);
+        assert!(game.boards[0].is_occupied(4));
+        assert_eq!(game.attempts, 0);
+        game.input(Key::Return);
+        assert!(!game.boards[0].is_occupied(4));
+        assert_eq!(game.hits, 1);
+    }
+
+    #[test]
+    fn rebound_game_keys() {
+        let mut keymap = KeyMap::new();
+        keymap.bind(Key::Return, Action::Start);
+        keymap.bind(Key::N, Action::MoveDown);
+        keymap.bind(Key::Backspace, Action::Restart);
+        let mut game = make_manager();
+        game.set_keymap(keymap);
+        game.input(Key::Down);
+        game.input(Key::N);
+        assert_eq!(game.selected_difficulty, Difficulty::Hard);
+        game.input(Key::Space);
+        assert_eq!(game.state, GameState::Menu);
+        game.input(Key::Return);
+        game.input(Key::Return);
+        assert_eq!(game.state, GameState::Playing);
+        game.state = GameState::Lose;
+        game.input(Key::R);
+        assert_eq!(game.state, GameState::Lose);
+        game.input(Key::Backspace);
+        assert_eq!(game.state, GameState::Playing);
+    }
+
+    #[test]
+    fn rebound_movement_keys() {
+        let mut game = make_manager();
+        let mut keymap = KeyMap::default();
+        keymap.unbind(Key::W, Action::MoveUp);
+        keymap.unbind(Key::Left, Action::MoveLeft);
+        keymap.bind(Key::Left, Action::MoveRight);
+        game.set_keymap(keymap);
+        game.handle_movement(Key::W);
+        assert_eq!(game.cursor_cell(), Some(4));
+        game.handle_movement(Key::Left);
+        assert_eq!(game.cursor_cell(), Some(5));
+        game.set_keymap(KeyMap::new());
+        game.handle_movement(Key::Down);
+        assert_eq!(game.cursor_cell(), Some(5));
+    }
+
+    #[test]
+    fn wall_blocks_movement() {
+        let mut game = make_manager();
+        game.boards[0].add_wall(5);
+        game.handle_movement(Key::Right);
+        assert_eq!(game.cursor_cell(), Some(4));
+        game.handle_movement(Key::Up);
+        assert_eq!(game.cursor_cell(), Some(1));
+        game.handle_movement(Key::Down);
+        game.handle_movement(Key::Left);
+        assert_eq!(game.cursor_cell(), Some(3));
+        game.handle_movement(Key::Right);
+        game.handle_movement(Key::Down);
+        assert_eq!(game.cursor_cell(), Some(7));
+    }
+
+    #[test]
+    fn movement_stays_on_board() {
+        let mut game = make_manager();
+        for &key in &[Key::Down, Key::Right, Key::Up, Key::Left] {
+            for _ in 0..4 {
+                game.handle_movement(key);
+                assert!(game.cursor_cell().is_some());
+            }
+        }
+        assert_eq!(game.cursor_cell(), Some(0));
+        assert_eq!(game.cursor.center(), game.boards[0].cell_center(0));
+    }
+
+    #[test]
+    fn hex_movement_stays_on_board() {
+        let mut game = make_manager();
+        let origin = game.boards[0].origin;
+        game.boards[0] = gobs::Board::hex(1, game.boards[0].length);
+        game.boards[0].origin = origin;
+        game.set_cursor_cell(centre_cell(&game.boards[0]));
+        assert_eq!(game.cursor_cell(), Some(3));
+        game.handle_movement(Key::Up);
+        assert_eq!(game.cursor_cell(), Some(3));
+        let moves = [(Key::Q, 2), (Key::W, 0), (Key::E, 1), (Key::D, 4), (Key::S, 6),
+                     (Key::A, 5)];
+        for &(key, cell) in &moves {
+            game.set_cursor_cell(3);
+            game.handle_movement(key);
+            assert_eq!(game.cursor_cell(), Some(cell));
+            let centre = game.boards[0].cell_center(cell);
+            assert!((game.cursor.center().x - centre.x).abs() < 1e-9);
+            assert!((game.cursor.center().y - centre.y).abs() < 1e-9);
+            game.handle_movement(key);
+            assert_eq!(game.cursor_cell(), Some(cell));
+        }
+    }
+
+    #[test]
+    fn walls_are_rendered_black() {
+        let mut game = make_manager();
+        game.boards[0].add_wall(0);
+        game.boards[0].add_tile();
         let sprites = game.get_sprites();
-        assert_eq!(sprites.len(), 1);
-        game.board.add_tile();
+        assert_eq!(sprites.len(), 3);
+        assert_eq!(sprites[0].colour, colours::BLACK);
+        assert_eq!(sprites[0].get_rect(), [0.0, 0.0, 100.0, 100.0]);
+    }
+
+    #[test]
+    fn whacked_tiles_fade() {
+        let mut game = make_manager();
+        game.boards[0].add_tile_at(4).unwrap();
+        game.whack_cursor();
         let sprites = game.get_sprites();
         assert_eq!(sprites.len(), 2);
+        assert_eq!(sprites[0].pos, gobs::Vec2D::new(100.0, 100.0));
+        game.state = GameState::Playing;
+        game.tile_timer.reset_with(100.0);
+        game.update(&UpdateArgs { dt: gobs::FADE_DURATION / 2.0 });
+        assert_eq!(game.get_sprites()[0].colour[3], 0.5);
+        game.update(&UpdateArgs { dt: gobs::FADE_DURATION });
+        assert_eq!(game.get_sprites().len(), 1);
     }
 
     #[test]
@@ -299,9 +4266,9 @@ mod tests {
         let game1 = make_manager();
         let mut game2 = make_manager();
         assert!(game1 == game2);
-        game2.cursor.pos.x = 50.0;
-        game2.board.add_tile();
-        game2.board.add_tile();
+        game2.handle_movement(Key::Left);
+        game2.boards[0].add_tile();
+        game2.boards[0].add_tile();
         game2.state = GameState::Lose;
         game2.score = 200;
         assert!(game1 != game2);
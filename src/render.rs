@@ -0,0 +1,231 @@
+//! Contains the drawing backends used to render **Whack!**
+use std::f64::consts::PI;
+use graphics::{self, Context, Transformed};
+use opengl_graphics::{GlGraphics, GlyphCache};
+use colours::Colour;
+use gobs::{Label, Shape, Sprite};
+use textures::TextureStore;
+
+/// The font that labels are drawn in, relative to the directory the game is run from.
+pub const FONT_PATH: &str = "assets/DejaVuSans-Bold.ttf";
+
+/// The size, in points, that labels are drawn at.
+pub const LABEL_FONT_SIZE: u32 = 16;
+
+/// A backend that the game can be drawn with.
+pub trait Renderer {
+    /// Fills the whole window with colour `c`.
+    fn clear(&mut self, c: Colour);
+
+    /// Fills the rectangle `rect`, given as `[x, y, width, height]`, with colour `c`.
+    fn rect(&mut self, c: Colour, rect: [f64; 4]);
+
+    /// Fills the ellipse that fits inside `rect`, given as `[x, y, width, height]`, with colour
+    /// `c`.
+    fn ellipse(&mut self, c: Colour, rect: [f64; 4]);
+
+    /// Draws the outline of the rectangle `rect` with lines `2 * radius` thick, centred on its
+    /// edges.
+    ///
+    /// By default the outline is drawn as the four filled rectangles from `border_rects`.
+    fn border(&mut self, c: Colour, rect: [f64; 4], radius: f64) {
+        for &edge in &border_rects(rect, radius) {
+            self.rect(c, edge);
+        }
+    }
+
+    /// Draws the outline of the ellipse that fits inside `rect` with a line `2 * radius`
+    /// thick, centred on the ellipse.
+    ///
+    /// By default the outline is approximated by squares spaced around the ellipse.
+    fn ellipse_border(&mut self, c: Colour, rect: [f64; 4], radius: f64) {
+        const SEGMENTS: usize = 32;
+        let (rx, ry) = (rect[2] / 2.0, rect[3] / 2.0);
+        let (cx, cy) = (rect[0] + rx, rect[1] + ry);
+        for i in 0..SEGMENTS {
+            let angle = 2.0 * PI * i as f64 / SEGMENTS as f64;
+            let (x, y) = (cx + (rx * angle.cos()), cy + (ry * angle.sin()));
+            self.rect(c, [x - radius, y - radius, 2.0 * radius, 2.0 * radius]);
+        }
+    }
+
+    /// Draws `sprite` with the method for its `Shape`: filled in its colour if it is `filled`
+    /// and then outlined if it has a `border`.
+    ///
+    /// By default the sprite's rotation and texture are ignored.
+    fn sprite(&mut self, sprite: &Sprite) {
+        if sprite.filled {
+            match sprite.shape {
+                Shape::Rect => self.rect(sprite.colour, sprite.get_rect()),
+                Shape::Circle { radius } => {
+                    self.ellipse(sprite.colour, circle_rect(sprite, radius))
+                }
+            }
+        }
+        if let Some((colour, thickness)) = sprite.border {
+            let edge = sprite.inflated(-thickness / 2.0);
+            match edge.shape {
+                Shape::Rect => self.border(colour, edge.get_rect(), thickness / 2.0),
+                Shape::Circle { radius } => {
+                    self.ellipse_border(colour, circle_rect(&edge, radius), thickness / 2.0)
+                }
+            }
+        }
+    }
+
+    /// Draws the text of `label` at its position in its faded colour.
+    ///
+    /// Drawing text needs a font, so by default labels are not drawn.
+    fn label(&mut self, _label: &Label) {}
+}
+
+/// Returns the four rectangles, each `[x, y, width, height]`, that make up the outline of
+/// `rect` drawn with lines `2 * radius` thick, centred on its edges.
+///
+/// The top and bottom edges span the full width of the outline, while the left and right
+/// edges fit between them.
+///
+/// # Examples
+///
+/// ```
+/// use whack::render::border_rects;
+///
+/// let edges = border_rects([10.0, 10.0, 100.0, 50.0], 1.0);
+/// assert_eq!(edges[0], [9.0, 9.0, 102.0, 2.0]);
+/// assert_eq!(edges[3], [109.0, 11.0, 2.0, 48.0]);
+/// ```
+pub fn border_rects(rect: [f64; 4], radius: f64) -> [[f64; 4]; 4] {
+    let (x, y, width, height) = (rect[0], rect[1], rect[2], rect[3]);
+    let thickness = 2.0 * radius;
+    [[x - radius, y - radius, width + thickness, thickness],
+     [x - radius, y + height - radius, width + thickness, thickness],
+     [x - radius, y + radius, thickness, height - thickness],
+     [x + width - radius, y + radius, thickness, height - thickness]]
+}
+
+/// Returns the rectangle, as `[x, y, width, height]`, that the circle of `radius` at the
+/// centre of `sprite` fits inside.
+fn circle_rect(sprite: &Sprite, radius: f64) -> [f64; 4] {
+    let center = sprite.center();
+    [center.x - radius, center.y - radius, 2.0 * radius, 2.0 * radius]
+}
+
+/// Draws to an OpenGL window through a `GlGraphics` backend.
+///
+/// Sprites with a texture are drawn with the image from `textures` stretched over their
+/// rectangle. If the texture is missing they are drawn in their colour instead. Labels are
+/// only drawn once a font has been given with `GlRenderer::glyphs`.
+pub struct GlRenderer<'a> {
+    context: Context,
+    gl: &'a mut GlGraphics,
+    textures: &'a TextureStore,
+    glyphs: Option<&'a mut GlyphCache<'static>>,
+}
+
+impl<'a> GlRenderer<'a> {
+    /// Returns a `GlRenderer` that draws with `gl`, using the transform and draw state of
+    /// `context` and the images in `textures`.
+    pub fn new(context: Context,
+               gl: &'a mut GlGraphics,
+               textures: &'a TextureStore)
+               -> GlRenderer<'a> {
+        GlRenderer {
+            context: context,
+            gl: gl,
+            textures: textures,
+            glyphs: None,
+        }
+    }
+
+    /// Draws labels in the font loaded into `glyphs`, see `FONT_PATH`.
+    pub fn glyphs(mut self, glyphs: &'a mut GlyphCache<'static>) -> GlRenderer<'a> {
+        self.glyphs = Some(glyphs);
+        self
+    }
+}
+
+impl<'a> Renderer for GlRenderer<'a> {
+    fn clear(&mut self, c: Colour) {
+        graphics::clear(c, self.gl);
+    }
+
+    fn rect(&mut self, c: Colour, rect: [f64; 4]) {
+        graphics::rectangle(c, rect, self.context.transform, self.gl);
+    }
+
+    fn ellipse(&mut self, c: Colour, rect: [f64; 4]) {
+        graphics::ellipse(c, rect, self.context.transform, self.gl);
+    }
+
+    fn border(&mut self, c: Colour, rect: [f64; 4], radius: f64) {
+        graphics::Rectangle::new_border(c, radius)
+            .draw(rect, &self.context.draw_state, self.context.transform, self.gl);
+    }
+
+    fn sprite(&mut self, sprite: &Sprite) {
+        let transform = sprite.render_transform(self.context.transform);
+        let draw_state = self.context.draw_state;
+        let texture = sprite.texture.and_then(|id| self.textures.get(id));
+        match (sprite.filled, texture, sprite.shape) {
+            (false, _, _) => (),
+            (true, Some(texture), _) => {
+                graphics::Image::new()
+                    .rect(sprite.get_rect())
+                    .draw(texture, &draw_state, transform, self.gl)
+            }
+            (true, None, Shape::Rect) => {
+                graphics::rectangle(sprite.colour, sprite.get_rect(), transform, self.gl)
+            }
+            (true, None, Shape::Circle { radius }) => {
+                graphics::ellipse(sprite.colour, circle_rect(sprite, radius), transform, self.gl)
+            }
+        }
+        if let Some((colour, thickness)) = sprite.border {
+            let edge = sprite.inflated(-thickness / 2.0);
+            match edge.shape {
+                Shape::Rect => {
+                    graphics::Rectangle::new_border(colour, thickness / 2.0)
+                        .draw(edge.get_rect(), &draw_state, transform, self.gl)
+                }
+                Shape::Circle { radius } => {
+                    graphics::Ellipse::new_border(colour, thickness / 2.0)
+                        .draw(circle_rect(&edge, radius), &draw_state, transform, self.gl)
+                }
+            }
+        }
+    }
+
+    fn label(&mut self, label: &Label) {
+        if let Some(ref mut glyphs) = self.glyphs {
+            let transform = self.context.transform.trans(label.pos.x, label.pos.y);
+            graphics::text(label.faded_colour(),
+                           LABEL_FONT_SIZE,
+                           &label.text,
+                           &mut **glyphs,
+                           transform,
+                           self.gl);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn border_rects_surround_rect() {
+        let edges = border_rects([0.0, 0.0, 10.0, 20.0], 1.0);
+        assert_eq!(edges,
+                   [[-1.0, -1.0, 12.0, 2.0],
+                    [-1.0, 19.0, 12.0, 2.0],
+                    [-1.0, 1.0, 2.0, 18.0],
+                    [9.0, 1.0, 2.0, 18.0]]);
+        let area: f64 = edges.iter().map(|e| e[2] * e[3]).sum();
+        assert_eq!(area, (12.0 * 22.0) - (8.0 * 18.0));
+    }
+
+    #[test]
+    fn bundled_font_loads() {
+        assert!(GlyphCache::new(FONT_PATH).is_ok());
+    }
+}
@@ -0,0 +1,92 @@
+//! Contains the rules for how many points whacking a tile is worth in **Whack!**
+use std::fmt;
+use gobs::TileKind;
+
+/// Information about a hit that a `ScoringStrategy` can use to award points.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HitContext {
+    /// The time in seconds since play started.
+    pub elapsed: f64,
+    /// The number of whacks in a row that have hit a tile, including this one.
+    pub combo: u32,
+    /// The kind of the tile that was hit.
+    pub kind: TileKind,
+}
+
+/// A policy for how many points a hit is worth.
+pub trait ScoringStrategy: fmt::Debug {
+    /// Returns the points awarded for a hit described by `ctx`.
+    fn points_for_hit(&self, ctx: &HitContext) -> u32;
+}
+
+/// Awards one point for every hit.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Flat;
+
+impl ScoringStrategy for Flat {
+    fn points_for_hit(&self, _ctx: &HitContext) -> u32 {
+        1
+    }
+}
+
+/// Awards one point for every hit plus a bonus that shrinks from `max_bonus` at the start of
+/// play to nothing after `duration` seconds.
+///
+/// # Examples
+///
+/// ```
+/// use whack::gobs::TileKind;
+/// use whack::scoring::{HitContext, ScoringStrategy, TimeBonus};
+///
+/// let scoring = TimeBonus {
+///     max_bonus: 4,
+///     duration: 60.0,
+/// };
+/// let hit = |elapsed| HitContext { elapsed: elapsed, combo: 1, kind: TileKind::Normal };
+/// assert_eq!(scoring.points_for_hit(&hit(0.0)), 5);
+/// assert_eq!(scoring.points_for_hit(&hit(30.0)), 3);
+/// assert_eq!(scoring.points_for_hit(&hit(90.0)), 1);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TimeBonus {
+    pub max_bonus: u32,
+    pub duration: f64,
+}
+
+impl ScoringStrategy for TimeBonus {
+    fn points_for_hit(&self, ctx: &HitContext) -> u32 {
+        if self.duration <= 0.0 {
+            return 1;
+        }
+        let remaining = (1.0 - (ctx.elapsed / self.duration)).max(0.0);
+        1 + (self.max_bonus as f64 * remaining).round() as u32
+    }
+}
+
+/// Awards one point for every hit plus an extra point for every `every` hits in the current
+/// combo.
+///
+/// # Examples
+///
+/// ```
+/// use whack::gobs::TileKind;
+/// use whack::scoring::{ComboBonus, HitContext, ScoringStrategy};
+///
+/// let scoring = ComboBonus { every: 3 };
+/// let hit = |combo| HitContext { elapsed: 0.0, combo: combo, kind: TileKind::Normal };
+/// assert_eq!(scoring.points_for_hit(&hit(2)), 1);
+/// assert_eq!(scoring.points_for_hit(&hit(7)), 3);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ComboBonus {
+    pub every: u32,
+}
+
+impl ScoringStrategy for ComboBonus {
+    fn points_for_hit(&self, ctx: &HitContext) -> u32 {
+        if self.every == 0 {
+            return 1;
+        }
+        1 + (ctx.combo / self.every)
+    }
+}
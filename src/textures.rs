@@ -0,0 +1,137 @@
+//! Contains the images that sprites in **Whack!** can be drawn with.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use opengl_graphics::{Texture, TextureSettings};
+use gobs::TextureId;
+
+/// An error raised when an image cannot be loaded into a `TextureStore`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureError {
+    /// The path of the image that could not be loaded.
+    pub path: PathBuf,
+    /// Why the image could not be loaded.
+    pub reason: String,
+}
+
+impl fmt::Display for TextureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "could not load texture '{}': {}", self.path.display(), self.reason)
+    }
+}
+
+impl Error for TextureError {
+    fn description(&self) -> &str {
+        "could not load texture"
+    }
+}
+
+/// Owns the textures used by sprites, handing out a `TextureId` for each one loaded.
+///
+/// Each path is only loaded once, loading it again returns the id it was first given.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use whack::textures::TextureStore;
+///
+/// let mut store: TextureStore<usize> = TextureStore::new();
+/// let id = store.load_with("mole.png", |p: &Path| Ok(p.to_string_lossy().len())).unwrap();
+/// assert_eq!(store.get(id), Some(&8));
+/// ```
+pub struct TextureStore<T = Texture> {
+    textures: Vec<(PathBuf, T)>,
+    ids: HashMap<PathBuf, TextureId>,
+}
+
+impl<T> TextureStore<T> {
+    /// Returns an empty `TextureStore`.
+    pub fn new() -> TextureStore<T> {
+        TextureStore {
+            textures: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Loads the image at `path` with `loader`, returning the id of the new texture or a
+    /// `TextureError` with the reason given by `loader`.
+    ///
+    /// If `path` has already been loaded, its id is returned without calling `loader`.
+    pub fn load_with<P, F>(&mut self, path: P, loader: F) -> Result<TextureId, TextureError>
+        where P: AsRef<Path>,
+              F: FnOnce(&Path) -> Result<T, String>
+    {
+        let path = path.as_ref();
+        if let Some(&id) = self.ids.get(path) {
+            return Ok(id);
+        }
+        let texture = loader(path).map_err(|reason| {
+                TextureError {
+                    path: path.to_path_buf(),
+                    reason: reason,
+                }
+            })?;
+        let id = TextureId(self.textures.len());
+        self.textures.push((path.to_path_buf(), texture));
+        self.ids.insert(path.to_path_buf(), id);
+        Ok(id)
+    }
+
+    /// Returns the texture with `id`, or `None` if it is not in this `TextureStore`.
+    pub fn get(&self, id: TextureId) -> Option<&T> {
+        self.textures.get(id.0).map(|t| &t.1)
+    }
+
+    /// Returns the id of the texture loaded from `path`, if it has been loaded.
+    pub fn id_of<P: AsRef<Path>>(&self, path: P) -> Option<TextureId> {
+        self.ids.get(path.as_ref()).cloned()
+    }
+
+    /// Returns the number of textures in the `TextureStore`.
+    pub fn len(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Tests if the `TextureStore` holds no textures.
+    pub fn is_empty(&self) -> bool {
+        self.textures.is_empty()
+    }
+}
+
+impl TextureStore<Texture> {
+    /// Loads the PNG at `path` as an OpenGL texture, returning its id.
+    ///
+    /// An OpenGL context must be current, as it is once the game window has been created.
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<TextureId, TextureError> {
+        self.load_with(path, load_texture)
+    }
+
+    /// Loads every texture again, keeping their ids.
+    ///
+    /// Textures belong to the OpenGL context they were loaded in, so this must be called
+    /// whenever the window is replaced.
+    pub fn reload(&mut self) -> Result<(), TextureError> {
+        for entry in &mut self.textures {
+            entry.1 = load_texture(&entry.0).map_err(|reason| {
+                    TextureError {
+                        path: entry.0.clone(),
+                        reason: reason,
+                    }
+                })?;
+        }
+        Ok(())
+    }
+}
+
+/// Loads the PNG at `path` as an OpenGL texture.
+fn load_texture(path: &Path) -> Result<Texture, String> {
+    Texture::from_path(path, &TextureSettings::new())
+}
+
+impl<T> Default for TextureStore<T> {
+    fn default() -> TextureStore<T> {
+        TextureStore::new()
+    }
+}
@@ -0,0 +1,51 @@
+extern crate whack;
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use whack::gobs::TextureId;
+use whack::textures::TextureStore;
+
+const MOLE: &str = "assets/mole.png";
+
+fn read(path: &Path) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    File::open(path).and_then(|mut f| f.read_to_end(&mut bytes)).map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+#[test]
+fn ids_follow_load_order() {
+    let mut store: TextureStore<Vec<u8>> = TextureStore::new();
+    assert!(store.is_empty());
+    let mole = store.load_with(MOLE, read).unwrap();
+    let licence = store.load_with("LICENSE", read).unwrap();
+    assert_eq!(mole, TextureId(0));
+    assert_eq!(licence, TextureId(1));
+    assert_eq!(store.len(), 2);
+    assert_eq!(&store.get(mole).unwrap()[1..4], b"PNG");
+    assert_eq!(store.id_of(MOLE), Some(mole));
+    assert_eq!(store.get(TextureId(2)), None);
+}
+
+#[test]
+fn paths_are_only_loaded_once() {
+    let mut store: TextureStore<Vec<u8>> = TextureStore::new();
+    let first = store.load_with(MOLE, read).unwrap();
+    let again = store.load_with(MOLE, |_: &Path| -> Result<Vec<u8>, String> {
+            panic!("the texture was loaded twice")
+        })
+        .unwrap();
+    assert_eq!(first, again);
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn missing_asset_is_an_error() {
+    let mut store = TextureStore::new();
+    let err = store.load("assets/missing.png").unwrap_err();
+    assert_eq!(err.path, Path::new("assets/missing.png"));
+    assert!(err.to_string().starts_with("could not load texture 'assets/missing.png'"));
+    assert!(store.is_empty());
+    assert_eq!(store.id_of("assets/missing.png"), None);
+}